@@ -0,0 +1,385 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::mediaplayer::MediaPlayer;
+use crate::track::Track;
+
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+
+/// Interval in which the media player thread checks whether the current track has finished.
+const FINISHED_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+type Command = Box<dyn FnOnce(&mut dyn MediaPlayer) + Send>;
+
+enum Message {
+    Command(Command),
+    TrackFinished(oneshot::Sender<()>),
+}
+
+/// A [`MediaPlayer`](crate::MediaPlayer) with async methods.
+///
+/// The [`MediaPlayer`](crate::MediaPlayer) runs on a thread of its own, which executes the commands one after another.
+/// The playback is stopped when the `AsyncMediaPlayer` is dropped.
+#[derive(Debug)]
+pub struct AsyncMediaPlayer {
+    sender: Option<Sender<Message>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Returns the error of a command that could not be executed, since the media player thread has stopped.
+fn stopped() -> Error {
+    Error::AudioLibError("Media player thread stopped".to_string())
+}
+
+/// Executes the commands until the [`AsyncMediaPlayer`](crate::AsyncMediaPlayer) is dropped and notifies the waiters for the end of the current track.
+fn execute(media_player: &mut dyn MediaPlayer, receiver: &Receiver<Message>) {
+    let mut current = (media_player.playlist_index(), media_player.position());
+    let mut waiters: Vec<oneshot::Sender<()>> = Vec::new();
+    loop {
+        match receiver.recv_timeout(FINISHED_CHECK_INTERVAL) {
+            Ok(Message::Command(command)) => command(media_player),
+            Ok(Message::TrackFinished(waiter)) => waiters.push(waiter),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        let index = media_player.playlist_index();
+        let position = media_player.position();
+        // the track has finished if another one is played, also if the same is played again
+        let finished = index.is_none() || index != current.0 || position < current.1;
+        if finished {
+            for waiter in waiters.drain(..) {
+                waiter.send(()).ok();
+            }
+        }
+        current = (index, position);
+    }
+    media_player.stop().ok();
+}
+
+impl AsyncMediaPlayer {
+    pub(crate) fn open<M, F>(open: F) -> Result<Self, Error>
+    where
+        M: MediaPlayer + 'static,
+        F: FnOnce() -> Result<M, Error> + Send + 'static,
+    {
+        let (sender, receiver) = channel();
+        let (opened_sender, opened_receiver) = channel();
+        let thread = std::thread::spawn(move || match open() {
+            Ok(mut media_player) => {
+                opened_sender.send(Ok(())).ok();
+                execute(&mut media_player, &receiver);
+            }
+            Err(error) => {
+                opened_sender.send(Err(error)).ok();
+            }
+        });
+        // the thread ended without sending the result only if opening panicked
+        opened_receiver.recv().unwrap_or_else(|_| Err(stopped()))?;
+        Ok(AsyncMediaPlayer {
+            sender: Some(sender),
+            thread: Some(thread),
+        })
+    }
+
+    fn send(&self, message: Message) -> Result<(), Error> {
+        match &self.sender {
+            Some(sender) => sender.send(message).map_err(|_| stopped()),
+            None => Err(stopped()),
+        }
+    }
+
+    /// Executes the `command` on the media player thread and returns its result.
+    async fn call<T, C>(&self, command: C) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        C: FnOnce(&mut dyn MediaPlayer) -> T + Send + 'static,
+    {
+        let (result_sender, result_receiver) = oneshot::channel();
+        self.send(Message::Command(Box::new(move |media_player| {
+            result_sender.send(command(media_player)).ok();
+        })))?;
+        result_receiver.await.map_err(|_| stopped())
+    }
+
+    /// See [`MediaPlayer::play`](crate::MediaPlayer::play).
+    pub async fn play(&self, track: &Track) -> Result<(), Error> {
+        let track = track.clone();
+        self.call(move |media_player| media_player.play(&track))
+            .await?
+    }
+
+    /// See [`MediaPlayer::play_from`](crate::MediaPlayer::play_from).
+    pub async fn play_from(&self, track: &Track, position: Duration) -> Result<(), Error> {
+        let track = track.clone();
+        self.call(move |media_player| media_player.play_from(&track, position))
+            .await?
+    }
+
+    /// See [`MediaPlayer::play_playlist`](crate::MediaPlayer::play_playlist).
+    pub async fn play_playlist(&self, playlist: &[Track]) -> Result<(), Error> {
+        let playlist = playlist.to_vec();
+        self.call(move |media_player| media_player.play_playlist(&playlist))
+            .await?
+    }
+
+    /// See [`MediaPlayer::play_playlist_from`](crate::MediaPlayer::play_playlist_from).
+    pub async fn play_playlist_from(
+        &self,
+        playlist: &[Track],
+        index: usize,
+        position: Duration,
+    ) -> Result<(), Error> {
+        let playlist = playlist.to_vec();
+        self.call(move |media_player| media_player.play_playlist_from(&playlist, index, position))
+            .await?
+    }
+
+    /// See [`MediaPlayer::stop`](crate::MediaPlayer::stop).
+    pub async fn stop(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.stop()).await?
+    }
+
+    /// See [`MediaPlayer::pause`](crate::MediaPlayer::pause).
+    pub async fn pause(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.pause()).await?
+    }
+
+    /// See [`MediaPlayer::resume`](crate::MediaPlayer::resume).
+    pub async fn resume(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.resume()).await?
+    }
+
+    /// See [`MediaPlayer::toggle`](crate::MediaPlayer::toggle).
+    pub async fn toggle(&self, track: &Track) -> Result<(), Error> {
+        let track = track.clone();
+        self.call(move |media_player| media_player.toggle(&track))
+            .await?
+    }
+
+    /// See [`MediaPlayer::toggle_playlist`](crate::MediaPlayer::toggle_playlist).
+    pub async fn toggle_playlist(&self, playlist: &[Track]) -> Result<(), Error> {
+        let playlist = playlist.to_vec();
+        self.call(move |media_player| media_player.toggle_playlist(&playlist))
+            .await?
+    }
+
+    /// See [`MediaPlayer::next`](crate::MediaPlayer::next).
+    pub async fn next(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.next()).await?
+    }
+
+    /// See [`MediaPlayer::previous`](crate::MediaPlayer::previous).
+    pub async fn previous(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.previous()).await?
+    }
+
+    /// See [`MediaPlayer::shuffle`](crate::MediaPlayer::shuffle).
+    pub async fn shuffle(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.shuffle()).await?
+    }
+
+    /// See [`MediaPlayer::volume_up`](crate::MediaPlayer::volume_up).
+    pub async fn volume_up(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.volume_up()).await?
+    }
+
+    /// See [`MediaPlayer::volume_down`](crate::MediaPlayer::volume_down).
+    pub async fn volume_down(&self) -> Result<(), Error> {
+        self.call(|media_player| media_player.volume_down()).await?
+    }
+
+    /// See [`MediaPlayer::set_volume`](crate::MediaPlayer::set_volume).
+    pub async fn set_volume(&self, volume: f32) -> Result<(), Error> {
+        self.call(move |media_player| media_player.set_volume(volume))
+            .await?
+    }
+
+    /// See [`MediaPlayer::volume`](crate::MediaPlayer::volume).
+    pub async fn volume(&self) -> Result<f32, Error> {
+        self.call(|media_player| media_player.volume()).await
+    }
+
+    /// See [`MediaPlayer::set_max_volume`](crate::MediaPlayer::set_max_volume).
+    pub async fn set_max_volume(&self, max_volume: f32) -> Result<(), Error> {
+        self.call(move |media_player| media_player.set_max_volume(max_volume))
+            .await?
+    }
+
+    /// See [`MediaPlayer::max_volume`](crate::MediaPlayer::max_volume).
+    pub async fn max_volume(&self) -> Result<f32, Error> {
+        self.call(|media_player| media_player.max_volume()).await
+    }
+
+    /// See [`MediaPlayer::position`](crate::MediaPlayer::position).
+    pub async fn position(&self) -> Result<Option<Duration>, Error> {
+        self.call(|media_player| media_player.position()).await
+    }
+
+    /// See [`MediaPlayer::playlist_index`](crate::MediaPlayer::playlist_index).
+    pub async fn playlist_index(&self) -> Result<Option<usize>, Error> {
+        self.call(|media_player| media_player.playlist_index())
+            .await
+    }
+
+    /// Completes when the current track has finished, i.e. it was played until its end,
+    /// the next track was played or the playback was stopped.
+    ///
+    /// Completes immediately if no track is played.
+    pub async fn track_finished(&self) -> Result<(), Error> {
+        let (waiter, finished) = oneshot::channel();
+        self.send(Message::TrackFinished(waiter))?;
+        finished.await.map_err(|_| stopped())
+    }
+}
+
+impl Drop for AsyncMediaPlayer {
+    fn drop(&mut self) {
+        // disconnects the media player thread, which stops the playback
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    /// Plays a playlist without audio, the tracks finish when calling `next`.
+    #[derive(Default)]
+    struct MockMediaPlayer {
+        playlist: Vec<Track>,
+        index: Option<usize>,
+        stopped: Arc<Mutex<bool>>,
+    }
+
+    impl MediaPlayer for MockMediaPlayer {
+        fn play_playlist_from(
+            &mut self,
+            playlist: &[Track],
+            index: usize,
+            _position: Duration,
+        ) -> Result<(), Error> {
+            self.playlist = playlist.to_vec();
+            self.index = Some(index);
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<(), Error> {
+            self.index = None;
+            *self.stopped.lock().unwrap() = true;
+            Ok(())
+        }
+        fn pause(&mut self) -> Result<(), Error> {
+            Err(Error::AudioLibError("pause".to_string()))
+        }
+        fn resume(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn toggle_playlist(&mut self, _playlist: &[Track]) -> Result<(), Error> {
+            Ok(())
+        }
+        fn next(&mut self) -> Result<(), Error> {
+            let playlist_length = self.playlist.len();
+            self.index = self
+                .index
+                .map(|index| index + 1)
+                .filter(|index| *index < playlist_length);
+            Ok(())
+        }
+        fn previous(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn shuffle(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_volume(&mut self, _volume: f32) -> Result<(), Error> {
+            Ok(())
+        }
+        fn volume(&self) -> f32 {
+            0.5
+        }
+        fn set_max_volume(&mut self, _max_volume: f32) -> Result<(), Error> {
+            Ok(())
+        }
+        fn max_volume(&self) -> f32 {
+            1.0
+        }
+        fn position(&self) -> Option<Duration> {
+            self.index.map(|_| Duration::from_secs(1))
+        }
+        fn playlist_index(&self) -> Option<usize> {
+            self.index
+        }
+    }
+
+    fn playlist() -> Vec<Track> {
+        vec![Track::from("1.wav"), Track::from("2.wav")]
+    }
+
+    #[tokio::test]
+    async fn test_commands() {
+        let stopped = Arc::new(Mutex::new(false));
+        let mock_stopped = stopped.clone();
+        let media_player = AsyncMediaPlayer::open(move || {
+            Ok(MockMediaPlayer {
+                stopped: mock_stopped,
+                ..MockMediaPlayer::default()
+            })
+        })
+        .unwrap();
+
+        media_player.play_playlist(&playlist()).await.unwrap();
+        assert_eq!(Ok(Some(0)), media_player.playlist_index().await);
+        assert_eq!(Ok(0.5), media_player.volume().await);
+        assert_eq!(
+            Err(Error::AudioLibError("pause".to_string())),
+            media_player.pause().await
+        );
+
+        // the playback is stopped on drop
+        drop(media_player);
+        assert!(*stopped.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_track_finished() {
+        let media_player = AsyncMediaPlayer::open(|| Ok(MockMediaPlayer::default())).unwrap();
+        // nothing is played
+        media_player.track_finished().await.unwrap();
+
+        media_player.play_playlist(&playlist()).await.unwrap();
+        let track_finished = media_player.track_finished();
+        tokio::pin!(track_finished);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), &mut track_finished)
+                .await
+                .is_err()
+        );
+
+        media_player.next().await.unwrap();
+        track_finished.await.unwrap();
+        assert_eq!(Ok(Some(1)), media_player.playlist_index().await);
+
+        media_player.next().await.unwrap();
+        media_player.track_finished().await.unwrap();
+        assert_eq!(Ok(None), media_player.playlist_index().await);
+    }
+
+    #[test]
+    fn test_open_error() {
+        let result = AsyncMediaPlayer::open(|| -> Result<MockMediaPlayer, Error> {
+            Err(Error::IoError("open".to_string()))
+        });
+        assert_eq!(Error::IoError("open".to_string()), result.unwrap_err());
+    }
+}
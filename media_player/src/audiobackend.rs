@@ -0,0 +1,74 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The backend that outputs the audio of the [`MediaPlayer`](crate::MediaPlayer), see [`open_with_backend`](crate::open_with_backend).
+///
+/// Can be parsed from the strings `"device"`, `"null"` and `"wav:<path>"`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AudioBackend {
+    /// The default audio output device of the system.
+    Device,
+    /// The audio is decoded in real time and discarded, e.g. for running without audio hardware.
+    Null,
+    /// The audio is rendered in real time into a WAV file at the path (stereo, 44100 Hz, 16 bit).
+    ///
+    /// Silence is rendered while no track is played.
+    Wav(PathBuf),
+}
+
+impl FromStr for AudioBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "device" => Ok(AudioBackend::Device),
+            "null" => Ok(AudioBackend::Null),
+            _ => match s.strip_prefix("wav:") {
+                Some(path) => Ok(AudioBackend::Wav(PathBuf::from(path))),
+                None => Err(format!("Unknown audio backend: {}", s)),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for AudioBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioBackend::Device => write!(f, "device"),
+            AudioBackend::Null => write!(f, "null"),
+            AudioBackend::Wav(path) => write!(f, "wav:{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(AudioBackend::Device), "device".parse());
+        assert_eq!(Ok(AudioBackend::Null), "null".parse());
+        assert_eq!(
+            Ok(AudioBackend::Wav(PathBuf::from("out.wav"))),
+            "wav:out.wav".parse()
+        );
+        assert_eq!(
+            Err("Unknown audio backend: alsa".to_string()),
+            "alsa".parse::<AudioBackend>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for backend in &["device", "null", "wav:out.wav"] {
+            let audio_backend: AudioBackend = backend.parse().unwrap();
+            assert_eq!(*backend, format!("{}", audio_backend));
+        }
+    }
+}
@@ -5,8 +5,21 @@
 use crate::error::Error;
 use crate::track::Track;
 
+use std::time::Duration;
+
 pub(crate) trait AudioLib {
-    fn play(&self, track: &Track) -> Result<(), Error>;
+    /// Appends the track to the queue of tracks to play, skipping `start`.
+    fn play(&self, track: &Track, start: Duration) -> Result<(), Error>;
     fn stop(&mut self) -> Result<(), Error>;
+    fn pause(&self);
+    fn resume(&self);
     fn is_playing(&self) -> bool;
+    fn is_paused(&self) -> bool;
+    /// Position of the currently played track.
+    fn position(&self) -> Option<Duration>;
+    /// Number of queued tracks, including the currently played one.
+    fn queued(&self) -> usize;
+    /// Sets the volume, where `1.0` is the original volume of the tracks.
+    fn set_volume(&self, volume: f32);
+    fn volume(&self) -> f32;
 }
@@ -7,6 +7,7 @@
 //!
 //! ```rust
 //! use media_player::{open, MediaPlayer, Track};
+//! use std::time::Duration;
 //!
 //! // get media_player trait object
 //! let mut media_player = open().unwrap();
@@ -25,6 +26,36 @@
 //!
 //! // ...
 //!
+//! // the playback can be paused and resumed
+//! media_player.pause().unwrap();
+//! media_player.resume().unwrap();
+//!
+//! // toggle pauses the track if it is the current one, otherwise it is played
+//! media_player.toggle(&track2).unwrap();
+//!
+//! // a track can be played from a start to an end offset, e.g. to skip its intro, and with its own volume
+//! let track3 = Track::from("tests/rand1.wav")
+//!     .with_start(Duration::from_secs(5))
+//!     .with_end(Duration::from_secs(20))
+//!     .with_volume(0.5);
+//! media_player.play(&track3).unwrap();
+//!
+//! // a playlist plays its tracks one after another
+//! let playlist = vec![track, track2];
+//! media_player.play_playlist(&playlist).unwrap();
+//! assert_eq!(Some(0), media_player.playlist_index());
+//!
+//! // navigate in the playlist, shuffle it or change the volume
+//! media_player.next().unwrap();
+//! media_player.shuffle().unwrap();
+//! media_player.volume_down().unwrap();
+//! media_player.set_volume(0.5).unwrap();
+//!
+//! // the maximum volume can never be exceeded
+//! media_player.set_max_volume(0.8).unwrap();
+//! media_player.set_volume(1.0).unwrap();
+//! assert_eq!(0.8, media_player.volume());
+//!
 //! // you can also stop the playing of the track
 //! media_player.stop().unwrap();
 //! ```
@@ -32,6 +63,9 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "async")]
+mod asyncmediaplayer;
+mod audiobackend;
 mod audiolib;
 mod error;
 mod mediaplayer;
@@ -39,6 +73,9 @@ mod track;
 
 mod rodiolib;
 
+#[cfg(feature = "async")]
+pub use asyncmediaplayer::AsyncMediaPlayer;
+pub use audiobackend::AudioBackend;
 pub use error::Error;
 pub use mediaplayer::MediaPlayer;
 pub use track::Track;
@@ -48,6 +85,69 @@ pub use track::Track;
 /// Returns an [`AudioLibError`](crate::Error::AudioLibError) if there was
 /// an error raised in the underlying audio library.
 pub fn open() -> Result<impl MediaPlayer, Error> {
-    let audiolib = rodiolib::open()?;
+    open_with_backend(&AudioBackend::Device)
+}
+
+/// Opens the [`MediaPlayer`](crate::MediaPlayer) with the given [`AudioBackend`](crate::AudioBackend), can result in an Error.
+///
+/// The [`Null`](crate::AudioBackend::Null) and [`Wav`](crate::AudioBackend::Wav) backends need no audio hardware,
+/// the latter renders the output into a WAV file, e.g. in order to check the played audio in tests.
+///
+/// Returns an [`AudioLibError`](crate::Error::AudioLibError) if there was
+/// an error raised in the underlying audio library or an [`IoError`](crate::Error::IoError)
+/// if the WAV file cannot be created.
+///
+/// # Example
+///
+/// ```rust
+/// use media_player::{open_with_backend, AudioBackend, MediaPlayer, Track};
+///
+/// let mut media_player = open_with_backend(&AudioBackend::Null).unwrap();
+/// media_player.play(&Track::from("tests/rand1.wav")).unwrap();
+/// media_player.stop().unwrap();
+/// ```
+pub fn open_with_backend(audio_backend: &AudioBackend) -> Result<impl MediaPlayer, Error> {
+    let audiolib = rodiolib::open(audio_backend)?;
     mediaplayer::open(audiolib)
 }
+
+/// Checks that the [`Track`](crate::Track) can be played, i.e. it exists, can be read and its format can be decoded.
+///
+/// The tracks are decoded the same way for all [`AudioBackend`](crate::AudioBackend)s, therefore no audio output is needed.
+/// Returns an [`IoError`](crate::Error::IoError) if the track cannot be read or an
+/// [`AudioLibError`](crate::Error::AudioLibError) if its format is not supported.
+///
+/// # Example
+///
+/// ```rust
+/// use media_player::{check_track, Track};
+///
+/// assert!(check_track(&Track::from("tests/rand1.wav")).is_ok());
+/// assert!(check_track(&Track::from("tests/non_existing.wav")).is_err());
+/// ```
+pub fn check_track(track: &Track) -> Result<(), Error> {
+    rodiolib::decode(track)
+}
+
+/// Opens an [`AsyncMediaPlayer`](crate::AsyncMediaPlayer) with the given [`AudioBackend`](crate::AudioBackend), can result in an Error.
+///
+/// Only available with the `async` feature. Returns the same errors as [`open_with_backend`](crate::open_with_backend).
+///
+/// # Example
+///
+/// ```rust
+/// use media_player::{open_async, AudioBackend, Track};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let media_player = open_async(AudioBackend::Null).unwrap();
+/// media_player.play(&Track::from("tests/rand1.wav")).await.unwrap();
+/// // e.g. combined with other futures in select!
+/// media_player.stop().await.unwrap();
+/// media_player.track_finished().await.unwrap();
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub fn open_async(audio_backend: AudioBackend) -> Result<AsyncMediaPlayer, Error> {
+    AsyncMediaPlayer::open(move || open_with_backend(&audio_backend))
+}
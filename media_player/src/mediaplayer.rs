@@ -5,60 +5,325 @@
 use crate::audiolib::AudioLib;
 use crate::error::Error;
 use crate::track::Track;
+
+use rand::seq::SliceRandom;
+
+use std::time::Duration;
+
 /// The [`MediaPlayer`](crate::MediaPlayer) trait.
 ///
-/// Enables playing tracks and stopping the playback.
-/// When a new track is played, the playback of the old one is stopped (if still playing).
+/// Enables playing tracks or playlists of tracks, pausing, resuming and stopping the playback,
+/// navigating and shuffling the playlist and changing the volume.
+/// When a new track or playlist is played, the playback of the old one is stopped (if still playing).
+/// The tracks of a playlist are played one after another.
 pub trait MediaPlayer {
     /// Plays the [`Track`].
     ///
     /// Results in an [`IoError`](crate::Error::IoError) if the file does not exist (or any other possible file access error).
     /// Results in an [`AudioLibError`](crate::Error::AudioLibError) if there was an error raised by the underlying audio library.
-    fn play(&mut self, track: &Track) -> Result<(), Error>;
+    fn play(&mut self, track: &Track) -> Result<(), Error> {
+        self.play_playlist(std::slice::from_ref(track))
+    }
+
+    /// Plays the [`Track`] starting at the given `position`.
+    ///
+    /// The `position` is ignored if the [`Track`] is the current one and still playing.
+    /// Results in the same errors as [`play`](crate::MediaPlayer::play).
+    fn play_from(&mut self, track: &Track, position: Duration) -> Result<(), Error> {
+        self.play_playlist_from(std::slice::from_ref(track), 0, position)
+    }
+
+    /// Plays the [`Track`]s of the `playlist` one after another.
+    ///
+    /// Results in the same errors as [`play`](crate::MediaPlayer::play).
+    fn play_playlist(&mut self, playlist: &[Track]) -> Result<(), Error> {
+        self.play_playlist_from(playlist, 0, Duration::from_secs(0))
+    }
+
+    /// Plays the [`Track`]s of the `playlist` one after another, starting with the track at `index` at the given `position`.
+    ///
+    /// The `index` and `position` are ignored if the `playlist` is the current one and still playing.
+    /// Results in the same errors as [`play`](crate::MediaPlayer::play).
+    fn play_playlist_from(
+        &mut self,
+        playlist: &[Track],
+        index: usize,
+        position: Duration,
+    ) -> Result<(), Error>;
 
     /// Stops the playback.
     ///
     /// Results in an [`AudioLibError`](crate::Error::AudioLibError) if there was an error raised by the underlying audio library.
     fn stop(&mut self) -> Result<(), Error>;
+
+    /// Pauses the playback of the current track.
+    ///
+    /// Does nothing if no track is played.
+    fn pause(&mut self) -> Result<(), Error>;
+
+    /// Resumes the playback of the current, paused track.
+    ///
+    /// Does nothing if no track is paused.
+    fn resume(&mut self) -> Result<(), Error>;
+
+    /// Toggles the playback of the [`Track`].
+    ///
+    /// If the [`Track`] is the current one and still playing, the playback is paused, if it is paused, the playback is resumed.
+    /// Otherwise the [`Track`] is [`play`](crate::MediaPlayer::play)ed and may result in the same errors.
+    fn toggle(&mut self, track: &Track) -> Result<(), Error> {
+        self.toggle_playlist(std::slice::from_ref(track))
+    }
+
+    /// Toggles the playback of the `playlist`.
+    ///
+    /// Same as [`toggle`](crate::MediaPlayer::toggle) but for a playlist.
+    fn toggle_playlist(&mut self, playlist: &[Track]) -> Result<(), Error>;
+
+    /// Skips to the next track of the current playlist.
+    ///
+    /// Does nothing if no track is played or the current track is the last one.
+    fn next(&mut self) -> Result<(), Error>;
+
+    /// Goes back to the previous track of the current playlist.
+    ///
+    /// The first track of the playlist is restarted. Does nothing if no track is played.
+    fn previous(&mut self) -> Result<(), Error>;
+
+    /// Shuffles the current playlist.
+    ///
+    /// The current track continues to play, afterwards all other tracks of the playlist are played in random order.
+    /// The order is kept until a new playlist is played.
+    fn shuffle(&mut self) -> Result<(), Error>;
+
+    /// Increases the volume by one step, up to the [`max_volume`](crate::MediaPlayer::max_volume).
+    fn volume_up(&mut self) -> Result<(), Error> {
+        self.set_volume(self.volume() + VOLUME_STEP)
+    }
+
+    /// Decreases the volume by one step, down to silence.
+    fn volume_down(&mut self) -> Result<(), Error> {
+        self.set_volume(self.volume() - VOLUME_STEP)
+    }
+
+    /// Sets the volume, where `0.0` is silence and `1.0` is the original volume of the tracks.
+    ///
+    /// The volume is limited to the range from `0.0` to the [`max_volume`](crate::MediaPlayer::max_volume),
+    /// an invalid volume results in silence.
+    /// The volume is kept when new tracks are played.
+    fn set_volume(&mut self, volume: f32) -> Result<(), Error>;
+
+    /// Returns the current volume.
+    fn volume(&self) -> f32;
+
+    /// Sets the maximum volume, which can never be exceeded.
+    ///
+    /// The maximum volume is limited to the range from `0.0` to `1.0` (the original volume of the tracks), which is the default.
+    /// If the current volume is higher, it is reduced to the maximum volume.
+    fn set_max_volume(&mut self, max_volume: f32) -> Result<(), Error>;
+
+    /// Returns the maximum volume.
+    fn max_volume(&self) -> f32;
+
+    /// Returns the playback position of the current track.
+    ///
+    /// Returns [`None`](std::option::Option::None) if no track is played (anymore).
+    fn position(&self) -> Option<Duration>;
+
+    /// Returns the index of the current track in the current playlist.
+    ///
+    /// The index refers to the original order of the playlist, even if it was [`shuffle`](crate::MediaPlayer::shuffle)d.
+    ///
+    /// Returns [`None`](std::option::Option::None) if no track is played (anymore).
+    fn playlist_index(&self) -> Option<usize>;
 }
 
+/// Step by which the volume is changed by [`volume_up`](crate::MediaPlayer::volume_up) and
+/// [`volume_down`](crate::MediaPlayer::volume_down).
+const VOLUME_STEP: f32 = 0.1;
+
+/// Upper limit of the maximum volume, which is the original volume of the tracks.
+const MAX_VOLUME: f32 = 1.0;
+
 struct MediaPlayerImplementation<T: AudioLib> {
-    last_track: Option<Track>,
+    playlist: Vec<Track>,
+    /// Indices of the playlist in the order they are played.
+    order: Vec<usize>,
+    max_volume: f32,
     audio_lib: T,
 }
 
 impl<T: AudioLib> MediaPlayer for MediaPlayerImplementation<T> {
-    fn play(&mut self, track: &Track) -> Result<(), Error> {
-        if let Some(last_track) = &self.last_track {
-            if last_track != track {
+    fn play_playlist_from(
+        &mut self,
+        playlist: &[Track],
+        index: usize,
+        position: Duration,
+    ) -> Result<(), Error> {
+        if self.playlist.as_slice() != playlist {
+            if !self.playlist.is_empty() {
                 self.audio_lib.stop()?;
-                self.audio_lib.play(&track)?;
-                self.last_track = Some(track.clone());
+            }
+            self.playlist = playlist.to_vec();
+            self.order = (0..playlist.len()).collect();
+            self.queue(index, position)?;
+        } else if self.audio_lib.is_paused() {
+            self.audio_lib.resume();
+        } else if !self.audio_lib.is_playing() {
+            let start = self.order.iter().position(|&i| i == index).unwrap_or(0);
+            self.queue(start, position)?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Error> {
+        self.playlist.clear();
+        self.order.clear();
+        self.audio_lib.stop()
+    }
+
+    fn pause(&mut self) -> Result<(), Error> {
+        if self.audio_lib.is_playing() {
+            self.audio_lib.pause();
+        }
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<(), Error> {
+        if self.audio_lib.is_paused() {
+            self.audio_lib.resume();
+        }
+        Ok(())
+    }
+
+    fn toggle_playlist(&mut self, playlist: &[Track]) -> Result<(), Error> {
+        let is_current_playlist = self.playlist.as_slice() == playlist;
+        if is_current_playlist && self.audio_lib.is_playing() {
+            if self.audio_lib.is_paused() {
+                self.audio_lib.resume();
             } else {
-                if !self.audio_lib.is_playing() {
-                    self.audio_lib.play(&track)?;
+                self.audio_lib.pause();
+            }
+            Ok(())
+        } else {
+            self.play_playlist(playlist)
+        }
+    }
+
+    fn next(&mut self) -> Result<(), Error> {
+        match self.current() {
+            Some(current) if current + 1 < self.order.len() => {
+                self.audio_lib.stop()?;
+                self.queue(current + 1, Duration::from_secs(0))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn previous(&mut self) -> Result<(), Error> {
+        match self.current() {
+            Some(current) => {
+                self.audio_lib.stop()?;
+                self.queue(current.saturating_sub(1), Duration::from_secs(0))
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn shuffle(&mut self) -> Result<(), Error> {
+        match self.current() {
+            Some(current) => {
+                // the current track continues, all other tracks follow in random order
+                let current_index = self.order[current];
+                let position = self.audio_lib.position().unwrap_or_default();
+                let is_paused = self.audio_lib.is_paused();
+                let mut others: Vec<usize> = (0..self.playlist.len())
+                    .filter(|&i| i != current_index)
+                    .collect();
+                others.shuffle(&mut rand::thread_rng());
+                self.order = std::iter::once(current_index).chain(others).collect();
+                self.audio_lib.stop()?;
+                self.queue(0, position)?;
+                if is_paused {
+                    self.audio_lib.pause();
                 }
             }
+            None => self.order.shuffle(&mut rand::thread_rng()),
+        }
+        Ok(())
+    }
+
+    fn set_volume(&mut self, volume: f32) -> Result<(), Error> {
+        // an invalid volume results in silence
+        let volume = if volume.is_nan() {
+            0.0
+        } else {
+            volume.clamp(0.0, self.max_volume)
+        };
+        self.audio_lib.set_volume(volume);
+        Ok(())
+    }
+
+    fn volume(&self) -> f32 {
+        self.audio_lib.volume()
+    }
+
+    fn set_max_volume(&mut self, max_volume: f32) -> Result<(), Error> {
+        // an invalid maximum volume results in silence
+        self.max_volume = if max_volume.is_nan() {
+            0.0
         } else {
-            self.audio_lib.play(&track)?;
-            self.last_track = Some(track.clone());
+            max_volume.clamp(0.0, MAX_VOLUME)
+        };
+        if self.volume() > self.max_volume {
+            self.audio_lib.set_volume(self.max_volume);
         }
         Ok(())
     }
 
-    fn stop(&mut self) -> Result<(), Error> {
-        self.last_track = None;
-        self.audio_lib.stop()
+    fn max_volume(&self) -> f32 {
+        self.max_volume
+    }
+
+    fn position(&self) -> Option<Duration> {
+        self.audio_lib.position()
+    }
+
+    fn playlist_index(&self) -> Option<usize> {
+        self.current().map(|current| self.order[current])
     }
 }
 
 impl<T: AudioLib> MediaPlayerImplementation<T> {
     fn from(audio_lib: T) -> Result<Self, Error> {
         Ok(MediaPlayerImplementation {
-            last_track: None,
+            playlist: Vec::new(),
+            order: Vec::new(),
+            max_volume: MAX_VOLUME,
             audio_lib: audio_lib,
         })
     }
+
+    /// Returns the position of the current track in the play order.
+    fn current(&self) -> Option<usize> {
+        // the audio library queues the current and all following tracks of the play order
+        match self.audio_lib.queued() {
+            0 => None,
+            queued => self.order.len().checked_sub(queued),
+        }
+    }
+
+    /// Queues the tracks of the play order starting with `start`, which is played from `position`.
+    fn queue(&self, start: usize, position: Duration) -> Result<(), Error> {
+        for (offset, &index) in self.order.iter().skip(start).enumerate() {
+            let start_position = if offset == 0 {
+                position
+            } else {
+                Duration::from_secs(0)
+            };
+            self.audio_lib.play(&self.playlist[index], start_position)?;
+        }
+        Ok(())
+    }
 }
 
 pub(crate) fn open<T: AudioLib>(audio_lib: T) -> Result<impl MediaPlayer, Error> {
@@ -69,18 +334,90 @@ pub(crate) fn open<T: AudioLib>(audio_lib: T) -> Result<impl MediaPlayer, Error>
 #[cfg(not(tarpaulin_include))]
 mod tests {
     use super::*;
+    use std::cell::Cell;
 
     struct DummyAudioLib;
     impl AudioLib for DummyAudioLib {
-        fn play(&self, _track: &Track) -> Result<(), Error> {
+        fn play(&self, _track: &Track, _start: Duration) -> Result<(), Error> {
             Ok(())
         }
         fn stop(&mut self) -> Result<(), Error> {
             Ok(())
         }
+        fn pause(&self) {}
+        fn resume(&self) {}
         fn is_playing(&self) -> bool {
             false
         }
+        fn is_paused(&self) -> bool {
+            false
+        }
+        fn position(&self) -> Option<Duration> {
+            None
+        }
+        fn queued(&self) -> usize {
+            0
+        }
+        fn set_volume(&self, _volume: f32) {}
+        fn volume(&self) -> f32 {
+            1.0
+        }
+    }
+
+    #[derive(Default)]
+    struct StatefulAudioLib {
+        queued: Cell<usize>,
+        paused: Cell<bool>,
+        start: Cell<Duration>,
+        volume: Cell<f32>,
+    }
+    impl StatefulAudioLib {
+        fn finish_track(&self) {
+            self.queued.set(self.queued.get() - 1);
+            self.start.set(Duration::from_secs(0));
+        }
+    }
+    impl AudioLib for StatefulAudioLib {
+        fn play(&self, _track: &Track, start: Duration) -> Result<(), Error> {
+            if self.queued.get() == 0 {
+                self.start.set(start);
+            }
+            self.queued.set(self.queued.get() + 1);
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<(), Error> {
+            self.queued.set(0);
+            self.paused.set(false);
+            Ok(())
+        }
+        fn pause(&self) {
+            self.paused.set(true);
+        }
+        fn resume(&self) {
+            self.paused.set(false);
+        }
+        fn is_playing(&self) -> bool {
+            self.queued.get() > 0
+        }
+        fn is_paused(&self) -> bool {
+            self.paused.get()
+        }
+        fn position(&self) -> Option<Duration> {
+            if self.is_playing() {
+                Some(self.start.get())
+            } else {
+                None
+            }
+        }
+        fn queued(&self) -> usize {
+            self.queued.get()
+        }
+        fn set_volume(&self, volume: f32) {
+            self.volume.set(volume);
+        }
+        fn volume(&self) -> f32 {
+            self.volume.get()
+        }
     }
 
     #[test]
@@ -95,7 +432,7 @@ mod tests {
         let mut media_player = MediaPlayerImplementation::from(DummyAudioLib).unwrap();
         media_player.play(&"/path/to/track/1".into()).unwrap();
         media_player.play(&"song2".into()).unwrap();
-        assert_eq!(media_player.last_track, Some(Track::from("song2")));
+        assert_eq!(media_player.playlist, vec![Track::from("song2")]);
     }
 
     #[test]
@@ -105,9 +442,238 @@ mod tests {
             .play(&String::from("/path/to/track/1").into())
             .unwrap();
         media_player.play(&"/path/to/track/1".into()).unwrap();
-        assert_eq!(
-            media_player.last_track,
-            Some(Track::from("/path/to/track/1"))
-        );
+        assert_eq!(media_player.playlist, vec![Track::from("/path/to/track/1")]);
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        media_player.pause().unwrap();
+        assert!(!media_player.audio_lib.is_paused());
+
+        media_player.play(&"track".into()).unwrap();
+        media_player.pause().unwrap();
+        assert!(media_player.audio_lib.is_paused());
+
+        media_player.resume().unwrap();
+        assert!(!media_player.audio_lib.is_paused());
+    }
+
+    #[test]
+    fn test_play_resumes_paused_track() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        media_player.play(&"track".into()).unwrap();
+        media_player.pause().unwrap();
+        media_player.play(&"track".into()).unwrap();
+        assert!(!media_player.audio_lib.is_paused());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        media_player.toggle(&"track".into()).unwrap();
+        assert!(media_player.audio_lib.is_playing());
+        assert!(!media_player.audio_lib.is_paused());
+
+        media_player.toggle(&"track".into()).unwrap();
+        assert!(media_player.audio_lib.is_paused());
+
+        media_player.toggle(&"track".into()).unwrap();
+        assert!(!media_player.audio_lib.is_paused());
+
+        media_player.pause().unwrap();
+        media_player.toggle(&"other track".into()).unwrap();
+        assert!(!media_player.audio_lib.is_paused());
+        assert_eq!(media_player.playlist, vec![Track::from("other track")]);
+    }
+
+    #[test]
+    fn test_play_from() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        assert_eq!(None, media_player.position());
+
+        media_player
+            .play_from(&"track".into(), Duration::from_secs(42))
+            .unwrap();
+        assert_eq!(Some(Duration::from_secs(42)), media_player.position());
+
+        // position is ignored for the current track
+        media_player
+            .play_from(&"track".into(), Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(Some(Duration::from_secs(42)), media_player.position());
+
+        media_player.play(&"other track".into()).unwrap();
+        assert_eq!(Some(Duration::from_secs(0)), media_player.position());
+
+        media_player.stop().unwrap();
+        assert_eq!(None, media_player.position());
+    }
+
+    #[test]
+    fn test_play_playlist() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        let playlist: Vec<Track> = vec!["track1".into(), "track2".into(), "track3".into()];
+        assert_eq!(None, media_player.playlist_index());
+
+        media_player.play_playlist(&playlist).unwrap();
+        assert_eq!(3, media_player.audio_lib.queued());
+        assert_eq!(Some(0), media_player.playlist_index());
+
+        media_player.audio_lib.finish_track();
+        assert_eq!(Some(1), media_player.playlist_index());
+
+        // playing the current playlist again does not restart it
+        media_player.play_playlist(&playlist).unwrap();
+        assert_eq!(Some(1), media_player.playlist_index());
+
+        media_player.audio_lib.finish_track();
+        media_player.audio_lib.finish_track();
+        assert_eq!(None, media_player.playlist_index());
+    }
+
+    #[test]
+    fn test_play_playlist_from() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        let playlist: Vec<Track> = vec!["track1".into(), "track2".into(), "track3".into()];
+
+        media_player
+            .play_playlist_from(&playlist, 1, Duration::from_secs(42))
+            .unwrap();
+        assert_eq!(2, media_player.audio_lib.queued());
+        assert_eq!(Some(1), media_player.playlist_index());
+        assert_eq!(Some(Duration::from_secs(42)), media_player.position());
+
+        media_player.audio_lib.finish_track();
+        assert_eq!(Some(2), media_player.playlist_index());
+        assert_eq!(Some(Duration::from_secs(0)), media_player.position());
+    }
+
+    #[test]
+    fn test_toggle_playlist() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        let playlist: Vec<Track> = vec!["track1".into(), "track2".into()];
+
+        media_player.toggle_playlist(&playlist).unwrap();
+        assert_eq!(Some(0), media_player.playlist_index());
+        assert!(!media_player.audio_lib.is_paused());
+
+        media_player.toggle_playlist(&playlist).unwrap();
+        assert!(media_player.audio_lib.is_paused());
+    }
+
+    #[test]
+    fn test_next_and_previous() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        let playlist: Vec<Track> = vec!["track1".into(), "track2".into(), "track3".into()];
+
+        // nothing is played, nothing to do
+        media_player.next().unwrap();
+        media_player.previous().unwrap();
+        assert_eq!(None, media_player.playlist_index());
+
+        media_player
+            .play_playlist_from(&playlist, 0, Duration::from_secs(10))
+            .unwrap();
+        media_player.next().unwrap();
+        assert_eq!(Some(1), media_player.playlist_index());
+        assert_eq!(Some(Duration::from_secs(0)), media_player.position());
+        media_player.next().unwrap();
+        assert_eq!(Some(2), media_player.playlist_index());
+        // the last track is not skipped
+        media_player.next().unwrap();
+        assert_eq!(Some(2), media_player.playlist_index());
+
+        media_player.previous().unwrap();
+        assert_eq!(Some(1), media_player.playlist_index());
+        media_player.previous().unwrap();
+        media_player.previous().unwrap();
+        assert_eq!(Some(0), media_player.playlist_index());
+        assert_eq!(3, media_player.audio_lib.queued());
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        let playlist: Vec<Track> = (0..10)
+            .map(|i| Track::from(format!("track{}", i)))
+            .collect();
+
+        media_player
+            .play_playlist_from(&playlist, 3, Duration::from_secs(10))
+            .unwrap();
+        media_player.pause().unwrap();
+        media_player.shuffle().unwrap();
+
+        // the current track continues at its position, all other tracks follow
+        assert_eq!(Some(3), media_player.playlist_index());
+        assert_eq!(Some(Duration::from_secs(10)), media_player.position());
+        assert!(media_player.audio_lib.is_paused());
+        assert_eq!(10, media_player.audio_lib.queued());
+        let mut order = media_player.order.clone();
+        order.sort();
+        assert_eq!((0..10).collect::<Vec<usize>>(), order);
+
+        // playing the same playlist again keeps the order
+        let shuffled_order = media_player.order.clone();
+        media_player.play_playlist(&playlist).unwrap();
+        assert_eq!(shuffled_order, media_player.order);
+    }
+
+    #[test]
+    fn test_volume() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        media_player.audio_lib.set_volume(0.95);
+        media_player.volume_up().unwrap();
+        assert_eq!(MAX_VOLUME, media_player.audio_lib.volume());
+
+        media_player.audio_lib.set_volume(0.5);
+        media_player.volume_down().unwrap();
+        assert!((media_player.audio_lib.volume() - 0.4).abs() < f32::EPSILON);
+
+        media_player.audio_lib.set_volume(0.05);
+        media_player.volume_down().unwrap();
+        assert_eq!(0.0, media_player.audio_lib.volume());
+    }
+
+    #[test]
+    fn test_max_volume() {
+        let mut media_player =
+            MediaPlayerImplementation::from(StatefulAudioLib::default()).unwrap();
+        assert_eq!(MAX_VOLUME, media_player.max_volume());
+
+        media_player.set_volume(2.0).unwrap();
+        assert_eq!(MAX_VOLUME, media_player.volume());
+
+        // the current volume is reduced to the new maximum
+        media_player.set_max_volume(0.5).unwrap();
+        assert_eq!(0.5, media_player.volume());
+        media_player.volume_up().unwrap();
+        assert_eq!(0.5, media_player.volume());
+        media_player.set_volume(0.8).unwrap();
+        assert_eq!(0.5, media_player.volume());
+
+        media_player.set_volume(-1.0).unwrap();
+        assert_eq!(0.0, media_player.volume());
+
+        // an invalid volume results in silence, from which the volume can be increased again
+        media_player.set_volume(f32::NAN).unwrap();
+        assert_eq!(0.0, media_player.volume());
+        media_player.volume_up().unwrap();
+        assert!(media_player.volume() > 0.0);
+
+        // the maximum volume cannot exceed the original volume
+        media_player.set_max_volume(1.5).unwrap();
+        assert_eq!(MAX_VOLUME, media_player.max_volume());
     }
 }
@@ -2,62 +2,104 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
-use rodio::{OutputStream, OutputStreamHandle, Sink};
+mod output;
+mod positionsource;
+mod renderer;
 
+use output::Output;
+use positionsource::Position;
+use rodio::{Sink, Source};
+
+use std::cell::RefCell;
 use std::fs::File;
 use std::io::BufReader;
+use std::time::Duration;
 
+use crate::audiobackend::AudioBackend;
 use crate::audiolib::AudioLib;
 use crate::error::Error;
 use crate::track::Track;
 
 struct RodioLib {
     sink: Sink,
-    stream: OutputStream,
-    stream_handle: OutputStreamHandle,
-}
-
-fn create_new_triple() -> Result<(Sink, OutputStream, OutputStreamHandle), Error> {
-    let (stream, stream_handle) = OutputStream::try_default().unwrap();
-    let sink = Sink::try_new(&stream_handle)?;
-    Ok((sink, stream, stream_handle))
+    output: Output,
+    positions: RefCell<Vec<Position>>,
 }
 
 impl RodioLib {
-    fn new() -> Result<Self, Error> {
-        let (sink, stream, stream_handle) = create_new_triple()?;
+    fn new(audio_backend: &AudioBackend) -> Result<Self, Error> {
+        let output = Output::open(audio_backend)?;
+        let sink = output.new_sink()?;
 
         Ok(RodioLib {
-            sink: sink,
-            stream: stream,
-            stream_handle: stream_handle,
+            sink,
+            output,
+            positions: RefCell::new(Vec::new()),
         })
     }
 }
 
 impl AudioLib for RodioLib {
-    fn play(&self, track: &Track) -> Result<(), Error> {
+    fn play(&self, track: &Track, start: Duration) -> Result<(), Error> {
         let file = File::open(track)?;
-        let source = rodio::Decoder::new(BufReader::new(file))?;
+        // the playback never starts before the start offset of the track
+        let start = start.max(track.start());
+        let source = rodio::Decoder::new(BufReader::new(file))?
+            .skip_duration(start)
+            .amplify(track.volume());
+        let (source, position) = positionsource::from(source, start, track.end());
         self.sink.append(source);
         self.sink.play();
+        self.positions.borrow_mut().push(position);
         Ok(())
     }
     fn stop(&mut self) -> Result<(), Error> {
         self.sink.stop();
-        let (sink, stream, stream_handle) = create_new_triple()?;
+        self.positions.borrow_mut().clear();
+        self.output.reset()?;
+        let sink = self.output.new_sink()?;
+        sink.set_volume(self.sink.volume());
         self.sink = sink;
-        self.stream = stream;
-        self.stream_handle = stream_handle;
         Ok(())
     }
+    fn pause(&self) {
+        self.sink.pause();
+    }
+    fn resume(&self) {
+        self.sink.play();
+    }
     fn is_playing(&self) -> bool {
         !self.sink.empty()
     }
+    fn is_paused(&self) -> bool {
+        self.sink.is_paused()
+    }
+    fn position(&self) -> Option<Duration> {
+        // the sink contains the current and all following tracks
+        let positions = self.positions.borrow();
+        let index = positions.len().checked_sub(self.sink.len())?;
+        positions.get(index).map(Position::get)
+    }
+    fn queued(&self) -> usize {
+        self.sink.len()
+    }
+    fn set_volume(&self, volume: f32) {
+        self.sink.set_volume(volume);
+    }
+    fn volume(&self) -> f32 {
+        self.sink.volume()
+    }
+}
+
+pub(crate) fn open(audio_backend: &AudioBackend) -> Result<impl AudioLib, Error> {
+    RodioLib::new(audio_backend)
 }
 
-pub(crate) fn open() -> Result<impl AudioLib, Error> {
-    RodioLib::new()
+/// Opens the track and creates its decoder like [play](AudioLib::play), without playing it.
+pub(crate) fn decode(track: &Track) -> Result<(), Error> {
+    let file = File::open(track)?;
+    rodio::Decoder::new(BufReader::new(file))?;
+    Ok(())
 }
 
 impl From<rodio::PlayError> for Error {
@@ -78,6 +120,21 @@ impl From<rodio::DevicesError> for Error {
     }
 }
 
+impl From<cpal::DefaultStreamConfigError> for Error {
+    fn from(error: cpal::DefaultStreamConfigError) -> Self {
+        Error::AudioLibError(format!("{}", error))
+    }
+}
+
+impl From<hound::Error> for Error {
+    fn from(error: hound::Error) -> Self {
+        match error {
+            hound::Error::IoError(error) => Error::from(error),
+            _ => Error::AudioLibError(format!("{}", error)),
+        }
+    }
+}
+
 impl From<rodio::decoder::DecoderError> for Error {
     fn from(error: rodio::decoder::DecoderError) -> Self {
         Error::AudioLibError(format!("{}", error))
@@ -87,24 +144,32 @@ impl From<rodio::decoder::DecoderError> for Error {
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 mod tests {
-    use std::time::Duration;
-
     use super::*;
 
     #[test]
-    fn test_create_triple() {
-        let result = create_new_triple();
+    fn test_decode() {
+        assert_eq!(Ok(()), decode(&Track::from("tests/rand1.wav")));
+        assert_eq!(Ok(()), decode(&Track::from("tests/random.flac")));
+        assert!(matches!(
+            decode(&Track::from("tests/non_existing.wav")),
+            Err(Error::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_device_output() {
+        let result = Output::open(&AudioBackend::Device);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_is_playing() {
-        let mut rodio_lib = open().unwrap();
+        let mut rodio_lib = open(&AudioBackend::Device).unwrap();
         let is_playing = rodio_lib.is_playing();
         assert_eq!(false, is_playing);
 
         let track = Track::from("tests/rand1.wav");
-        rodio_lib.play(&track).unwrap();
+        rodio_lib.play(&track, Duration::from_secs(0)).unwrap();
         assert_eq!(true, rodio_lib.is_playing());
 
         rodio_lib.stop().unwrap();
@@ -114,12 +179,12 @@ mod tests {
     #[test]
     #[ignore = "Test of correct usage of rodio"]
     fn test_is_playing_to_the_end() {
-        let mut rodio_lib = open().unwrap();
+        let mut rodio_lib = open(&AudioBackend::Device).unwrap();
         let is_playing = rodio_lib.is_playing();
         assert_eq!(false, is_playing);
 
         let track = Track::from("tests/rand1.wav");
-        rodio_lib.play(&track).unwrap();
+        rodio_lib.play(&track, Duration::from_secs(0)).unwrap();
         assert_eq!(true, rodio_lib.is_playing());
 
         for i in 0..32 {
@@ -131,6 +196,63 @@ mod tests {
         assert_eq!(false, rodio_lib.is_playing());
     }
 
+    #[test]
+    fn test_null_backend() {
+        let mut rodio_lib = open(&AudioBackend::Null).unwrap();
+        assert!(!rodio_lib.is_playing());
+
+        let track = Track::from("tests/rand1.wav");
+        rodio_lib.play(&track, Duration::from_secs(0)).unwrap();
+        rodio_lib.set_volume(0.5);
+        assert!(rodio_lib.is_playing());
+        std::thread::sleep(Duration::from_millis(300));
+        let position = rodio_lib.position().unwrap();
+        // the track is decoded in real time
+        assert!(position >= Duration::from_millis(200), "{:?}", position);
+        assert!(position <= Duration::from_millis(600), "{:?}", position);
+
+        rodio_lib.stop().unwrap();
+        assert!(!rodio_lib.is_playing());
+        assert_eq!(0.5, rodio_lib.volume());
+    }
+
+    #[test]
+    fn test_null_backend_offsets() {
+        let rodio_lib = open(&AudioBackend::Null).unwrap();
+        let track = Track::from("tests/rand1.wav")
+            .with_start(Duration::from_secs(10))
+            .with_end(Duration::from_millis(10_200));
+        rodio_lib.play(&track, Duration::from_secs(0)).unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        let position = rodio_lib.position().unwrap();
+        assert!(position >= Duration::from_secs(10), "{:?}", position);
+        // the track ends at its end offset
+        std::thread::sleep(Duration::from_millis(400));
+        assert!(!rodio_lib.is_playing());
+    }
+
+    #[test]
+    fn test_from_default_stream_config_error() {
+        let error = Error::from(cpal::DefaultStreamConfigError::DeviceNotAvailable);
+        assert_eq!(
+            Error::AudioLibError(String::from(
+                "The requested device is no longer available. For example, it has been unplugged."
+            )),
+            error
+        );
+    }
+
+    #[test]
+    fn test_from_hound_error() {
+        let error = Error::from(hound::Error::Unsupported);
+        assert_eq!(
+            Error::AudioLibError(String::from(
+                "The wave format of the file is not supported."
+            )),
+            error
+        );
+    }
+
     #[test]
     fn test_from_play_error() {
         let rodio_error = rodio::PlayError::NoDevice;
@@ -0,0 +1,98 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{OutputStream, OutputStreamHandle, Sink, StreamError};
+
+use crate::audiobackend::AudioBackend;
+use crate::error::Error;
+use crate::rodiolib::renderer::{self, Renderer, CHANNELS, SAMPLE_RATE};
+
+/// The output of the [`Sink`](rodio::Sink)s, depending on the [`AudioBackend`](crate::AudioBackend).
+pub(crate) enum Output {
+    Device {
+        // the stream plays as long as it is not dropped
+        _stream: OutputStream,
+        stream_handle: OutputStreamHandle,
+    },
+    Rendered(Renderer),
+}
+
+impl Output {
+    pub(crate) fn open(audio_backend: &AudioBackend) -> Result<Self, Error> {
+        match audio_backend {
+            AudioBackend::Device => {
+                let device = cpal::default_host()
+                    .default_output_device()
+                    .ok_or(StreamError::NoDevice)?;
+                // rodio panics if the device supports no output format
+                device.default_output_config()?;
+                let (stream, stream_handle) = OutputStream::try_from_device(&device)?;
+                Ok(Output::Device {
+                    _stream: stream,
+                    stream_handle,
+                })
+            }
+            AudioBackend::Null => Ok(Output::Rendered(renderer::new(|_| Ok(())))),
+            AudioBackend::Wav(path) => {
+                let spec = hound::WavSpec {
+                    channels: CHANNELS,
+                    sample_rate: SAMPLE_RATE,
+                    bits_per_sample: 16,
+                    sample_format: hound::SampleFormat::Int,
+                };
+                // the file is finalized when the writer is dropped at the end of the rendering
+                let mut writer = hound::WavWriter::create(path, spec)?;
+                Ok(Output::Rendered(renderer::new(move |sample: f32| {
+                    writer.write_sample((sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16)?;
+                    Ok(())
+                })))
+            }
+        }
+    }
+
+    /// Creates a new [`Sink`](rodio::Sink) that plays on this output.
+    pub(crate) fn new_sink(&self) -> Result<Sink, Error> {
+        match self {
+            Output::Device { stream_handle, .. } => Ok(Sink::try_new(stream_handle)?),
+            Output::Rendered(renderer) => Ok(renderer.new_sink()),
+        }
+    }
+
+    /// Discards the audio that is already buffered by the output.
+    pub(crate) fn reset(&mut self) -> Result<(), Error> {
+        if let Output::Device { .. } = self {
+            *self = Output::open(&AudioBackend::Device)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_wav() {
+        let path =
+            std::env::temp_dir().join(format!("media_player_output_{}.wav", std::process::id()));
+        let mut output = Output::open(&AudioBackend::Wav(path.clone())).unwrap();
+        let sink = output.new_sink().unwrap();
+        assert!(output.reset().is_ok());
+        std::thread::sleep(Duration::from_millis(50));
+        drop(sink);
+        drop(output);
+
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(CHANNELS, reader.spec().channels);
+        assert_eq!(SAMPLE_RATE, reader.spec().sample_rate);
+        assert!(reader.duration() > 0);
+        std::fs::remove_file(&path).unwrap();
+
+        let result = Output::open(&AudioBackend::Wav("/non/existing/out.wav".into()));
+        assert!(matches!(result, Err(Error::IoError(_))));
+    }
+}
@@ -0,0 +1,164 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use rodio::{Sample, Source};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const NS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Source that counts the played duration of the wrapped source.
+///
+/// The played duration is shared with the [`Position`] that was used to create the source,
+/// since the source itself is moved into the sink.
+/// The source ends at its optional end position.
+pub(crate) struct PositionSource<S> {
+    source: S,
+    counter: Arc<SampleCounter>,
+    duration: Option<Duration>,
+}
+
+/// The playback position of a [`PositionSource`].
+#[derive(Debug, Clone)]
+pub(crate) struct Position {
+    start: Duration,
+    counter: Arc<SampleCounter>,
+}
+
+#[derive(Debug, Default)]
+struct SampleCounter {
+    samples: AtomicU64,
+    samples_per_second: AtomicU64,
+}
+
+impl Position {
+    pub(crate) fn get(&self) -> Duration {
+        let samples = self.counter.samples.load(Ordering::Relaxed);
+        let samples_per_second = self.counter.samples_per_second.load(Ordering::Relaxed);
+        if samples_per_second == 0 {
+            return self.start;
+        }
+        let nanos = u128::from(samples) * NS_PER_SECOND / u128::from(samples_per_second);
+        self.start + Duration::from_nanos(nanos as u64)
+    }
+}
+
+/// Wraps the `source` into a [`PositionSource`] that starts at position `start` and ends at position `end`.
+///
+/// The `start` must already be skipped in `source`.
+// `TakeDuration` of rodio cannot be used for the end, it miscalculates the duration of sources with several channels
+pub(crate) fn from<S>(
+    source: S,
+    start: Duration,
+    end: Option<Duration>,
+) -> (PositionSource<S>, Position)
+where
+    S: Source,
+    S::Item: Sample,
+{
+    let counter = Arc::new(SampleCounter::default());
+    let position = Position {
+        start,
+        counter: counter.clone(),
+    };
+    let duration = end.map(|end| end.saturating_sub(start));
+    (
+        PositionSource {
+            source,
+            counter,
+            duration,
+        },
+        position,
+    )
+}
+
+impl<S> Iterator for PositionSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let samples_per_second =
+            u64::from(self.source.sample_rate()) * u64::from(self.source.channels());
+        if let Some(duration) = self.duration {
+            let samples = self.counter.samples.load(Ordering::Relaxed);
+            if u128::from(samples) * NS_PER_SECOND
+                >= duration.as_nanos() * u128::from(samples_per_second)
+            {
+                return None;
+            }
+        }
+        let sample = self.source.next();
+        if sample.is_some() {
+            self.counter
+                .samples_per_second
+                .store(samples_per_second, Ordering::Relaxed);
+            self.counter.samples.fetch_add(1, Ordering::Relaxed);
+        }
+        sample
+    }
+}
+
+impl<S> Source for PositionSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.source.current_frame_len()
+    }
+    fn channels(&self) -> u16 {
+        self.source.channels()
+    }
+    fn sample_rate(&self) -> u32 {
+        self.source.sample_rate()
+    }
+    fn total_duration(&self) -> Option<Duration> {
+        self.source.total_duration()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use rodio::source::SineWave;
+
+    #[test]
+    fn test_position() {
+        // SineWave has one channel with a sample rate of 48000
+        let source = SineWave::new(440).take_duration(Duration::from_secs(1));
+        let (mut position_source, position) = from(source, Duration::from_secs(10), None);
+        assert_eq!(Duration::from_secs(10), position.get());
+
+        for _ in 0..24000 {
+            position_source.next();
+        }
+        assert_eq!(Duration::from_millis(10500), position.get());
+    }
+
+    #[test]
+    fn test_position_at_end() {
+        let source = SineWave::new(440).take_duration(Duration::from_secs(1));
+        let (position_source, position) = from(source, Duration::from_secs(0), None);
+        assert_eq!(48000, position_source.count());
+        assert_eq!(Duration::from_secs(1), position.get());
+    }
+
+    #[test]
+    fn test_position_at_end_offset() {
+        let source = SineWave::new(440).take_duration(Duration::from_secs(1));
+        let (position_source, position) = from(
+            source,
+            Duration::from_secs(10),
+            Some(Duration::from_millis(10250)),
+        );
+        assert_eq!(12000, position_source.count());
+        assert_eq!(Duration::from_millis(10250), position.get());
+    }
+}
@@ -0,0 +1,125 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use rodio::dynamic_mixer::{self, DynamicMixerController};
+use rodio::Sink;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+
+/// Number of channels of the rendered audio.
+pub(crate) const CHANNELS: u16 = 2;
+/// Sample rate of the rendered audio.
+pub(crate) const SAMPLE_RATE: u32 = 44100;
+/// Duration of the audio that is rendered at once.
+const CHUNK_DURATION: Duration = Duration::from_millis(10);
+
+/// Renders the audio of its sinks in real time in a thread, instead of playing it on an audio device.
+///
+/// Each sample is passed to the `write` function, silence is rendered while no sink plays.
+pub(crate) struct Renderer {
+    controller: Arc<DynamicMixerController<f32>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Renderer {
+    /// Creates a new [`Sink`](rodio::Sink) whose audio is rendered.
+    pub(crate) fn new_sink(&self) -> Sink {
+        let (sink, queue) = Sink::new_idle();
+        self.controller.add(queue);
+        sink
+    }
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+/// Starts rendering, the rendering stops when `write` fails or the [`Renderer`] is dropped.
+pub(crate) fn new<W>(write: W) -> Renderer
+where
+    W: FnMut(f32) -> Result<(), Error> + Send + 'static,
+{
+    let (controller, mut mixer) = dynamic_mixer::mixer(CHANNELS, SAMPLE_RATE);
+    let running = Arc::new(AtomicBool::new(true));
+    let thread_running = running.clone();
+    let mut write = write;
+    let thread = std::thread::spawn(move || {
+        let samples_per_chunk = (CHANNELS as u32 * SAMPLE_RATE / 100) as usize;
+        let start = Instant::now();
+        let mut chunks = 0;
+        while thread_running.load(Ordering::SeqCst) {
+            for _ in 0..samples_per_chunk {
+                // the mixer has no samples while no sink plays
+                if write(mixer.next().unwrap_or(0.0)).is_err() {
+                    return;
+                }
+            }
+            chunks += 1;
+            let due = start + CHUNK_DURATION * chunks;
+            if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                std::thread::sleep(wait);
+            }
+        }
+    });
+    Renderer {
+        controller,
+        running,
+        thread: Some(thread),
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use rodio::source::SineWave;
+    use rodio::Source;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_render_in_real_time() {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let rendered_samples = samples.clone();
+        let renderer = new(move |sample| {
+            rendered_samples.lock().unwrap().push(sample);
+            Ok(())
+        });
+        let sink = renderer.new_sink();
+        sink.append(SineWave::new(440).take_duration(Duration::from_millis(50)));
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(sink.empty());
+        drop(renderer);
+
+        let samples = samples.lock().unwrap();
+        // rendering is paced in real time
+        let rendered = samples.len() as u64 * 1000 / u64::from(CHANNELS as u32 * SAMPLE_RATE);
+        assert!((150..=300).contains(&rendered), "rendered {} ms", rendered);
+        assert!(samples.iter().any(|sample| *sample != 0.0));
+    }
+
+    #[test]
+    fn test_stop_on_write_error() {
+        let writes = Arc::new(AtomicUsize::new(0));
+        let thread_writes = writes.clone();
+        let _renderer = new(move |_| {
+            thread_writes.fetch_add(1, Ordering::SeqCst);
+            Err(Error::IoError("full".to_string()))
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        // nothing is written after the failed write
+        assert_eq!(1, writes.load(Ordering::SeqCst));
+    }
+}
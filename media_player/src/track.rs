@@ -3,40 +3,95 @@
 #![forbid(unsafe_code)]
 
 use std::path::Path;
+use std::time::Duration;
 
 /// The track (i.e. its path)
 ///
 /// The [`Track`](crate::Track) is created from a [`String`](std::string::String) or a [`&str`](std::str) and contains the path to the track.
+/// Optionally, only the part between a start and an end offset is played, e.g. to skip an intro, and the track is played
+/// with its own volume relative to the volume of the [`MediaPlayer`](crate::MediaPlayer).
 #[derive(Debug, PartialEq, Clone)]
-pub struct Track(String);
+pub struct Track {
+    path: String,
+    start: Duration,
+    end: Option<Duration>,
+    volume: f32,
+}
+
+impl Track {
+    /// Returns the track that starts at the `start` offset instead of its beginning.
+    pub fn with_start(mut self, start: Duration) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Returns the track that ends at the `end` offset instead of its end.
+    pub fn with_end(mut self, end: Duration) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Returns the track with the `volume`, where `1.0` is the original volume of the track.
+    ///
+    /// The volume is limited to the range from `0.0` to `1.0`, i.e. a track is never louder than the
+    /// [`max_volume`](crate::MediaPlayer::max_volume) of the player. An invalid volume results in silence.
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = if volume.is_nan() {
+            0.0
+        } else {
+            volume.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Returns the offset at which the playback of the track starts, zero by default.
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// Returns the offset at which the playback of the track ends, [`None`](std::option::Option::None) by default.
+    pub fn end(&self) -> Option<Duration> {
+        self.end
+    }
+
+    /// Returns the volume of the track, `1.0` by default.
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+}
 
 impl From<String> for Track {
     fn from(path: String) -> Self {
-        Track(path)
+        Track {
+            path,
+            start: Duration::from_secs(0),
+            end: None,
+            volume: 1.0,
+        }
     }
 }
 
 impl From<&str> for Track {
     fn from(path: &str) -> Self {
-        Track(path.into())
+        Track::from(path.to_string())
     }
 }
 
 impl AsRef<str> for Track {
     fn as_ref(&self) -> &str {
-        self.0.as_ref()
+        self.path.as_ref()
     }
 }
 
 impl AsRef<Path> for Track {
     fn as_ref(&self) -> &Path {
-        self.0.as_ref()
+        self.path.as_ref()
     }
 }
 
 impl std::fmt::Display for Track {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.path)
     }
 }
 
@@ -58,4 +113,23 @@ mod tests {
         let formatted_track = format!("{}", track);
         assert_eq!("track".to_string(), formatted_track);
     }
+
+    #[test]
+    fn test_options() {
+        let track = Track::from("track");
+        assert_eq!(Duration::from_secs(0), track.start());
+        assert_eq!(None, track.end());
+        assert_eq!(1.0, track.volume());
+
+        let track = track
+            .with_start(Duration::from_secs(5))
+            .with_end(Duration::from_secs(60))
+            .with_volume(-0.5);
+        assert_eq!(Duration::from_secs(5), track.start());
+        assert_eq!(Some(Duration::from_secs(60)), track.end());
+        assert_eq!(0.0, track.volume());
+        assert_ne!(Track::from("track"), track);
+        assert_eq!(1.0, Track::from("track").with_volume(2.0).volume());
+        assert_eq!(0.0, Track::from("track").with_volume(f32::NAN).volume());
+    }
 }
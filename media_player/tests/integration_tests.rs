@@ -1,7 +1,7 @@
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 mod media_player_integration_tests {
-    use media_player::{open, MediaPlayer, Track};
+    use media_player::{open, open_with_backend, AudioBackend, MediaPlayer, Track};
     use std::thread::sleep;
     use std::time::Duration;
 
@@ -35,4 +35,94 @@ mod media_player_integration_tests {
         }
         assert!(true);
     }
+
+    #[test]
+    fn test_double_play_without_audio_hardware() {
+        let mut media_player = open_with_backend(&AudioBackend::Null).unwrap();
+        media_player.play(&Track::from("tests/rand1.wav")).unwrap();
+        sleep(Duration::from_millis(200));
+        let position = media_player.position().unwrap();
+        assert!(position >= Duration::from_millis(100), "{:?}", position);
+
+        media_player.play(&Track::from("tests/rand2.wav")).unwrap();
+        sleep(Duration::from_millis(200));
+        assert!(media_player.position().is_some());
+
+        media_player.stop().unwrap();
+        assert_eq!(None, media_player.position());
+    }
+
+    #[test]
+    fn test_render_to_wav() {
+        let path = std::env::temp_dir().join(format!(
+            "media_player_integration_test_{}.wav",
+            std::process::id()
+        ));
+        let mut media_player = open_with_backend(&AudioBackend::Wav(path.clone())).unwrap();
+        media_player.play(&Track::from("tests/rand1.wav")).unwrap();
+        sleep(Duration::from_millis(300));
+        media_player.stop().unwrap();
+        sleep(Duration::from_millis(200));
+        drop(media_player);
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        let spec = reader.spec();
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        std::fs::remove_file(&path).unwrap();
+
+        let samples_per_second = spec.sample_rate as usize * spec.channels as usize;
+        let rendered = Duration::from_millis((samples.len() * 1000 / samples_per_second) as u64);
+        assert!(rendered >= Duration::from_millis(450), "{:?}", rendered);
+        // the track was played, but the output is silent after stopping
+        assert!(samples.iter().any(|sample| *sample != 0));
+        let last_100_ms = &samples[samples.len() - samples_per_second / 10..];
+        assert!(last_100_ms.iter().all(|sample| *sample == 0));
+    }
+
+    #[test]
+    fn test_track_volume_does_not_exceed_max_volume() {
+        let temp_dir = std::env::temp_dir();
+        let id = std::process::id();
+        let track_path = temp_dir.join(format!("media_player_integration_test_track_{}.wav", id));
+        let path = temp_dir.join(format!(
+            "media_player_integration_test_max_volume_{}.wav",
+            id
+        ));
+        // a square wave at a quarter of the full scale, which may be amplified without clipping
+        let amplitude = i16::MAX / 4;
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 44100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&track_path, spec).unwrap();
+        for index in 0..44100 {
+            let sample = if index / 50 % 2 == 0 {
+                amplitude
+            } else {
+                -amplitude
+            };
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut media_player = open_with_backend(&AudioBackend::Wav(path.clone())).unwrap();
+        media_player.set_max_volume(0.5).unwrap();
+        media_player.set_volume(1.0).unwrap();
+        let track = Track::from(track_path.to_str().unwrap()).with_volume(2.0);
+        media_player.play(&track).unwrap();
+        sleep(Duration::from_millis(300));
+        drop(media_player);
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        std::fs::remove_file(&track_path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let peak = samples.iter().map(|sample| sample.abs()).max().unwrap();
+        let gain = f32::from(peak) / f32::from(amplitude);
+        assert!(gain > 0.25, "gain {}", gain);
+        assert!(gain <= 0.51, "gain {}", gain);
+    }
 }
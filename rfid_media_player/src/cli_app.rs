@@ -2,13 +2,29 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
-use clap::{App, Arg};
+use clap::{App, Arg, SubCommand};
+use media_player::AudioBackend;
+use rfid_reader::{SimulatedInput, UsbDeviceSelector};
 use std::num::ParseIntError;
+use std::path::Path;
+use track_store::Format;
 
 /// Name of the Vendor ID command line option.
 pub const VENDOR_ID_ARGUMENT_NAME: &str = "Vendor ID";
 /// Name of the Product ID command line option.
 pub const PRODUCT_ID_ARGUMENT_NAME: &str = "Product ID";
+/// Name of the Event Device command line option.
+pub const EVENT_DEVICE_ARGUMENT_NAME: &str = "Event Device";
+/// Name of the USB Device command line option.
+pub const USB_DEVICE_ARGUMENT_NAME: &str = "USB Device";
+/// Name of the HID Report Size command line option.
+pub const HID_REPORT_SIZE_ARGUMENT_NAME: &str = "HID Report Size";
+/// Name of the Terminator command line option.
+pub const TERMINATOR_ARGUMENT_NAME: &str = "Terminator";
+/// Name of the Hex Digits command line flag.
+pub const HEX_DIGITS_ARGUMENT_NAME: &str = "Hex Digits";
+/// Name of the Reader command line option.
+pub const READER_ARGUMENT_NAME: &str = "Reader";
 /// Name of the Tracks File command line option.
 pub const TRACKS_FILE_ARGUMENT_NAME: &str = "Tracks File";
 /// Name of the Timeout command line option.
@@ -17,6 +33,42 @@ pub const TIMEOUT_ARGUMENT_NAME: &str = "Timeout";
 pub const LOG_LEVEL_ARGUMENT_NAME: &str = "Log Level";
 /// Name of the Console Log command line flag.
 pub const CONSOLE_LOG_ARGUMENT_NAME: &str = "Console Log";
+/// Name of the Same Card command line option.
+pub const SAME_CARD_ARGUMENT_NAME: &str = "Same Card";
+/// Name of the Resume command line flag.
+pub const RESUME_ARGUMENT_NAME: &str = "Resume";
+/// Name of the Rewind command line option.
+pub const REWIND_ARGUMENT_NAME: &str = "Rewind";
+/// Name of the Forget After command line option.
+pub const FORGET_AFTER_ARGUMENT_NAME: &str = "Forget After";
+/// Name of the Play While Present command line flag.
+pub const PLAY_WHILE_PRESENT_ARGUMENT_NAME: &str = "Play While Present";
+/// Name of the Absence Timeout command line option.
+pub const ABSENCE_TIMEOUT_ARGUMENT_NAME: &str = "Absence Timeout";
+/// Name of the Debounce command line option.
+pub const DEBOUNCE_ARGUMENT_NAME: &str = "Debounce";
+/// Name of the Cooldown command line option.
+pub const COOLDOWN_ARGUMENT_NAME: &str = "Cooldown";
+/// Name of the Audio Output command line option.
+pub const AUDIO_OUTPUT_ARGUMENT_NAME: &str = "Audio Output";
+/// Name of the Volume command line option.
+pub const VOLUME_ARGUMENT_NAME: &str = "Volume";
+/// Name of the Max Volume command line option.
+pub const MAX_VOLUME_ARGUMENT_NAME: &str = "Max Volume";
+/// Name of the learn subcommand.
+pub const LEARN_SUBCOMMAND_NAME: &str = "learn";
+/// Name of the Drop Directory command line option of the learn subcommand.
+pub const DROP_DIRECTORY_ARGUMENT_NAME: &str = "Drop Directory";
+/// Name of the scan subcommand.
+pub const SCAN_SUBCOMMAND_NAME: &str = "scan";
+/// Name of the devices subcommand.
+pub const DEVICES_SUBCOMMAND_NAME: &str = "devices";
+/// Name of the validate subcommand.
+pub const VALIDATE_SUBCOMMAND_NAME: &str = "validate";
+/// Name of the export subcommand.
+pub const EXPORT_SUBCOMMAND_NAME: &str = "export";
+/// Name of the Output File argument of the export subcommand.
+pub const OUTPUT_FILE_ARGUMENT_NAME: &str = "Output File";
 
 /// Creates the [`clap::App`](https://docs.rs/clap/2.33.3/clap/struct.App.html) of the rfid_media_player application.
 pub fn create_app<'a, 'b>() -> App<'a, 'b> {
@@ -29,18 +81,79 @@ pub fn create_app<'a, 'b>() -> App<'a, 'b> {
                 .short("v")
                 .long("vendor_id")
                 .value_name("VENDOR_ID")
-                .help("The Vendor ID of the RFID card reader.\
+                .help("The Vendor ID of the RFID card reader, which is read via libusb.\
+                Optional if exactly one known reader is attached, which is then selected.\
                 Must be a valid unsigned integer with at most 16 bits.")
-                .required(true).validator(validate_id),
+                .requires(PRODUCT_ID_ARGUMENT_NAME)
+                .required(false).validator(validate_id),
         )
         .arg(
           Arg::with_name(PRODUCT_ID_ARGUMENT_NAME)
               .short("p")
               .long("product_id")
               .value_name("PRODUCT_ID")
-              .help("The Product ID of the RFID card reader.\
+              .help("The Product ID of the RFID card reader, which is read via libusb.\
+              Optional if exactly one known reader is attached, which is then selected.\
               Must be a valid unsigned integer with at most 16 bits.")
-              .required(true).validator(validate_id),
+              .requires(VENDOR_ID_ARGUMENT_NAME)
+              .required(false).validator(validate_id),
+      )
+      .arg(
+        Arg::with_name(USB_DEVICE_ARGUMENT_NAME)
+            .long("usb_device")
+            .value_name("USB_DEVICE")
+            .help("Selects one of several USB RFID card readers with the same Vendor ID and Product ID, which is read via libusb.\
+            \"port:<BUS>-<PORT>[.<PORT>...]\" selects the reader plugged into the port chain, e.g. port:1-1.2 like in /sys/bus/usb/devices,\
+            \"address:<BUS>:<ADDRESS>\" selects the address on the bus, e.g. address:2:4 like Bus 002 Device 004 of lsusb,\
+            \"serial:<SERIAL>\" selects the serial number.\
+            The devices subcommand prints these of all attached devices.")
+            .conflicts_with_all(&[EVENT_DEVICE_ARGUMENT_NAME, READER_ARGUMENT_NAME])
+            .required(false).validator(validate_usb_device),
+      )
+      .arg(
+        Arg::with_name(HID_REPORT_SIZE_ARGUMENT_NAME)
+            .long("hid_report_size")
+            .value_name("HID_REPORT_SIZE")
+            .help("The size in bytes of the keyboard reports of the USB RFID card reader, which is read via libusb.\
+            3 for the Neuftech reader, 8 for readers with full boot keyboard reports.\
+            Must be a valid unsigned integer between 3 and 8.")
+            .required(false).validator(validate_hid_report_size)
+            .default_value("3"),
+      )
+      .arg(
+        Arg::with_name(TERMINATOR_ARGUMENT_NAME)
+            .long("terminator")
+            .value_name("TERMINATOR")
+            .help("The key the USB RFID card reader, which is read via libusb, types after the RFID value.")
+            .possible_value("enter")
+            .possible_value("tab")
+            .possible_value("space")
+            .required(false).default_value("enter"),
+      )
+      .arg(Arg::with_name(HEX_DIGITS_ARGUMENT_NAME).long("hex_digits").help("The USB RFID card reader, which is read via libusb, types the RFID value in hexadecimal digits instead of decimal digits.\
+      10 digits are read as EM4100 id."))
+      .arg(
+        Arg::with_name(EVENT_DEVICE_ARGUMENT_NAME)
+            .short("e")
+            .long("event_device")
+            .value_name("EVENT_DEVICE")
+            .help("The Linux input event device of the RFID card reader, e.g. /dev/input/by-id/<reader>-event-kbd.\
+            Readers that present themselves as keyboard are read via evdev instead of libusb and grabbed exclusively.\
+            A file with an event stream recorded from the device (e.g. via cat /dev/input/event0) is replayed.")
+            .conflicts_with_all(&[VENDOR_ID_ARGUMENT_NAME, PRODUCT_ID_ARGUMENT_NAME])
+            .required(false),
+      )
+      .arg(
+        Arg::with_name(READER_ARGUMENT_NAME)
+            .long("reader")
+            .value_name("READER")
+            .help("Reads the RFID values from a simulated reader instead of a device, e.g. for running without hardware.\
+            \"stdin\" reads a value per line entered on stdin,\
+            \"pipe:<PATH>\" reads a value per line written to the named pipe,\
+            \"script:<PATH>\" reads the \"<delay in milliseconds> <rfid value>\" lines of the file.\
+            Do not combine \"stdin\" with learning via stdin.")
+            .conflicts_with_all(&[VENDOR_ID_ARGUMENT_NAME, PRODUCT_ID_ARGUMENT_NAME, EVENT_DEVICE_ARGUMENT_NAME])
+            .required(false).validator(validate_reader),
       )
       .arg(
         Arg::with_name(TRACKS_FILE_ARGUMENT_NAME)
@@ -49,8 +162,18 @@ pub fn create_app<'a, 'b>() -> App<'a, 'b> {
             .value_name("TRACKS_FILE")
             .help("The YAML file that contains the mapping from RFID value to the path of the associated track.\
             Mapping must be defined like:\n\
-            \"<rfid value as string incl. leading zeros>: <path to track>\"")
-            .required(true),
+            \"<rfid value>: <path to track>\"\n\
+            The RFID value is the decimal number output by the reader (e.g. 0012345678) or one of the numbers printed on the card\
+            in the notation \"hex:<8 digits>\", \"em4100:<10 hex digits>\" or \"wiegand26:<facility>,<card>\"\
+            (which only contains the lower 24 bits, i.e. matches no card above 0016777215).\
+            Relative paths of tracks are resolved against the directory of the file, ~ and environment variables are expanded.\
+            Instead of a path, a map with the path and the options title, description, start, end (offsets in seconds),\
+            volume, loop and shuffle is accepted, e.g. \"<rfid value>: {path: <path>, title: <title>, start: 12.5, loop: true}\".\
+            The extension selects the format: yaml (default), toml, json or csv (columns id, path and title, a row per track).\
+            A file with the extension sqlite or db is an SQLite database, e.g. created by the export subcommand,\
+            to which learned cards are written without rewriting the whole file.\
+            Required unless scanning cards.")
+            .required(false),
 
       )
       .arg(
@@ -60,7 +183,8 @@ pub fn create_app<'a, 'b>() -> App<'a, 'b> {
             .value_name("TIMEOUT")
             .help("The timeout in milliseconds (ms) for the USB device interrupt read.\
             This means the time the read of the USB device should be blocking.\
-            Aborting the application (e.g. via Ctrl+c) may be delayed up by this timeout value.\
+            The playback stops immediately when aborting the application (e.g. via Ctrl+c),\
+            but releasing the device may be delayed up by this timeout value.\
             Do not use this value unless you know what you are doing.\
             Must be a valid unsigned integer with at most 64 bits in decimal radix.")
             .required(false).validator(validate_timeout)
@@ -78,6 +202,126 @@ pub fn create_app<'a, 'b>() -> App<'a, 'b> {
           .possible_value("trace")
           .required(false).default_value("info"))
       .arg(Arg::with_name(CONSOLE_LOG_ARGUMENT_NAME).long("console_log").short("c").help("Log will be duplicated to the console (stdout)."))
+      .arg(
+        Arg::with_name(SAME_CARD_ARGUMENT_NAME)
+          .short("s")
+          .long("same_card")
+          .value_name("SAME_CARD")
+          .help("What happens when the card of the current track is read again.\
+          \"ignore\" continues the playback, \"toggle\" pauses or resumes the playback.")
+          .possible_value("ignore")
+          .possible_value("toggle")
+          .required(false).default_value("ignore"))
+      .arg(Arg::with_name(RESUME_ARGUMENT_NAME).long("resume").short("r").help("Tracks are resumed at the position they were stopped, even after a restart.\
+      The positions are stored in a file next to the tracks file."))
+      .arg(
+        Arg::with_name(REWIND_ARGUMENT_NAME)
+          .long("rewind")
+          .value_name("REWIND")
+          .help("The time in seconds a track is rewound when it is resumed.\
+          Must be a valid unsigned integer with at most 64 bits in decimal radix.")
+          .required(false).validator(validate_unsigned_integer)
+          .default_value("5"))
+      .arg(
+        Arg::with_name(FORGET_AFTER_ARGUMENT_NAME)
+          .long("forget_after")
+          .value_name("FORGET_AFTER")
+          .help("The number of days after which the position of a track that was not played is forgotten.\
+          0 means that positions are never forgotten.\
+          Must be a valid unsigned integer with at most 64 bits in decimal radix.")
+          .required(false).validator(validate_unsigned_integer)
+          .default_value("30"))
+      .arg(Arg::with_name(PLAY_WHILE_PRESENT_ARGUMENT_NAME).long("play_while_present").help("Tracks are only played while the card lies on the reader.\
+      Removing the card pauses the track, putting it back resumes it. The reader must read the card repeatedly while it lies on the reader."))
+      .arg(
+        Arg::with_name(ABSENCE_TIMEOUT_ARGUMENT_NAME)
+          .long("absence_timeout")
+          .value_name("ABSENCE_TIMEOUT")
+          .help("The time in milliseconds (ms) after which a card that was not read again is removed, used with --play_while_present.\
+          Must be longer than the interval in which the reader reads a card repeatedly.\
+          Must be a valid unsigned integer with at most 64 bits in decimal radix.")
+          .required(false).validator(validate_unsigned_integer)
+          .default_value("1500"))
+      .arg(
+        Arg::with_name(DEBOUNCE_ARGUMENT_NAME)
+          .long("debounce")
+          .value_name("DEBOUNCE")
+          .help("The time in milliseconds (ms) in which repeated reads of the same card are ignored, e.g. while the card lies on the reader.\
+          The time starts again with every read. 0 disables ignoring repeated reads. Not used with --play_while_present.\
+          Must be a valid unsigned integer with at most 64 bits in decimal radix.")
+          .required(false).validator(validate_unsigned_integer)
+          .default_value("1000"))
+      .arg(
+        Arg::with_name(COOLDOWN_ARGUMENT_NAME)
+          .long("cooldown")
+          .value_name("COOLDOWN")
+          .help("The time in milliseconds (ms) after a card in which other cards are ignored, e.g. while switching cards.\
+          0 disables the cooldown.\
+          Must be a valid unsigned integer with at most 64 bits in decimal radix.")
+          .required(false).validator(validate_unsigned_integer)
+          .default_value("0"))
+      .arg(
+        Arg::with_name(AUDIO_OUTPUT_ARGUMENT_NAME)
+          .long("audio_output")
+          .value_name("AUDIO_OUTPUT")
+          .help("Where the audio is output.\
+          \"device\" plays on the audio device of the system,\
+          \"null\" decodes the audio in real time but discards it (e.g. for running without audio hardware),\
+          \"wav:<PATH>\" renders the audio in real time into the WAV file.")
+          .required(false).validator(validate_audio_output)
+          .default_value("device"))
+      .arg(
+        Arg::with_name(VOLUME_ARGUMENT_NAME)
+          .long("volume")
+          .value_name("VOLUME")
+          .help("The volume in percent of the original volume of the tracks at start-up.\
+          The volume is limited by the maximum volume.\
+          Must be a valid unsigned integer between 0 and 100.")
+          .required(false).validator(validate_percent)
+          .default_value("100"))
+      .arg(
+        Arg::with_name(MAX_VOLUME_ARGUMENT_NAME)
+          .long("max_volume")
+          .value_name("MAX_VOLUME")
+          .help("The maximum volume in percent of the original volume of the tracks, which can never be exceeded.\
+          Must be a valid unsigned integer between 0 and 100.")
+          .required(false).validator(validate_percent)
+          .default_value("100"))
+      .subcommand(
+        SubCommand::with_name(LEARN_SUBCOMMAND_NAME)
+          .about("Plays the tracks like without subcommand, but unknown cards are assigned a track, which is written back to the tracks file.\
+          By default the path of the track is entered on stdin.")
+          .arg(
+            Arg::with_name(DROP_DIRECTORY_ARGUMENT_NAME)
+              .short("d")
+              .long("drop_dir")
+              .value_name("DROP_DIRECTORY")
+              .help("The newest file dropped into this directory after the card was read is assigned to an unknown card instead of entering the path on stdin.")
+              .required(false)))
+      .subcommand(
+        SubCommand::with_name(SCAN_SUBCOMMAND_NAME)
+          .about("Prints all notations of the scanned cards instead of playing tracks, e.g. in order to write the tracks file.\
+          No tracks file is required."))
+      .subcommand(
+        SubCommand::with_name(DEVICES_SUBCOMMAND_NAME)
+          .about("Prints the attached USB devices with their vendor and product ids, e.g. in order to find the RFID card reader.\
+          Known readers are marked, readers must be readable. No tracks file is required."))
+      .subcommand(
+        SubCommand::with_name(VALIDATE_SUBCOMMAND_NAME)
+          .about("Prints all problems of the tracks file instead of playing tracks, e.g. before deployment.\
+          Detects invalid and duplicate RFID values, invalid entries and tracks that do not exist, cannot be read or cannot be decoded.\
+          No reader is opened, fails if problems are found."))
+      .subcommand(
+        SubCommand::with_name(EXPORT_SUBCOMMAND_NAME)
+          .about("Writes the tracks file to the output file in the format of its extension instead of playing tracks,\
+          e.g. in order to edit the tracks in a spreadsheet or to migrate them to an SQLite database. No reader is opened.")
+          .arg(
+            Arg::with_name(OUTPUT_FILE_ARGUMENT_NAME)
+              .value_name("OUTPUT_FILE")
+              .help("The file the tracks are written to. The extension selects the format: yaml, toml, json, csv (columns id, path and title)\
+              or sqlite (replaces all entries of the database). Control cards and options other than the title cannot be written to csv.")
+              .required(true)
+              .validator(validate_output_file)))
 }
 
 /// Tries to convert a [`String`](std::string::String) into an [`u16`](std::u16).
@@ -113,6 +357,57 @@ fn validate_timeout(timeout_string: String) -> Result<(), String> {
     Ok(())
 }
 
+fn validate_audio_output(audio_output_string: String) -> Result<(), String> {
+    audio_output_string.parse::<AudioBackend>().map(|_| ())
+}
+
+fn validate_reader(reader_string: String) -> Result<(), String> {
+    reader_string.parse::<SimulatedInput>().map(|_| ())
+}
+
+fn validate_usb_device(usb_device_string: String) -> Result<(), String> {
+    usb_device_string.parse::<UsbDeviceSelector>().map(|_| ())
+}
+
+fn validate_output_file(output_file_string: String) -> Result<(), String> {
+    Format::from_path(Path::new(&output_file_string))
+        .map(|_| ())
+        .ok_or_else(|| {
+            format!(
+                "Unknown format of {}, use the extension yaml, toml, json, csv or sqlite",
+                output_file_string
+            )
+        })
+}
+
+fn validate_unsigned_integer(value_string: String) -> Result<(), String> {
+    let conversion_result = value_string.parse::<u64>();
+
+    if conversion_result.is_err() {
+        return Err(
+            "Value must be a valid decimal unsigned integer with at most 64 bits.".to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_hid_report_size(value_string: String) -> Result<(), String> {
+    match value_string.parse::<u8>() {
+        Ok(report_size) if (3..=8).contains(&report_size) => Ok(()),
+        _ => Err("Value must be a valid decimal unsigned integer between 3 and 8.".to_string()),
+    }
+}
+
+fn validate_percent(value_string: String) -> Result<(), String> {
+    let conversion_result = value_string.parse::<u8>();
+
+    match conversion_result {
+        Ok(percent) if percent <= 100 => Ok(()),
+        _ => Err("Value must be a valid decimal unsigned integer between 0 and 100.".to_string()),
+    }
+}
+
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 mod tests {
@@ -167,5 +462,385 @@ mod tests {
             Some("path/to/tracks"),
             matches.value_of(TRACKS_FILE_ARGUMENT_NAME)
         );
+        assert_eq!(Some("ignore"), matches.value_of(SAME_CARD_ARGUMENT_NAME));
+        assert!(!matches.is_present(RESUME_ARGUMENT_NAME));
+        assert_eq!(Some("5"), matches.value_of(REWIND_ARGUMENT_NAME));
+        assert_eq!(Some("30"), matches.value_of(FORGET_AFTER_ARGUMENT_NAME));
+        assert_eq!(Some("100"), matches.value_of(VOLUME_ARGUMENT_NAME));
+        assert_eq!(Some("100"), matches.value_of(MAX_VOLUME_ARGUMENT_NAME));
+        assert_eq!(Some("device"), matches.value_of(AUDIO_OUTPUT_ARGUMENT_NAME));
+        assert!(!matches.is_present(PLAY_WHILE_PRESENT_ARGUMENT_NAME));
+        assert_eq!(
+            Some("1500"),
+            matches.value_of(ABSENCE_TIMEOUT_ARGUMENT_NAME)
+        );
+        assert_eq!(Some("1000"), matches.value_of(DEBOUNCE_ARGUMENT_NAME));
+        assert_eq!(Some("0"), matches.value_of(COOLDOWN_ARGUMENT_NAME));
+        assert_eq!(Some("3"), matches.value_of(HID_REPORT_SIZE_ARGUMENT_NAME));
+        assert_eq!(Some("enter"), matches.value_of(TERMINATOR_ARGUMENT_NAME));
+        assert!(!matches.is_present(HEX_DIGITS_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_hid_format() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "--hid_report_size",
+            "8",
+            "--terminator",
+            "tab",
+            "--hex_digits",
+        ]);
+        assert_eq!(Some("8"), matches.value_of(HID_REPORT_SIZE_ARGUMENT_NAME));
+        assert_eq!(Some("tab"), matches.value_of(TERMINATOR_ARGUMENT_NAME));
+        assert!(matches.is_present(HEX_DIGITS_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_validate_hid_report_size() {
+        assert_eq!(Ok(()), validate_hid_report_size("3".to_string()));
+        assert_eq!(Ok(()), validate_hid_report_size("8".to_string()));
+        let error =
+            Err("Value must be a valid decimal unsigned integer between 3 and 8.".to_string());
+        assert_eq!(error, validate_hid_report_size("2".to_string()));
+        assert_eq!(error, validate_hid_report_size("9".to_string()));
+    }
+
+    #[test]
+    fn test_debounce() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "--debounce",
+            "2000",
+            "--cooldown",
+            "500",
+        ]);
+        assert_eq!(Some("2000"), matches.value_of(DEBOUNCE_ARGUMENT_NAME));
+        assert_eq!(Some("500"), matches.value_of(COOLDOWN_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_validate_unsigned_integer() {
+        let result = validate_unsigned_integer("10".to_string());
+        assert_eq!(Ok(()), result);
+        let result = validate_unsigned_integer("-1".to_string());
+        assert_eq!(
+            Err("Value must be a valid decimal unsigned integer with at most 64 bits.".to_string()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_resume() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "--resume",
+            "--rewind",
+            "10",
+            "--forget_after",
+            "0",
+        ]);
+        assert!(matches.is_present(RESUME_ARGUMENT_NAME));
+        assert_eq!(Some("10"), matches.value_of(REWIND_ARGUMENT_NAME));
+        assert_eq!(Some("0"), matches.value_of(FORGET_AFTER_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_play_while_present() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "--play_while_present",
+            "--absence_timeout",
+            "3000",
+        ]);
+        assert!(matches.is_present(PLAY_WHILE_PRESENT_ARGUMENT_NAME));
+        assert_eq!(
+            Some("3000"),
+            matches.value_of(ABSENCE_TIMEOUT_ARGUMENT_NAME)
+        );
+    }
+
+    #[test]
+    fn test_same_card() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "--same_card",
+            "toggle",
+        ]);
+        assert_eq!(Some("toggle"), matches.value_of(SAME_CARD_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_validate_percent() {
+        let result = validate_percent("100".to_string());
+        assert_eq!(Ok(()), result);
+        let result = validate_percent("0".to_string());
+        assert_eq!(Ok(()), result);
+        let expected_error =
+            Err("Value must be a valid decimal unsigned integer between 0 and 100.".to_string());
+        assert_eq!(expected_error, validate_percent("101".to_string()));
+        assert_eq!(expected_error, validate_percent("-1".to_string()));
+    }
+
+    #[test]
+    fn test_volume() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "--volume",
+            "50",
+            "--max_volume",
+            "80",
+        ]);
+        assert_eq!(Some("50"), matches.value_of(VOLUME_ARGUMENT_NAME));
+        assert_eq!(Some("80"), matches.value_of(MAX_VOLUME_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_learn() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--product_id",
+            "2",
+            "--tracks",
+            "path/to/tracks",
+            "learn",
+            "--drop_dir",
+            "path/to/drop",
+        ]);
+        let learn_matches = matches.subcommand_matches(LEARN_SUBCOMMAND_NAME).unwrap();
+        assert_eq!(
+            Some("path/to/drop"),
+            learn_matches.value_of(DROP_DIRECTORY_ARGUMENT_NAME)
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec!["rfid_media_player", "--reader", "stdin", "scan"]);
+        assert!(matches.subcommand_matches(SCAN_SUBCOMMAND_NAME).is_some());
+        assert_eq!(None, matches.value_of(TRACKS_FILE_ARGUMENT_NAME));
+    }
+
+    #[test]
+    fn test_event_device() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--event_device",
+            "/dev/input/event0",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert_eq!(
+            Some("/dev/input/event0"),
+            matches.value_of(EVENT_DEVICE_ARGUMENT_NAME)
+        );
+        assert_eq!(None, matches.value_of(VENDOR_ID_ARGUMENT_NAME));
+
+        let result = create_app().get_matches_from_safe(vec![
+            "rfid_media_player",
+            "--event_device",
+            "/dev/input/event0",
+            "--vendor_id",
+            "1",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_auto_selected_device() {
+        // the known reader is selected without any device
+        let matches =
+            create_app().get_matches_from(vec!["rfid_media_player", "--tracks", "path/to/tracks"]);
+        assert_eq!(None, matches.value_of(VENDOR_ID_ARGUMENT_NAME));
+        assert_eq!(None, matches.value_of(PRODUCT_ID_ARGUMENT_NAME));
+
+        // vendor and product id are only given together
+        let result = create_app().get_matches_from_safe(vec![
+            "rfid_media_player",
+            "--vendor_id",
+            "1",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_usb_device() {
+        let matches = create_app().get_matches_from(vec![
+            "rfid_media_player",
+            "--usb_device",
+            "port:1-1.2",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert_eq!(
+            Some("port:1-1.2"),
+            matches.value_of(USB_DEVICE_ARGUMENT_NAME)
+        );
+
+        let result = create_app().get_matches_from_safe(vec![
+            "rfid_media_player",
+            "--usb_device",
+            "serial:08FF20140315",
+            "--reader",
+            "stdin",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_usb_device() {
+        assert_eq!(Ok(()), validate_usb_device("address:2:4".to_string()));
+        assert_eq!(
+            Err("Unknown USB device selector: 2:4".to_string()),
+            validate_usb_device("2:4".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let matches = create_app().get_matches_from(vec![
+            "rfid_media_player",
+            "--tracks",
+            "path/to/tracks",
+            "validate",
+        ]);
+        assert!(matches
+            .subcommand_matches(VALIDATE_SUBCOMMAND_NAME)
+            .is_some());
+    }
+
+    #[test]
+    fn test_export() {
+        let matches = create_app().get_matches_from(vec![
+            "rfid_media_player",
+            "--tracks",
+            "path/to/tracks",
+            "export",
+            "tracks.csv",
+        ]);
+        assert_eq!(
+            Some("tracks.csv"),
+            matches
+                .subcommand_matches(EXPORT_SUBCOMMAND_NAME)
+                .unwrap()
+                .value_of(OUTPUT_FILE_ARGUMENT_NAME)
+        );
+
+        let result = create_app().get_matches_from_safe(vec![
+            "rfid_media_player",
+            "--tracks",
+            "path/to/tracks",
+            "export",
+            "tracks.txt",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_devices() {
+        let matches = create_app().get_matches_from(vec!["rfid_media_player", "devices"]);
+        assert!(matches
+            .subcommand_matches(DEVICES_SUBCOMMAND_NAME)
+            .is_some());
+    }
+
+    #[test]
+    fn test_validate_reader() {
+        assert_eq!(Ok(()), validate_reader("stdin".to_string()));
+        assert_eq!(Ok(()), validate_reader("script:cards.txt".to_string()));
+        assert_eq!(
+            Err("Unknown simulated input: usb".to_string()),
+            validate_reader("usb".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reader() {
+        let app = create_app();
+        let matches = app.get_matches_from(vec![
+            "rfid_media_player",
+            "--reader",
+            "pipe:/tmp/rfid",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert_eq!(
+            Some("pipe:/tmp/rfid"),
+            matches.value_of(READER_ARGUMENT_NAME)
+        );
+
+        let result = create_app().get_matches_from_safe(vec![
+            "rfid_media_player",
+            "--reader",
+            "stdin",
+            "--event_device",
+            "/dev/input/event0",
+            "--tracks",
+            "path/to/tracks",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_audio_output() {
+        assert_eq!(Ok(()), validate_audio_output("null".to_string()));
+        assert_eq!(Ok(()), validate_audio_output("wav:out.wav".to_string()));
+        assert_eq!(
+            Err("Unknown audio backend: alsa".to_string()),
+            validate_audio_output("alsa".to_string())
+        );
     }
 }
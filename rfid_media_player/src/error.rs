@@ -23,6 +23,10 @@ pub enum Error {
     ///
     /// Returned whenever an error with the track store occurred.
     TrackStoreError(String),
+    /// Position store error.
+    ///
+    /// Returned whenever the playback positions could not be persisted.
+    PositionStoreError(String),
 }
 
 macro_rules! implement_from_error_trait {
@@ -0,0 +1,352 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::learnmode::LearnMode;
+
+use log::{info, warn};
+
+use rfid_reader::Canceller;
+use track_store::{Id, TrackPath};
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Time the learner waits at most for the track of an unknown card.
+const LEARN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Interval in which the learner polls for the track and checks whether it was cancelled.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Assigns tracks to unknown cards in the [`LearnMode`](crate::LearnMode).
+///
+/// Waits at most [`LEARN_TIMEOUT`] for the track and stops waiting as soon as reading the cards is cancelled,
+/// e.g. on a terminating signal.
+pub(crate) struct Learner {
+    learn_mode: LearnMode,
+    /// The lines of stdin, which are read on a thread of its own in order to not block while waiting for them.
+    lines: Option<Receiver<String>>,
+    timeout: Duration,
+}
+
+impl Learner {
+    /// Creates the learner of the `learn_mode`, stdin is read from now on if the tracks are entered on stdin.
+    pub(crate) fn new(learn_mode: LearnMode) -> Self {
+        let lines = match learn_mode {
+            LearnMode::Stdin => Some(read_lines(std::io::BufReader::new(std::io::stdin()))),
+            LearnMode::DropDirectory(_) => None,
+        };
+        Learner::with(learn_mode, lines, LEARN_TIMEOUT)
+    }
+
+    /// Creates the learner of the `learn_mode`, which receives the tracks entered on stdin as `lines` and waits at most
+    /// `timeout` for a track.
+    pub(crate) fn with(
+        learn_mode: LearnMode,
+        lines: Option<Receiver<String>>,
+        timeout: Duration,
+    ) -> Self {
+        Learner {
+            learn_mode,
+            lines,
+            timeout,
+        }
+    }
+
+    /// Returns the track to assign to the unknown card with the given [`Id`](track_store::Id).
+    ///
+    /// A dropped file is only assigned if it was dropped after the card was read, it was copied completely and
+    /// `is_assigned` returns `false` for it, i.e. it is not assigned to another card yet.
+    /// Returns [`None`](std::option::Option::None) if no track shall be assigned, no track was given in time or
+    /// the `canceller` cancelled reading.
+    pub(crate) fn track_for<F>(
+        &self,
+        id: &Id,
+        canceller: &Canceller,
+        is_assigned: F,
+    ) -> Option<TrackPath>
+    where
+        F: Fn(&TrackPath) -> bool,
+    {
+        let deadline = Instant::now() + self.timeout;
+        match &self.learn_mode {
+            LearnMode::Stdin => prompt(
+                self.lines.as_ref()?,
+                &mut std::io::stdout(),
+                id,
+                deadline,
+                canceller,
+            ),
+            LearnMode::DropDirectory(directory) => {
+                dropped_file(directory, deadline, canceller, is_assigned)
+            }
+        }
+    }
+}
+
+/// Reads the lines of the `input` on a thread of its own and returns the receiver of the lines.
+///
+/// The thread ends when the input is closed or the receiver is dropped.
+fn read_lines<R: BufRead + Send + 'static>(input: R) -> Receiver<String> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in input.lines() {
+            let sent = line
+                .map_err(|error| warn!("Reading stdin failed: {}", error))
+                .and_then(|line| sender.send(line).map_err(|_| ()));
+            if sent.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn prompt(
+    lines: &Receiver<String>,
+    output: &mut impl Write,
+    id: &Id,
+    deadline: Instant,
+    canceller: &Canceller,
+) -> Option<TrackPath> {
+    // lines entered before the card was read are no answer
+    while lines.try_recv().is_ok() {}
+    // the prompt is optional, reading the answer is what matters
+    write!(
+        output,
+        "Unknown card {}, enter the path of the track to assign (leave empty to skip): ",
+        id
+    )
+    .and_then(|_| output.flush())
+    .ok();
+    while !canceller.is_cancelled() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining == Duration::from_secs(0) {
+            info!("No track entered in time, no track assigned to card {}", id);
+            return None;
+        }
+        match lines.recv_timeout(remaining.min(POLL_INTERVAL)) {
+            Ok(line) => {
+                let path = line.trim();
+                if path.is_empty() {
+                    info!("No track assigned to card {}", id);
+                    return None;
+                }
+                return Some(TrackPath::from(path));
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                warn!("Stdin is closed, no track assigned to card {}", id);
+                return None;
+            }
+        }
+    }
+    None
+}
+
+fn dropped_file<F>(
+    directory: &Path,
+    deadline: Instant,
+    canceller: &Canceller,
+    is_assigned: F,
+) -> Option<TrackPath>
+where
+    F: Fn(&TrackPath) -> bool,
+{
+    // only files dropped after the card was read are assigned
+    let existing_files = files(directory)?;
+    info!("Waiting for a file dropped into {}", directory.display());
+    let mut candidate = None;
+    while !canceller.is_cancelled() && Instant::now() < deadline {
+        std::thread::sleep(POLL_INTERVAL);
+        let newest = files(directory)?
+            .into_iter()
+            .filter(|(path, file)| existing_files.get(path) != Some(file))
+            .filter(|(path, _)| !is_assigned(&TrackPath::from(path.clone())))
+            .max_by_key(|(_, (modified, _))| *modified)
+            .map(|(path, (_, length))| (path, length));
+        // a file is assigned once its length did not change within the poll interval, i.e. it was copied completely
+        if newest.is_some() && newest == candidate {
+            return newest.map(|(path, _)| TrackPath::from(path));
+        }
+        candidate = newest;
+    }
+    info!("No file dropped into {} in time", directory.display());
+    None
+}
+
+/// Returns the modification time and the length of the visible files in the `directory`.
+fn files(directory: &Path) -> Option<HashMap<PathBuf, (SystemTime, u64)>> {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) => {
+            warn!(
+                "Drop directory {} cannot be read: {}",
+                directory.display(),
+                error
+            );
+            return None;
+        }
+    };
+    let files = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if metadata.is_file() {
+                Some((entry.path(), (metadata.modified().ok()?, metadata.len())))
+            } else {
+                None
+            }
+        })
+        .collect();
+    Some(files)
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use rfid_reader::{RfidReader, RfidReaderHandle, RfidValue};
+
+    #[derive(Debug)]
+    struct TimeoutRfidReader;
+    impl RfidReader for TimeoutRfidReader {
+        fn read(&self) -> Result<RfidValue, rfid_reader::Error> {
+            std::thread::sleep(Duration::from_millis(10));
+            Err(rfid_reader::Error::Timeout)
+        }
+        fn deinitialize(&mut self) -> Result<(), rfid_reader::Error> {
+            Ok(())
+        }
+    }
+
+    fn spawn() -> RfidReaderHandle {
+        rfid_reader::spawn(|| Ok(TimeoutRfidReader)).unwrap()
+    }
+
+    fn stdin_learner(input: &'static str, timeout: Duration) -> Learner {
+        Learner::with(
+            LearnMode::Stdin,
+            Some(read_lines(input.as_bytes())),
+            timeout,
+        )
+    }
+
+    #[test]
+    fn test_prompt() {
+        let (sender, lines) = std::sync::mpsc::channel();
+        let mut handle = spawn();
+        let canceller = handle.canceller();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let answer = std::thread::spawn(move || {
+            let mut output = Vec::new();
+            let track_path = prompt(&lines, &mut output, &Id::from("1234"), deadline, &canceller);
+            (track_path, String::from_utf8(output).unwrap())
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        sender.send("path/to/track ".to_string()).unwrap();
+        let (track_path, output) = answer.join().unwrap();
+        assert_eq!(Some(TrackPath::from("path/to/track")), track_path);
+        assert!(output.contains("1234"));
+        handle.deinitialize().ok();
+    }
+
+    #[test]
+    fn test_prompt_without_answer() {
+        let mut handle = spawn();
+        let canceller = handle.canceller();
+        let id = Id::from("1234");
+
+        // the line entered before the card was read is skipped, the empty answer skips the card
+        let (sender, lines) = std::sync::mpsc::channel();
+        sender.send("early/track".to_string()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let prompt_canceller = canceller.clone();
+        let answer = std::thread::spawn(move || {
+            prompt(
+                &lines,
+                &mut Vec::new(),
+                &Id::from("1234"),
+                deadline,
+                &prompt_canceller,
+            )
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        sender.send(String::new()).unwrap();
+        assert_eq!(None, answer.join().unwrap());
+
+        // closed stdin
+        let learner = stdin_learner("", Duration::from_secs(5));
+        assert_eq!(None, learner.track_for(&id, &canceller, |_| false));
+
+        // no answer in time
+        let (_sender, lines) = std::sync::mpsc::channel();
+        let learner = Learner::with(LearnMode::Stdin, Some(lines), Duration::from_millis(200));
+        assert_eq!(None, learner.track_for(&id, &canceller, |_| false));
+
+        // cancelled reading stops waiting immediately
+        let (_sender, lines) = std::sync::mpsc::channel();
+        let learner = Learner::with(LearnMode::Stdin, Some(lines), Duration::from_secs(60));
+        canceller.cancel();
+        let start = Instant::now();
+        assert_eq!(None, learner.track_for(&id, &canceller, |_| false));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        handle.deinitialize().ok();
+    }
+
+    #[test]
+    fn test_dropped_file() {
+        let directory =
+            std::env::temp_dir().join(format!("rfid_media_player_learner_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("old.mp3"), "").unwrap();
+        let mut handle = spawn();
+        let canceller = handle.canceller();
+        let learner = Learner::with(
+            LearnMode::DropDirectory(directory.clone()),
+            None,
+            Duration::from_secs(5),
+        );
+
+        // the file that existed before the card was read is not assigned
+        let drop_directory = directory.clone();
+        let drop = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(250));
+            std::fs::write(drop_directory.join(".hidden"), "").unwrap();
+            std::fs::create_dir_all(drop_directory.join("sub")).unwrap();
+            std::fs::write(drop_directory.join("new.mp3"), "").unwrap();
+        });
+        let track_path = learner.track_for(&Id::from("1234"), &canceller, |_| false);
+        drop.join().unwrap();
+        assert_eq!(Some(TrackPath::from(directory.join("new.mp3"))), track_path);
+
+        // an assigned file is not assigned again
+        let learner = Learner::with(
+            LearnMode::DropDirectory(directory.clone()),
+            None,
+            Duration::from_millis(500),
+        );
+        let drop_directory = directory.clone();
+        let drop = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            std::fs::write(drop_directory.join("assigned.mp3"), "").unwrap();
+        });
+        let assigned = TrackPath::from(directory.join("assigned.mp3"));
+        let track_path = learner.track_for(&Id::from("1234"), &canceller, |track_path| {
+            track_path == &assigned
+        });
+        drop.join().unwrap();
+        assert_eq!(None, track_path);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(
+            None,
+            learner.track_for(&Id::from("1234"), &canceller, |_| false)
+        );
+        handle.deinitialize().ok();
+    }
+}
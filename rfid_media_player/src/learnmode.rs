@@ -0,0 +1,44 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use std::path::PathBuf;
+
+/// Defines how a track is assigned to an unknown card in learn mode.
+///
+/// The assigned track is written back to the tracks file, if it can be played.
+/// The track is awaited for at most two minutes, while no other card is read.
+#[derive(Debug, PartialEq, Clone)]
+pub enum LearnMode {
+    /// The path of the track is entered on stdin, a relative path is resolved against the directory of the tracks file.
+    Stdin,
+    /// The file dropped into the given directory after the card was read is assigned,
+    /// unless it is assigned to another card already.
+    DropDirectory(PathBuf),
+}
+
+impl std::fmt::Display for LearnMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LearnMode::Stdin => write!(f, "stdin"),
+            LearnMode::DropDirectory(directory) => {
+                write!(f, "drop directory {}", directory.display())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("stdin", format!("{}", LearnMode::Stdin));
+        assert_eq!(
+            "drop directory drop",
+            format!("{}", LearnMode::DropDirectory(PathBuf::from("drop")))
+        );
+    }
+}
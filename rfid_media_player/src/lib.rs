@@ -12,50 +12,112 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
-use media_player;
-use rfid_reader::{ProductId, VendorId};
-use track_store;
+use media_player::{self, AudioBackend, MediaPlayer};
+use rfid_reader::{Debounce, RfidReaderHandle, RfidValue};
+use track_store::{self, Format, MutableTrackStore};
 
+use std::path::Path;
 use std::time::Duration;
 
 use log::error;
 
 mod cli_app;
 mod error;
+mod learner;
+mod learnmode;
+mod playerconfig;
+mod positionstore;
+mod readerdevice;
 mod rfidmediaplayer;
+mod samecardpolicy;
+mod tracksfile;
 
 pub use cli_app::{
-    convert_to_id, create_app, CONSOLE_LOG_ARGUMENT_NAME, LOG_LEVEL_ARGUMENT_NAME,
-    PRODUCT_ID_ARGUMENT_NAME, TIMEOUT_ARGUMENT_NAME, TRACKS_FILE_ARGUMENT_NAME,
-    VENDOR_ID_ARGUMENT_NAME,
+    convert_to_id, create_app, ABSENCE_TIMEOUT_ARGUMENT_NAME, AUDIO_OUTPUT_ARGUMENT_NAME,
+    CONSOLE_LOG_ARGUMENT_NAME, COOLDOWN_ARGUMENT_NAME, DEBOUNCE_ARGUMENT_NAME,
+    DEVICES_SUBCOMMAND_NAME, DROP_DIRECTORY_ARGUMENT_NAME, EVENT_DEVICE_ARGUMENT_NAME,
+    EXPORT_SUBCOMMAND_NAME, FORGET_AFTER_ARGUMENT_NAME, HEX_DIGITS_ARGUMENT_NAME,
+    HID_REPORT_SIZE_ARGUMENT_NAME, LEARN_SUBCOMMAND_NAME, LOG_LEVEL_ARGUMENT_NAME,
+    MAX_VOLUME_ARGUMENT_NAME, OUTPUT_FILE_ARGUMENT_NAME, PLAY_WHILE_PRESENT_ARGUMENT_NAME,
+    PRODUCT_ID_ARGUMENT_NAME, READER_ARGUMENT_NAME, RESUME_ARGUMENT_NAME, REWIND_ARGUMENT_NAME,
+    SAME_CARD_ARGUMENT_NAME, SCAN_SUBCOMMAND_NAME, TERMINATOR_ARGUMENT_NAME, TIMEOUT_ARGUMENT_NAME,
+    TRACKS_FILE_ARGUMENT_NAME, USB_DEVICE_ARGUMENT_NAME, VALIDATE_SUBCOMMAND_NAME,
+    VENDOR_ID_ARGUMENT_NAME, VOLUME_ARGUMENT_NAME,
 };
 pub use error::Error;
+pub use learnmode::LearnMode;
+pub use playerconfig::PlayerConfig;
+pub use positionstore::PositionStore;
+pub use readerdevice::ReaderDevice;
 pub use rfidmediaplayer::RfidMediaPlayer;
+pub use samecardpolicy::SameCardPolicy;
+
+use tracksfile::TracksFile;
 
 /// Tries to open the [`RfidMediaPlayer`](crate::RfidMediaPlayer).
 ///
 /// Returns an [`RfidMediaPlayer`](crate::RfidMediaPlayer) trait object.
 ///
+/// The cards are read from the [`ReaderDevice`](crate::ReaderDevice) on a thread of its own.
+/// The tracks are loaded from the `tracks_file`, which is watched for changes while running, so that the
+/// tracks are reloaded without restarting the application.
+/// The `tracks_file` can also be an SQLite database, see [`Format`](track_store::Format).
+/// The audio is output by the [`AudioBackend`](media_player::AudioBackend), e.g. the audio device of the system.
+/// All other options, e.g. the volume or the [`SameCardPolicy`](crate::SameCardPolicy), are given by the
+/// [`PlayerConfig`](crate::PlayerConfig).
+///
 /// Returns an [`Error`](crate::Error) whenever there is a problem with the rfid_reader, track_store or media_player.
 pub fn open(
-    vendor_id: VendorId,
-    product_id: ProductId,
-    timeout: Duration,
-    yaml_string: &str,
+    reader_device: ReaderDevice,
+    tracks_file: &Path,
+    audio_backend: &AudioBackend,
+    config: PlayerConfig,
 ) -> Result<impl RfidMediaPlayer, crate::Error> {
+    let PlayerConfig {
+        timeout,
+        absence_timeout,
+        debounce,
+        same_card_policy,
+        position_store,
+        volume,
+        max_volume,
+        learn_mode,
+    } = config;
     let mut result = Err(Error::TrackStoreError(
         "Track store not loaded yet".to_string(),
     ));
-    let result_track_store = track_store::load(yaml_string);
+    let tracks_file = TracksFile::new(tracks_file, track_store::load_file);
+    let result_track_store = tracks_file.load();
     if let Ok(track_store) = result_track_store {
-        let result_rfid_reader = rfid_reader::open(vendor_id, product_id, timeout);
+        // the reader is read on a thread of its own, in order to not block while waiting for a card
+        let open_rfid_reader = move || {
+            reader_device
+                .open(timeout)
+                .map(|reader| debounce.wrap(reader))
+        };
+        let result_rfid_reader = match absence_timeout {
+            Some(absence_timeout) => {
+                rfid_reader::spawn_with_presence(open_rfid_reader, absence_timeout)
+            }
+            None => rfid_reader::spawn(open_rfid_reader),
+        };
         if let Ok(rfid_reader) = result_rfid_reader {
-            let result_media_player = media_player::open();
+            // the maximum volume is set first, since it limits the volume
+            let result_media_player =
+                media_player::open_with_backend(audio_backend).and_then(|mut media_player| {
+                    media_player.set_max_volume(max_volume)?;
+                    media_player.set_volume(volume)?;
+                    Ok(media_player)
+                });
             if let Ok(media_player) = result_media_player {
                 result = Ok(rfidmediaplayer::open(
                     media_player,
                     rfid_reader,
                     track_store,
+                    Some(tracks_file),
+                    same_card_policy,
+                    position_store,
+                    learn_mode,
                 ));
             } else if let Err(media_player_error) = result_media_player {
                 error!(
@@ -76,25 +138,293 @@ pub fn open(
             "Loading track store resulted in error: {}",
             result_track_store_error
         );
-        result = Err(crate::Error::from(result_track_store_error))
+        result = Err(result_track_store_error)
     }
 
     result
 }
 
+/// Tries to spawn the reader of the [`ReaderDevice`](crate::ReaderDevice) in order to scan cards without playing tracks.
+///
+/// The reads are filtered by the [`Debounce`](rfid_reader::Debounce), so that a card lying on the reader is scanned once.
+/// Returns an [`Error`](crate::Error) whenever the reader cannot be opened.
+pub fn scan(
+    reader_device: ReaderDevice,
+    timeout: Duration,
+    debounce: Debounce,
+) -> Result<RfidReaderHandle, crate::Error> {
+    rfid_reader::spawn(move || {
+        reader_device
+            .open(timeout)
+            .map(|reader| debounce.wrap(reader))
+    })
+    .map_err(crate::Error::from)
+}
+
+/// Validates the tracks file before deployment and returns all problems found, see [`validate_as`](track_store::validate_as).
+///
+/// The [`Format`](track_store::Format) is selected by the extension of the file, YAML if it is unknown.
+/// The entries of an SQLite database are validated as JSON, so that no lines are reported.
+/// Additionally to the problems of the ids and entries, every track is checked to be decodable by the media player,
+/// see [`check_track`](media_player::check_track).
+/// Returns a [`TrackStoreError`](crate::Error::TrackStoreError) whenever the tracks file cannot be read.
+pub fn validate(tracks_file: &Path) -> Result<Vec<track_store::Problem>, crate::Error> {
+    let (string, format) = read_tracks_file(tracks_file)?;
+    let base_directory = tracks_file.parent().unwrap_or_else(|| Path::new(""));
+    Ok(track_store::validate_as(
+        &string,
+        format,
+        base_directory,
+        |track_path| {
+            media_player::check_track(&media_player::Track::from(track_path.to_string()))
+                .map_err(|error| error.to_string())
+        },
+    ))
+}
+
+/// Writes the tracks of the `tracks_file` to the `output_file` in the [`Format`](track_store::Format) of its extension,
+/// e.g. `tracks.csv` in order to edit the tracks in a spreadsheet.
+///
+/// An `output_file` like `tracks.sqlite` migrates the tracks to an SQLite database, which replaces all its previous
+/// entries, see [`migrate_to_sqlite`](track_store::migrate_to_sqlite).
+/// The paths are written as they are in the tracks file.
+/// Returns a [`TrackStoreError`](crate::Error::TrackStoreError) whenever the format of the `output_file` is unknown,
+/// the tracks file cannot be loaded or the output file cannot be written.
+pub fn export(tracks_file: &Path, output_file: &Path) -> Result<(), crate::Error> {
+    let format = Format::from_path(output_file).ok_or_else(|| {
+        Error::TrackStoreError(format!("Unknown format of {}", output_file.display()))
+    })?;
+    let (string, tracks_file_format) = read_tracks_file(tracks_file)?;
+    if format == Format::Sqlite {
+        track_store::migrate_to_sqlite(&string, tracks_file_format, output_file)?;
+        return Ok(());
+    }
+    let base_directory = tracks_file.parent().unwrap_or_else(|| Path::new(""));
+    let string =
+        track_store::load_as(&string, tracks_file_format, base_directory)?.save_as(format)?;
+    std::fs::write(output_file, string)
+        .map_err(|error| Error::TrackStoreError(format!("{}: {}", output_file.display(), error)))
+}
+
+/// Reads the string of the `tracks_file` and returns it with its [`Format`](track_store::Format),
+/// which is selected by the extension of the file.
+///
+/// The entries of an SQLite database are read as JSON.
+fn read_tracks_file(tracks_file: &Path) -> Result<(String, Format), crate::Error> {
+    let to_error = |error: std::io::Error| {
+        Error::TrackStoreError(format!("{}: {}", tracks_file.display(), error))
+    };
+    let format = Format::from_path(tracks_file).unwrap_or_default();
+    if format == Format::Sqlite {
+        // a missing database is reported like any other missing tracks file
+        std::fs::metadata(tracks_file).map_err(to_error)?;
+        let base_directory = tracks_file.parent().unwrap_or_else(|| Path::new(""));
+        let track_store = track_store::load_file(tracks_file, format, base_directory)?;
+        return Ok((track_store.save_as(Format::Json)?, Format::Json));
+    }
+    let string = std::fs::read_to_string(tracks_file).map_err(to_error)?;
+    Ok((string, format))
+}
+
+/// Formats the notations of the [`RfidValue`](rfid_reader::RfidValue) that can be used in the tracks file.
+///
+/// The EM4100 notation is missing, since its version byte is not read.
+/// The Wiegand 26 notation is missing if the number exceeds its 24 bits, since it would not match the card.
+pub fn format_notations(rfid_value: RfidValue) -> String {
+    if rfid_value.number() > 0x00FF_FFFF {
+        return format!("{} (hex:{})", rfid_value, rfid_value.to_hex());
+    }
+    format!(
+        "{} (hex:{}, wiegand26:{})",
+        rfid_value,
+        rfid_value.to_hex(),
+        rfid_value.to_wiegand26()
+    )
+}
+
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 mod tests {
     use super::*;
+    use rfid_reader::{HidFormat, ProductId, UsbDeviceSelector, VendorId};
+
+    fn tracks_file(name: &str, yaml_string: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "rfid_media_player_lib_{}_{}.yaml",
+            name,
+            std::process::id()
+        ));
+        std::fs::write(&path, yaml_string).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_format_notations() {
+        assert_eq!(
+            "0012345678 (hex:00BC614E, wiegand26:188,24910)",
+            format_notations(RfidValue::from(12_345_678))
+        );
+        assert_eq!(
+            "0123456789 (hex:075BCD15)",
+            format_notations(RfidValue::from(123_456_789))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let media_player_tests =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../media_player/tests");
+        let tracks_file = tracks_file(
+            "validate",
+            &format!(
+                "1234: {0}/rand1.wav
+abc:
+  - {0}/random.flac
+  - path/to/track
+hex:000004D2:
+  action: stop",
+                media_player_tests.display()
+            ),
+        );
+        let problems: Vec<String> = validate(&tracks_file)
+            .unwrap()
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect();
+        std::fs::remove_file(&tracks_file).unwrap();
+        assert_eq!(
+            vec![
+                "line 2: id \"abc\" is no valid RFID value, it never matches a card",
+                // relative paths are resolved against the directory of the tracks file
+                &format!(
+                    "line 2: track {} does not exist",
+                    std::env::temp_dir().join("path/to/track").display()
+                ),
+                "line 5: id \"hex:000004D2\" is a duplicate of line 1",
+            ],
+            problems
+        );
+
+        assert!(matches!(
+            validate(Path::new("/non/existing/tracks.yaml")),
+            Err(crate::Error::TrackStoreError(_))
+        ));
+    }
+
+    #[test]
+    fn test_export() {
+        let tracks_file = tracks_file("export", "1234: track\n5678:\n  - ~/track\n  - track");
+        let output_file = tracks_file.with_extension("csv");
+        assert_eq!(Ok(()), export(&tracks_file, &output_file));
+        let csv = std::fs::read_to_string(&output_file).unwrap();
+        assert_eq!(
+            "id,path,title\n1234,track,\n5678,~/track,\n5678,track,\n",
+            csv
+        );
+
+        // the exported file can be used as tracks file
+        let csv_file = output_file;
+        let output_file = tracks_file.with_extension("toml");
+        assert_eq!(Ok(()), export(&csv_file, &output_file));
+        let toml = std::fs::read_to_string(&output_file).unwrap();
+        assert_eq!("1234 = \"track\"\n5678 = [\"~/track\", \"track\"]\n", toml);
+
+        assert!(matches!(
+            export(&tracks_file, &tracks_file.with_extension("txt")),
+            Err(Error::TrackStoreError(_))
+        ));
+        for file in &[tracks_file, csv_file, output_file] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn test_export_sqlite() {
+        let tracks_file = tracks_file("export_sqlite", "1234: track\n5678:\n  action: stop");
+        let database = tracks_file.with_extension("sqlite");
+        assert_eq!(Ok(()), export(&tracks_file, &database));
+
+        // the database can be validated and exported back
+        let problems: Vec<String> = validate(&database)
+            .unwrap()
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect();
+        assert_eq!(
+            vec![format!(
+                "track {} does not exist",
+                std::env::temp_dir().join("track").display()
+            )],
+            problems
+        );
+        let output_file = tracks_file.with_extension("json");
+        assert_eq!(Ok(()), export(&database, &output_file));
+        let json = std::fs::read_to_string(&output_file).unwrap();
+        assert_eq!(
+            "{\n  \"1234\": \"track\",\n  \"5678\": {\n    \"action\": \"stop\"\n  }\n}",
+            json
+        );
+        for file in &[tracks_file, database, output_file] {
+            std::fs::remove_file(file).unwrap();
+        }
+
+        // a missing database is not created
+        let database = std::env::temp_dir().join("non_existing_tracks.sqlite");
+        assert!(matches!(
+            validate(&database),
+            Err(Error::TrackStoreError(_))
+        ));
+        assert!(!database.exists());
+    }
+
+    #[test]
+    fn test_scan() {
+        let script = std::env::temp_dir().join(format!(
+            "rfid_media_player_lib_scan_{}.script",
+            std::process::id()
+        ));
+        std::fs::write(
+            &script,
+            "0 12345678
+0 12345678
+",
+        )
+        .unwrap();
+        let mut handle = scan(
+            ReaderDevice::Simulated(rfid_reader::SimulatedInput::Script(script.clone())),
+            Duration::from_millis(50),
+            Debounce::new(Duration::from_secs(1), Duration::from_secs(0)),
+        )
+        .unwrap();
+        std::fs::remove_file(&script).unwrap();
+        assert_eq!(
+            Ok(rfid_reader::RfidEvent::Read(RfidValue::from(12_345_678))),
+            handle.recv_timeout(Duration::from_secs(1))
+        );
+        // the repeated read is suppressed
+        assert_eq!(
+            Err(rfid_reader::Error::Timeout),
+            handle.recv_timeout(Duration::from_millis(200))
+        );
+        assert_eq!(Ok(()), handle.deinitialize());
+    }
 
     #[test]
     fn test_invalid_yaml() {
+        let path = tracks_file("invalid_yaml", "test");
         let result = open(
-            VendorId::from(1),
-            ProductId::from(1),
-            Duration::from_millis(1000),
-            "test",
+            ReaderDevice::Usb(
+                VendorId::from(1),
+                ProductId::from(1),
+                UsbDeviceSelector::Any,
+                HidFormat::neuftech(),
+            ),
+            &path,
+            &AudioBackend::Device,
+            PlayerConfig::default(),
         );
+        std::fs::remove_file(&path).unwrap();
         if let Err(error) = result {
             assert_eq!(Error::TrackStoreError("LoadError(\"invalid type: string \\\"test\\\", expected a map at line 1 column 1\")".to_string()), error);
         } else {
@@ -102,14 +432,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_missing_tracks_file() {
+        let result = open(
+            ReaderDevice::Usb(
+                VendorId::from(1),
+                ProductId::from(1),
+                UsbDeviceSelector::Any,
+                HidFormat::neuftech(),
+            ),
+            Path::new("/non/existing/tracks.yaml"),
+            &AudioBackend::Device,
+            PlayerConfig::default(),
+        );
+        assert!(matches!(result, Err(Error::TrackStoreError(_))));
+    }
+
     #[test]
     fn test_wrong_device() {
+        let path = tracks_file("wrong_device", "1234: path");
         let result = open(
-            VendorId::from(1),
-            ProductId::from(1),
-            Duration::from_millis(1000),
-            "1234: path",
+            ReaderDevice::Usb(
+                VendorId::from(1),
+                ProductId::from(1),
+                UsbDeviceSelector::Any,
+                HidFormat::neuftech(),
+            ),
+            &path,
+            &AudioBackend::Device,
+            PlayerConfig::default(),
         );
+        std::fs::remove_file(&path).unwrap();
         if let Err(error) = result {
             assert_eq!(
                 Error::RfidReaderError("DeviceNotFound(VendorId(1), ProductId(1))".to_string()),
@@ -119,4 +472,44 @@ mod tests {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_simulated_pipeline() {
+        let track = Path::new(env!("CARGO_MANIFEST_DIR")).join("../media_player/tests/rand1.wav");
+        let path = tracks_file(
+            "simulated_pipeline",
+            &format!("0012345678: {}", track.display()),
+        );
+        let script = path.with_extension("script");
+        std::fs::write(&script, "0 0012345678").unwrap();
+        let positions_file = path.with_extension("positions.yaml");
+        let debounce = Debounce::new(Duration::from_secs(1), Duration::from_secs(0));
+        let debounce_statistics = debounce.statistics();
+        let mut rfid_media_player = open(
+            ReaderDevice::Simulated(rfid_reader::SimulatedInput::Script(script.clone())),
+            &path,
+            &AudioBackend::Null,
+            PlayerConfig::default()
+                .with_timeout(Duration::from_millis(100))
+                .with_debounce(debounce)
+                .with_position_store(Some(PositionStore::load(
+                    &positions_file,
+                    Duration::from_secs(0),
+                    None,
+                ))),
+        )
+        .unwrap();
+        for _ in 0..3 {
+            rfid_media_player.run().unwrap();
+        }
+        rfid_media_player.shutdown().unwrap();
+        assert_eq!(1, debounce_statistics.passed());
+
+        // the card was read, its track played and the playback position was stored on shutdown
+        let positions = std::fs::read_to_string(&positions_file).unwrap();
+        assert!(positions.contains("rand1.wav"), "{}", positions);
+        for file in &[path, script, positions_file] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
 }
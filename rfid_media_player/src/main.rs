@@ -1,17 +1,31 @@
 use rfid_media_player::{
-    convert_to_id, create_app, open, RfidMediaPlayer, CONSOLE_LOG_ARGUMENT_NAME,
-    LOG_LEVEL_ARGUMENT_NAME, PRODUCT_ID_ARGUMENT_NAME, TIMEOUT_ARGUMENT_NAME,
-    TRACKS_FILE_ARGUMENT_NAME, VENDOR_ID_ARGUMENT_NAME,
+    convert_to_id, create_app, export, format_notations, open, scan, validate, LearnMode,
+    PlayerConfig, PositionStore, ReaderDevice, RfidMediaPlayer, SameCardPolicy,
+    ABSENCE_TIMEOUT_ARGUMENT_NAME, AUDIO_OUTPUT_ARGUMENT_NAME, CONSOLE_LOG_ARGUMENT_NAME,
+    COOLDOWN_ARGUMENT_NAME, DEBOUNCE_ARGUMENT_NAME, DEVICES_SUBCOMMAND_NAME,
+    DROP_DIRECTORY_ARGUMENT_NAME, EVENT_DEVICE_ARGUMENT_NAME, EXPORT_SUBCOMMAND_NAME,
+    FORGET_AFTER_ARGUMENT_NAME, HEX_DIGITS_ARGUMENT_NAME, HID_REPORT_SIZE_ARGUMENT_NAME,
+    LEARN_SUBCOMMAND_NAME, LOG_LEVEL_ARGUMENT_NAME, MAX_VOLUME_ARGUMENT_NAME,
+    OUTPUT_FILE_ARGUMENT_NAME, PLAY_WHILE_PRESENT_ARGUMENT_NAME, PRODUCT_ID_ARGUMENT_NAME,
+    READER_ARGUMENT_NAME, RESUME_ARGUMENT_NAME, REWIND_ARGUMENT_NAME, SAME_CARD_ARGUMENT_NAME,
+    SCAN_SUBCOMMAND_NAME, TERMINATOR_ARGUMENT_NAME, TIMEOUT_ARGUMENT_NAME,
+    TRACKS_FILE_ARGUMENT_NAME, USB_DEVICE_ARGUMENT_NAME, VALIDATE_SUBCOMMAND_NAME,
+    VENDOR_ID_ARGUMENT_NAME, VOLUME_ARGUMENT_NAME,
 };
 
-use rfid_reader::{ProductId, VendorId};
+use media_player::AudioBackend;
+use rfid_reader::{
+    Canceller, Debounce, HidFormat, ProductId, RfidEvent, SimulatedInput, UsbDeviceSelector,
+    VendorId,
+};
 
 use flexi_logger::{Duplicate, LogTarget, Logger};
 use log::info;
 
-use signal_hook::consts::TERM_SIGNALS;
+use signal_hook::consts::{SIGHUP, TERM_SIGNALS};
 use signal_hook::iterator::Signals;
 
+use std::path::Path;
 use std::sync::Arc;
 use std::thread;
 use std::{
@@ -36,28 +50,211 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Started rfid_media_player");
 
-    let vendor_id = VendorId::from(convert_to_id(
+    if matches
+        .subcommand_matches(DEVICES_SUBCOMMAND_NAME)
+        .is_some()
+    {
+        return print_devices();
+    }
+    if matches
+        .subcommand_matches(VALIDATE_SUBCOMMAND_NAME)
+        .is_some()
+    {
+        return print_problems(Path::new(
+            matches
+                .value_of(TRACKS_FILE_ARGUMENT_NAME)
+                .ok_or("The tracks file is required for validating it")?,
+        ));
+    }
+    if let Some(export_matches) = matches.subcommand_matches(EXPORT_SUBCOMMAND_NAME) {
+        // the output file is required, therefore unwrapping is safe
+        let output_file = Path::new(export_matches.value_of(OUTPUT_FILE_ARGUMENT_NAME).unwrap());
+        export(
+            Path::new(
+                matches
+                    .value_of(TRACKS_FILE_ARGUMENT_NAME)
+                    .ok_or("The tracks file is required for exporting it")?,
+            ),
+            output_file,
+        )?;
+        println!("Exported tracks to {}", output_file.display());
+        return Ok(());
+    }
+
+    // defaults are set and values are validated, therefore unwrapping and parsing is safe.
+    let hid_format = HidFormat::new(
         matches
-            .value_of(VENDOR_ID_ARGUMENT_NAME)
+            .value_of(HID_REPORT_SIZE_ARGUMENT_NAME)
             .unwrap()
-            .to_string(),
-    )?);
-    let product_id = ProductId::from(convert_to_id(
+            .parse()?,
         matches
-            .value_of(PRODUCT_ID_ARGUMENT_NAME)
+            .value_of(TERMINATOR_ARGUMENT_NAME)
             .unwrap()
-            .to_string(),
-    )?);
+            .parse()?,
+        matches.is_present(HEX_DIGITS_ARGUMENT_NAME),
+    );
+    // the usb device is validated, therefore parsing is safe.
+    let selector: UsbDeviceSelector = match matches.value_of(USB_DEVICE_ARGUMENT_NAME) {
+        Some(usb_device) => usb_device.parse()?,
+        None => UsbDeviceSelector::Any,
+    };
+    // vendor and product id are only given together, therefore unwrapping is safe.
+    let reader_device = match (
+        matches.value_of(EVENT_DEVICE_ARGUMENT_NAME),
+        matches.value_of(READER_ARGUMENT_NAME),
+        matches.value_of(VENDOR_ID_ARGUMENT_NAME),
+    ) {
+        (Some(event_device), _, _) => ReaderDevice::EventDevice(event_device.into()),
+        // the reader is validated, therefore parsing is safe.
+        (None, Some(reader), _) => ReaderDevice::Simulated(reader.parse()?),
+        (None, None, Some(vendor_id)) => ReaderDevice::Usb(
+            VendorId::from(convert_to_id(vendor_id.to_string())?),
+            ProductId::from(convert_to_id(
+                matches
+                    .value_of(PRODUCT_ID_ARGUMENT_NAME)
+                    .unwrap()
+                    .to_string(),
+            )?),
+            selector,
+            hid_format,
+        ),
+        (None, None, None) => {
+            let known_reader = rfid_reader::find_known_reader(&selector).map_err(|error| {
+                format!(
+                    "No reader given and no single known reader found ({}), list the devices with the devices subcommand",
+                    error
+                )
+            })?;
+            info!("Selected the known reader {}", known_reader);
+            ReaderDevice::Usb(
+                known_reader.vendor_id(),
+                known_reader.product_id(),
+                selector,
+                hid_format,
+            )
+        }
+    };
+    info!("Reading cards from {}", reader_device);
 
     let timeout = Duration::from_millis(u64::from_str_radix(
         matches.value_of(TIMEOUT_ARGUMENT_NAME).unwrap(),
         10,
     )?);
 
-    let yaml_string =
-        std::fs::read_to_string(matches.value_of(TRACKS_FILE_ARGUMENT_NAME).unwrap())?;
+    let absence_timeout = if matches.is_present(PLAY_WHILE_PRESENT_ARGUMENT_NAME) {
+        // default is set and value is validated, therefore unwrapping and parsing is safe.
+        let absence_timeout = Duration::from_millis(
+            matches
+                .value_of(ABSENCE_TIMEOUT_ARGUMENT_NAME)
+                .unwrap()
+                .parse()?,
+        );
+        info!(
+            "Playing tracks while the card is present, cards are removed after {} ms",
+            absence_timeout.as_millis()
+        );
+        Some(absence_timeout)
+    } else {
+        None
+    };
+
+    // defaults are set and values are validated, therefore unwrapping and parsing is safe.
+    let debounce_interval = match absence_timeout {
+        // the presence of a card is detected from its repeated reads, therefore they must not be ignored
+        Some(_) => Duration::from_millis(0),
+        None => Duration::from_millis(matches.value_of(DEBOUNCE_ARGUMENT_NAME).unwrap().parse()?),
+    };
+    let cooldown =
+        Duration::from_millis(matches.value_of(COOLDOWN_ARGUMENT_NAME).unwrap().parse()?);
+    info!(
+        "Ignoring repeated reads for {} ms and other cards for {} ms",
+        debounce_interval.as_millis(),
+        cooldown.as_millis()
+    );
+    let debounce = Debounce::new(debounce_interval, cooldown);
+    let debounce_statistics = debounce.statistics();
 
-    let mut rfid_media_player = open(vendor_id, product_id, timeout, &yaml_string)?;
+    if matches.subcommand_matches(SCAN_SUBCOMMAND_NAME).is_some() {
+        return scan_cards(reader_device, timeout, debounce);
+    }
+
+    let tracks_file = Path::new(
+        matches
+            .value_of(TRACKS_FILE_ARGUMENT_NAME)
+            .ok_or("The tracks file is required unless scanning cards")?,
+    );
+
+    // default is set and values are restricted, therefore parsing is safe.
+    let same_card_policy: SameCardPolicy =
+        matches.value_of(SAME_CARD_ARGUMENT_NAME).unwrap().parse()?;
+
+    let position_store = if matches.is_present(RESUME_ARGUMENT_NAME) {
+        // defaults are set and values are validated, therefore unwrapping is safe.
+        let rewind = Duration::from_secs(matches.value_of(REWIND_ARGUMENT_NAME).unwrap().parse()?);
+        let forget_after_days: u64 = matches
+            .value_of(FORGET_AFTER_ARGUMENT_NAME)
+            .unwrap()
+            .parse()?;
+        let forget_after = match forget_after_days {
+            0 => None,
+            days => Some(Duration::from_secs(days * 24 * 60 * 60)),
+        };
+        // e.g. tracks.yaml -> tracks.positions.yaml
+        let positions_file = tracks_file.with_extension("positions.yaml");
+        info!(
+            "Resuming tracks with positions from {}",
+            positions_file.display()
+        );
+        Some(PositionStore::load(positions_file, rewind, forget_after))
+    } else {
+        None
+    };
+
+    // default is set and value is validated, therefore unwrapping and parsing is safe.
+    let audio_backend: AudioBackend = matches
+        .value_of(AUDIO_OUTPUT_ARGUMENT_NAME)
+        .unwrap()
+        .parse()?;
+    info!("Audio is output to {}", audio_backend);
+
+    // defaults are set and values are validated, therefore unwrapping is safe.
+    let volume_percent: u8 = matches.value_of(VOLUME_ARGUMENT_NAME).unwrap().parse()?;
+    let max_volume_percent: u8 = matches
+        .value_of(MAX_VOLUME_ARGUMENT_NAME)
+        .unwrap()
+        .parse()?;
+    info!(
+        "Volume is {} %, maximum volume is {} %",
+        volume_percent, max_volume_percent
+    );
+
+    let learn_mode = matches
+        .subcommand_matches(LEARN_SUBCOMMAND_NAME)
+        .map(
+            |learn_matches| match learn_matches.value_of(DROP_DIRECTORY_ARGUMENT_NAME) {
+                Some(drop_directory) => LearnMode::DropDirectory(drop_directory.into()),
+                None => LearnMode::Stdin,
+            },
+        );
+    if reader_device == ReaderDevice::Simulated(SimulatedInput::Stdin)
+        && learn_mode == Some(LearnMode::Stdin)
+    {
+        return Err("The simulated reader and the learn mode cannot both read from stdin".into());
+    }
+    if let Some(learn_mode) = &learn_mode {
+        info!("Learning unknown cards via {}", learn_mode);
+    }
+
+    let config = PlayerConfig::default()
+        .with_timeout(timeout)
+        .with_absence_timeout(absence_timeout)
+        .with_debounce(debounce)
+        .with_same_card_policy(same_card_policy)
+        .with_position_store(position_store)
+        .with_volume(f32::from(volume_percent) / 100.0)
+        .with_max_volume(f32::from(max_volume_percent) / 100.0)
+        .with_learn_mode(learn_mode);
+    let mut rfid_media_player = open(reader_device, tracks_file, &audio_backend, config)?;
 
     info!("Application opened successfully");
 
@@ -65,22 +262,107 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
 
-    // all terminating signals
-    let mut signals = Signals::new(TERM_SIGNALS)?;
+    // Shared atomic bool to signal that the tracks file shall be reloaded
+    let reload = Arc::new(AtomicBool::new(false));
+    let rl = reload.clone();
+
+    // all terminating signals and SIGHUP
+    let mut signals = Signals::new(TERM_SIGNALS.iter().chain(&[SIGHUP]))?;
+
+    // cancels waiting for a card, so that terminating signals are handled immediately
+    let canceller = rfid_media_player.canceller();
 
-    // spawn a thread to react to all terminating signals
+    // spawn a thread to react to all terminating signals and SIGHUP
     thread::spawn(move || {
         for sig in signals.forever() {
             info!("Received signal {:?}", sig);
-            r.store(false, Ordering::SeqCst);
+            if sig == SIGHUP {
+                rl.store(true, Ordering::SeqCst);
+            } else {
+                r.store(false, Ordering::SeqCst);
+                canceller.cancel();
+            }
         }
     });
 
     while running.load(Ordering::SeqCst) {
+        if reload.swap(false, Ordering::SeqCst) {
+            // an invalid tracks file is logged and the previously loaded tracks are kept
+            rfid_media_player.reload().ok();
+        }
         rfid_media_player.run()?;
     }
     info!("Shutting down ...");
     rfid_media_player.shutdown()?;
+    info!("Card reads: {}", debounce_statistics);
     info!("Shutting complete.");
     Ok(())
 }
+
+/// Prints the notations of the scanned cards until a terminating signal is received.
+#[cfg(not(tarpaulin_include))]
+fn scan_cards(
+    reader_device: ReaderDevice,
+    timeout: Duration,
+    debounce: Debounce,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut handle = scan(reader_device, timeout, debounce)?;
+    cancel_on_signal(handle.canceller())?;
+    println!("Put a card on the reader, stop scanning with Ctrl+c");
+    loop {
+        match handle.recv_timeout(Duration::from_secs(1)) {
+            Ok(RfidEvent::Read(rfid_value)) => println!("{}", format_notations(rfid_value)),
+            Ok(RfidEvent::Disconnected) => info!("Reader disconnected"),
+            Ok(RfidEvent::Reconnected) => info!("Reader reconnected"),
+            Ok(RfidEvent::Error(error)) => return Err(error.into()),
+            Err(rfid_reader::Error::Timeout) => {}
+            Ok(_) | Err(_) => break,
+        }
+    }
+    handle.deinitialize()?;
+    Ok(())
+}
+
+/// Prints the attached USB devices.
+#[cfg(not(tarpaulin_include))]
+fn print_devices() -> Result<(), Box<dyn std::error::Error>> {
+    let devices = rfid_reader::list_devices()?;
+    for device in &devices {
+        println!("{}", device);
+    }
+    if !devices.iter().any(|device| device.known_reader()) {
+        println!("No known reader found, readable devices may be readers as well");
+    }
+    Ok(())
+}
+
+/// Prints the problems of the tracks file, fails if there are any besides warnings.
+#[cfg(not(tarpaulin_include))]
+fn print_problems(tracks_file: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let problems = validate(tracks_file)?;
+    for problem in &problems {
+        println!("{}: {}", tracks_file.display(), problem);
+    }
+    let errors = problems
+        .iter()
+        .filter(|problem| !problem.is_warning())
+        .count();
+    if errors > 0 {
+        return Err(format!("{} problems found in {}", errors, tracks_file.display()).into());
+    }
+    println!("No problems found in {}", tracks_file.display());
+    Ok(())
+}
+
+/// Cancels the reader on all terminating signals.
+#[cfg(not(tarpaulin_include))]
+fn cancel_on_signal(canceller: Canceller) -> Result<(), Box<dyn std::error::Error>> {
+    let mut signals = Signals::new(TERM_SIGNALS)?;
+    thread::spawn(move || {
+        for sig in signals.forever() {
+            info!("Received signal {:?}", sig);
+            canceller.cancel();
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,111 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::learnmode::LearnMode;
+use crate::positionstore::PositionStore;
+use crate::samecardpolicy::SameCardPolicy;
+
+use rfid_reader::Debounce;
+
+use std::time::Duration;
+
+/// The options with which the [`RfidMediaPlayer`](crate::RfidMediaPlayer) is [`open`](crate::open)ed.
+///
+/// The [`default`](std::default::Default::default) reads the reader with a timeout of one second, does not filter reads,
+/// [`Ignore`](crate::SameCardPolicy::Ignore)s the card of the current track, neither resumes tracks nor learns cards and
+/// plays the tracks with their original volume.
+///
+/// # Example
+/// ```rust
+/// use rfid_media_player::{PlayerConfig, SameCardPolicy};
+///
+/// // the other options keep their default
+/// let config = PlayerConfig::default()
+///     .with_same_card_policy(SameCardPolicy::Toggle)
+///     .with_volume(0.5);
+/// ```
+#[derive(Debug)]
+pub struct PlayerConfig {
+    pub(crate) timeout: Duration,
+    pub(crate) absence_timeout: Option<Duration>,
+    pub(crate) debounce: Debounce,
+    pub(crate) same_card_policy: SameCardPolicy,
+    pub(crate) position_store: Option<PositionStore>,
+    pub(crate) volume: f32,
+    pub(crate) max_volume: f32,
+    pub(crate) learn_mode: Option<LearnMode>,
+}
+
+impl PlayerConfig {
+    /// Returns the config in which the reader waits at most `timeout` per read.
+    ///
+    /// Shutting down waits at most this `timeout` for the reader, the playback is stopped immediately.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the config in which the tracks are only played while the card lies on the reader.
+    ///
+    /// Removing the card (i.e. it is not read within the `absence_timeout`) pauses the playback and putting it back
+    /// resumes it.
+    pub fn with_absence_timeout(mut self, absence_timeout: Option<Duration>) -> Self {
+        self.absence_timeout = absence_timeout;
+        self
+    }
+
+    /// Returns the config in which the reads are filtered by the [`Debounce`](rfid_reader::Debounce),
+    /// whose statistics stay available to the caller.
+    pub fn with_debounce(mut self, debounce: Debounce) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Returns the config with the [`SameCardPolicy`](crate::SameCardPolicy), which defines what happens when the card
+    /// of the current track is read again.
+    pub fn with_same_card_policy(mut self, same_card_policy: SameCardPolicy) -> Self {
+        self.same_card_policy = same_card_policy;
+        self
+    }
+
+    /// Returns the config in which tracks are resumed at their position stored in the [`PositionStore`](crate::PositionStore).
+    pub fn with_position_store(mut self, position_store: Option<PositionStore>) -> Self {
+        self.position_store = position_store;
+        self
+    }
+
+    /// Returns the config with the `volume`, where `1.0` is the original volume of the tracks.
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Returns the config with the `max_volume`, which can never be exceeded, where `1.0` is the original volume of the tracks.
+    pub fn with_max_volume(mut self, max_volume: f32) -> Self {
+        self.max_volume = max_volume;
+        self
+    }
+
+    /// Returns the config in which unknown cards are assigned a track by the [`LearnMode`](crate::LearnMode),
+    /// which is written back to the tracks file.
+    pub fn with_learn_mode(mut self, learn_mode: Option<LearnMode>) -> Self {
+        self.learn_mode = learn_mode;
+        self
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        PlayerConfig {
+            timeout: Duration::from_secs(1),
+            absence_timeout: None,
+            debounce: Debounce::new(Duration::from_secs(0), Duration::from_secs(0)),
+            same_card_policy: SameCardPolicy::Ignore,
+            position_store: None,
+            volume: 1.0,
+            max_volume: 1.0,
+            learn_mode: None,
+        }
+    }
+}
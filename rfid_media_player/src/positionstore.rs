@@ -0,0 +1,283 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Store of the playback positions of playlists.
+///
+/// A playlist is identified by its first track. Its position consists of the index of the current
+/// track and the playback position within this track.
+/// The positions are persisted in a yaml file, so that a playlist can be resumed where it was
+/// stopped, even after a restart of the application.
+/// On resume, the position is rewound by the configured duration.
+/// Positions that were not updated for longer than the configured duration are forgotten.
+#[derive(Debug)]
+pub struct PositionStore {
+    path: PathBuf,
+    rewind: Duration,
+    forget_after: Option<Duration>,
+    positions: BTreeMap<String, StoredPosition>,
+    changed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+struct StoredPosition {
+    /// Index of the current track in the playlist.
+    #[serde(default)]
+    track: usize,
+    /// Playback position in milliseconds.
+    position: u64,
+    /// Seconds since the unix epoch of the last update.
+    updated: u64,
+}
+
+impl PositionStore {
+    /// Loads the [`PositionStore`](crate::PositionStore) from the file at `path`.
+    ///
+    /// Starts with an empty store if the file does not exist or cannot be parsed.
+    /// Resumed positions are rewound by `rewind`. If `forget_after` is given, positions that were not
+    /// updated for this duration are forgotten.
+    pub fn load(
+        path: impl Into<PathBuf>,
+        rewind: Duration,
+        forget_after: Option<Duration>,
+    ) -> Self {
+        let path = path.into();
+        let positions = match std::fs::read_to_string(&path) {
+            Ok(yaml_string) => match serde_yaml::from_str(&yaml_string) {
+                Ok(positions) => positions,
+                Err(error) => {
+                    warn!(
+                        "Positions file {} is invalid and is ignored: {}",
+                        path.display(),
+                        error
+                    );
+                    BTreeMap::new()
+                }
+            },
+            Err(error) => {
+                debug!(
+                    "Positions file {} could not be read: {}",
+                    path.display(),
+                    error
+                );
+                BTreeMap::new()
+            }
+        };
+        PositionStore {
+            path,
+            rewind,
+            forget_after,
+            positions,
+            changed: false,
+        }
+    }
+
+    /// Returns the track index and position to resume the playlist from, which are zero if the playlist is unknown or forgotten.
+    pub(crate) fn position(&self, playlist: &str) -> (usize, Duration) {
+        self.position_at(playlist, now())
+    }
+
+    /// Updates the track index and position of the playlist.
+    pub(crate) fn update(&mut self, playlist: &str, track: usize, position: Duration) {
+        self.update_at(playlist, track, position, now());
+    }
+
+    /// Removes the position of the playlist, e.g. when it was played to the end.
+    pub(crate) fn remove(&mut self, playlist: &str) {
+        if self.positions.remove(playlist).is_some() {
+            self.changed = true;
+        }
+    }
+
+    /// Forgets all expired positions and writes the store to its file, if any position has changed.
+    ///
+    /// Returns a [`PositionStoreError`](crate::Error::PositionStoreError) if the file cannot be written.
+    pub(crate) fn save(&mut self) -> Result<(), Error> {
+        if !self.changed {
+            return Ok(());
+        }
+        self.forget_expired_at(now());
+        let yaml_string = serde_yaml::to_string(&self.positions)
+            .map_err(|error| Error::PositionStoreError(error.to_string()))?;
+        // write to a temporary file first, so that the positions file is never left half written
+        let temporary_path = self.path.with_extension("tmp");
+        std::fs::write(&temporary_path, yaml_string)
+            .and_then(|_| std::fs::rename(&temporary_path, &self.path))
+            .map_err(|error| Error::PositionStoreError(error.to_string()))?;
+        self.changed = false;
+        Ok(())
+    }
+
+    fn position_at(&self, playlist: &str, now: u64) -> (usize, Duration) {
+        match self.positions.get(playlist) {
+            Some(stored_position) if !self.is_expired(stored_position, now) => (
+                stored_position.track,
+                Duration::from_millis(stored_position.position).saturating_sub(self.rewind),
+            ),
+            _ => (0, Duration::from_secs(0)),
+        }
+    }
+
+    fn update_at(&mut self, playlist: &str, track: usize, position: Duration, now: u64) {
+        let position = position.as_millis() as u64;
+        let unchanged = self
+            .positions
+            .get(playlist)
+            .map(|stored_position| (stored_position.track, stored_position.position))
+            == Some((track, position));
+        if !unchanged {
+            let stored_position = StoredPosition {
+                track,
+                position,
+                updated: now,
+            };
+            self.positions.insert(playlist.to_string(), stored_position);
+            self.changed = true;
+        }
+    }
+
+    fn forget_expired_at(&mut self, now: u64) {
+        let expired: Vec<String> = self
+            .positions
+            .iter()
+            .filter(|(_, stored_position)| self.is_expired(stored_position, now))
+            .map(|(playlist, _)| playlist.clone())
+            .collect();
+        for playlist in expired {
+            debug!("Forget position of playlist {}", playlist);
+            self.positions.remove(&playlist);
+        }
+    }
+
+    fn is_expired(&self, stored_position: &StoredPosition, now: u64) -> bool {
+        match self.forget_after {
+            Some(forget_after) => {
+                stored_position
+                    .updated
+                    .saturating_add(forget_after.as_secs())
+                    < now
+            }
+            None => false,
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn temporary_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "rfid_media_player_{}_{}.yaml",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_unknown_track() {
+        let store = PositionStore::load(
+            temporary_file("unknown_track"),
+            Duration::from_secs(5),
+            None,
+        );
+        assert_eq!((0, Duration::from_secs(0)), store.position("track"));
+    }
+
+    #[test]
+    fn test_rewind() {
+        let mut store = PositionStore::load(temporary_file("rewind"), Duration::from_secs(5), None);
+        store.update("track", 0, Duration::from_secs(65));
+        assert_eq!((0, Duration::from_secs(60)), store.position("track"));
+        store.update("track", 2, Duration::from_secs(3));
+        assert_eq!((2, Duration::from_secs(0)), store.position("track"));
+        store.remove("track");
+        assert_eq!((0, Duration::from_secs(0)), store.position("track"));
+    }
+
+    #[test]
+    fn test_forget_after() {
+        let mut store = PositionStore::load(
+            temporary_file("forget_after"),
+            Duration::from_secs(0),
+            Some(Duration::from_secs(100)),
+        );
+        store.update_at("track", 1, Duration::from_secs(10), 1000);
+        assert_eq!(
+            (1, Duration::from_secs(10)),
+            store.position_at("track", 1100)
+        );
+        assert_eq!(
+            (0, Duration::from_secs(0)),
+            store.position_at("track", 1101)
+        );
+
+        store.forget_expired_at(1101);
+        assert!(store.positions.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = temporary_file("save_and_load");
+        let mut store = PositionStore::load(&path, Duration::from_secs(0), None);
+        store.update("path/to/track", 3, Duration::from_millis(1500));
+        assert_eq!(Ok(()), store.save());
+
+        let store = PositionStore::load(&path, Duration::from_secs(0), None);
+        assert_eq!(
+            (3, Duration::from_millis(1500)),
+            store.position("path/to/track")
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_load_invalid_file() {
+        let path = temporary_file("invalid_file");
+        std::fs::write(&path, "invalid").unwrap();
+        let store = PositionStore::load(&path, Duration::from_secs(0), None);
+        assert!(store.positions.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_save_error() {
+        let mut store = PositionStore::load(
+            "/non/existing/directory/positions.yaml",
+            Duration::from_secs(0),
+            None,
+        );
+        // nothing changed, nothing to save
+        assert_eq!(Ok(()), store.save());
+        store.update("track", 0, Duration::from_secs(1));
+        assert!(matches!(store.save(), Err(Error::PositionStoreError(_))));
+    }
+
+    #[test]
+    fn test_load_without_track_index() {
+        let path = temporary_file("without_track_index");
+        std::fs::write(&path, "track:\n  position: 2000\n  updated: 0\n").unwrap();
+        let store = PositionStore::load(&path, Duration::from_secs(0), None);
+        assert_eq!((0, Duration::from_secs(2)), store.position("track"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
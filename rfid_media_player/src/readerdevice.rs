@@ -0,0 +1,145 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use rfid_reader::{HidFormat, ProductId, RfidReader, SimulatedInput, UsbDeviceSelector, VendorId};
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The RFID reader device and the backend used to read from it.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReaderDevice {
+    /// The USB device with the vendor and product id, which is read via libusb.
+    ///
+    /// The [`UsbDeviceSelector`](rfid_reader::UsbDeviceSelector) selects one of several devices with these ids.
+    /// The kernel HID driver is detached while the device is read, its keyboard reports are decoded in the [`HidFormat`](rfid_reader::HidFormat).
+    Usb(VendorId, ProductId, UsbDeviceSelector, HidFormat),
+    /// The Linux input event device at the path, e.g. `/dev/input/event0`, which is grabbed exclusively.
+    ///
+    /// May also be a file with an event stream recorded from an input device.
+    EventDevice(PathBuf),
+    /// The simulated reader, which reads the RFID values from the input instead of a device.
+    Simulated(SimulatedInput),
+}
+
+impl ReaderDevice {
+    /// Opens the [`RfidReader`](rfid_reader::RfidReader) of the device.
+    pub(crate) fn open(
+        &self,
+        timeout: Duration,
+    ) -> Result<Box<dyn RfidReader>, rfid_reader::Error> {
+        let rfid_reader: Box<dyn RfidReader> = match self {
+            ReaderDevice::Usb(vendor_id, product_id, selector, hid_format) => {
+                Box::new(rfid_reader::open_hid(
+                    *vendor_id,
+                    *product_id,
+                    selector.clone(),
+                    *hid_format,
+                    timeout,
+                )?)
+            }
+            #[cfg(target_os = "linux")]
+            ReaderDevice::EventDevice(path) => {
+                Box::new(rfid_reader::open_event_device(path, timeout)?)
+            }
+            #[cfg(not(target_os = "linux"))]
+            ReaderDevice::EventDevice(path) => {
+                return Err(rfid_reader::Error::OtherInputError(format!(
+                    "{}: input event devices are only supported on Linux",
+                    path.display()
+                )))
+            }
+            ReaderDevice::Simulated(input) => {
+                Box::new(rfid_reader::open_simulated(input.clone(), timeout)?)
+            }
+        };
+        Ok(rfid_reader)
+    }
+}
+
+impl std::fmt::Display for ReaderDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReaderDevice::Usb(vendor_id, product_id, UsbDeviceSelector::Any, hid_format) => {
+                write!(
+                    f,
+                    "USB device {}:{} ({})",
+                    vendor_id, product_id, hid_format
+                )
+            }
+            ReaderDevice::Usb(vendor_id, product_id, selector, hid_format) => write!(
+                f,
+                "USB device {}:{} at {} ({})",
+                vendor_id, product_id, selector, hid_format
+            ),
+            ReaderDevice::EventDevice(path) => write!(f, "input event device {}", path.display()),
+            ReaderDevice::Simulated(input) => write!(f, "simulated reader {}", input),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_open_event_device() {
+        let reader_device = ReaderDevice::EventDevice(PathBuf::from("/non/existing/event0"));
+        assert_eq!(
+            rfid_reader::Error::InputDeviceNotFound(PathBuf::from("/non/existing/event0")),
+            reader_device.open(Duration::from_millis(100)).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_open_simulated() {
+        let reader_device =
+            ReaderDevice::Simulated(SimulatedInput::Pipe(PathBuf::from("/non/existing/pipe")));
+        assert_eq!(
+            rfid_reader::Error::InputDeviceNotFound(PathBuf::from("/non/existing/pipe")),
+            reader_device.open(Duration::from_millis(100)).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "USB device 0x16c0:0x27db (3 byte reports with decimal digits terminated by enter)",
+            format!(
+                "{}",
+                ReaderDevice::Usb(
+                    VendorId::from(0x16c0),
+                    ProductId::from(0x27db),
+                    UsbDeviceSelector::Any,
+                    HidFormat::neuftech()
+                )
+            )
+        );
+        assert_eq!(
+            "USB device 0x16c0:0x27db at port:1-1.2 (3 byte reports with decimal digits terminated by enter)",
+            format!(
+                "{}",
+                ReaderDevice::Usb(
+                    VendorId::from(0x16c0),
+                    ProductId::from(0x27db),
+                    UsbDeviceSelector::Port(1, vec![1, 2]),
+                    HidFormat::neuftech()
+                )
+            )
+        );
+        assert_eq!(
+            "input event device /dev/input/event0",
+            format!(
+                "{}",
+                ReaderDevice::EventDevice(PathBuf::from("/dev/input/event0"))
+            )
+        );
+        assert_eq!(
+            "simulated reader stdin",
+            format!("{}", ReaderDevice::Simulated(SimulatedInput::Stdin))
+        );
+    }
+}
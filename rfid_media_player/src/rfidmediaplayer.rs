@@ -3,12 +3,26 @@
 #![forbid(unsafe_code)]
 
 use crate::error::Error;
+use crate::learner::Learner;
+use crate::learnmode::LearnMode;
+use crate::positionstore::PositionStore;
+use crate::samecardpolicy::SameCardPolicy;
+use crate::tracksfile::TracksFile;
 
 use log::{debug, error, info, warn};
 
-use media_player::MediaPlayer;
-use rfid_reader::RfidReader;
-use track_store::TrackStore;
+use media_player::{MediaPlayer, Track};
+use rfid_reader::{Canceller, RfidEvent, RfidReaderHandle};
+use track_store::{Action, Format, MutableTrackStore, Playlist, TrackEntry, TrackStore};
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Interval in which the playback positions are persisted while a playlist is played.
+const SAVE_POSITIONS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Time [`run`](crate::RfidMediaPlayer::run) waits at most for an RFID value, before checking e.g. the sleep timer.
+const RUN_INTERVAL: Duration = Duration::from_millis(100);
 
 /// The `RfidMediaPlayer` trait.
 pub trait RfidMediaPlayer {
@@ -17,65 +31,155 @@ pub trait RfidMediaPlayer {
     /// Returns either a success or an [`Error`](crate::Error).
     /// Implementors must implement run non-blocking in order to allow the callee
     /// to stop the application on terminated signals etc.
+    /// Returns immediately when the [`Canceller`](rfid_reader::Canceller) cancels reading.
     fn run(&mut self) -> Result<(), Error>;
     /// `shutdown`s the `RfidMediaPlayer`.
     ///
     /// Returns either a success or an [`Error`](crate::Error).
     /// Must be called before the application is stopped.
     fn shutdown(&mut self) -> Result<(), Error>;
+    /// `reload`s the tracks from the tracks file.
+    ///
+    /// Changes of the tracks file are detected during [`run`](crate::RfidMediaPlayer::run) anyway,
+    /// this enables reloading on demand (e.g. on SIGHUP).
+    /// Returns a [`TrackStoreError`](crate::Error::TrackStoreError) if the tracks file is invalid,
+    /// the previously loaded tracks are kept in this case.
+    fn reload(&mut self) -> Result<(), Error>;
+    /// Returns a [`Canceller`](rfid_reader::Canceller), which stops reading RFID values from any thread,
+    /// e.g. on a terminating signal, so that the application can shut down immediately.
+    fn canceller(&self) -> Canceller;
 }
 
-struct RfidMediaPlayerImplementation<M, R, T>
+struct RfidMediaPlayerImplementation<M, T>
 where
     M: MediaPlayer,
-    R: RfidReader,
-    T: TrackStore,
+    T: MutableTrackStore,
 {
     media_player: M,
-    rfid_reader: R,
+    rfid_reader: RfidReaderHandle,
     track_store: T,
+    tracks_file: Option<TracksFile<T>>,
+    same_card_policy: SameCardPolicy,
+    position_store: Option<PositionStore>,
+    learner: Option<Learner>,
+    current_playlist: Vec<Track>,
+    current_entry: Option<TrackEntry>,
+    last_save: Instant,
+    sleep_deadline: Option<Instant>,
+    paused_by_removal: bool,
 }
 
 /// Opens the `RfidMediaPlayer`.
-pub(crate) fn open<M, R, T>(media_player: M, rfid_reader: R, track_store: T) -> impl RfidMediaPlayer
+pub(crate) fn open<M, T>(
+    media_player: M,
+    rfid_reader: RfidReaderHandle,
+    track_store: T,
+    tracks_file: Option<TracksFile<T>>,
+    same_card_policy: SameCardPolicy,
+    position_store: Option<PositionStore>,
+    learn_mode: Option<LearnMode>,
+) -> impl RfidMediaPlayer
 where
     M: MediaPlayer,
-    R: RfidReader,
-    T: TrackStore,
+    T: MutableTrackStore,
 {
-    RfidMediaPlayerImplementation::open(media_player, rfid_reader, track_store)
+    RfidMediaPlayerImplementation::open(
+        media_player,
+        rfid_reader,
+        track_store,
+        tracks_file,
+        same_card_policy,
+        position_store,
+        learn_mode,
+    )
 }
 
-impl<M, R, T> RfidMediaPlayer for RfidMediaPlayerImplementation<M, R, T>
+impl<M, T> RfidMediaPlayer for RfidMediaPlayerImplementation<M, T>
 where
     M: MediaPlayer,
-    R: RfidReader,
-    T: TrackStore,
+    T: MutableTrackStore,
 {
     fn run(&mut self) -> Result<(), Error> {
+        if let Some(tracks_file) = &mut self.tracks_file {
+            if tracks_file.has_changed() {
+                // an invalid tracks file is logged and the previously loaded tracks are kept
+                self.reload().ok();
+            }
+        }
         let mut result = Ok(());
-        let read_result = self.rfid_reader.read();
+        let read_result = self.rfid_reader.recv_timeout(RUN_INTERVAL);
         match read_result {
-            Ok(rfid_value) => {
+            Ok(RfidEvent::Read(rfid_value)) => {
                 info!("Received RFID value: {}", rfid_value);
-                let option_track_path = get_track(&self.track_store, rfid_value);
-                if let Some(track_path) = option_track_path {
-                    result = play_track(&mut self.media_player, track_path);
+                result = self.read_card(track_store::Id::from(rfid_value));
+            }
+            Ok(RfidEvent::CardPresent(rfid_value)) => {
+                info!("Card {} put on the reader", rfid_value);
+                let id = track_store::Id::from(rfid_value);
+                let resume = self.paused_by_removal && self.is_current(&id);
+                self.paused_by_removal = false;
+                result = if resume {
+                    info!("Resume playing");
+                    self.media_player.resume().map_err(Error::from)
+                } else {
+                    self.read_card(id)
+                };
+            }
+            Ok(RfidEvent::CardRemoved(rfid_value)) => {
+                info!("Card {} removed from the reader", rfid_value);
+                let id = track_store::Id::from(rfid_value);
+                if self.is_current(&id) && self.media_player.position().is_some() {
+                    info!("Pause playing until the card is put back");
+                    self.paused_by_removal = true;
+                    result = self.media_player.pause().map_err(Error::from);
                 }
             }
-            Err(error) => match error {
+            Ok(RfidEvent::Error(error)) | Err(error) => match error {
                 rfid_reader::Error::Timeout => result = Ok(()),
                 _ => {
                     error!("Reading RFID resolved in error: {}", error);
                     result = Err(Error::from(error));
                 }
             },
+            // the playback goes on while the reader is disconnected
+            Ok(RfidEvent::Disconnected) => {
+                warn!("RFID reader disconnected, waiting for it to be connected again")
+            }
+            Ok(RfidEvent::Reconnected) => info!("RFID reader connected again"),
+            Ok(RfidEvent::Cancelled) => return Ok(()),
+        }
+        if matches!(self.sleep_deadline, Some(deadline) if Instant::now() >= deadline) {
+            info!("Sleep timer elapsed, stop playing");
+            if let Err(error) = self.stop_playback() {
+                result = Err(error);
+            }
+        }
+        if self.has_finished_loop() {
+            if let Err(error) = self.replay() {
+                result = Err(error);
+            }
+        }
+        self.update_position();
+        if self.last_save.elapsed() >= SAVE_POSITIONS_INTERVAL {
+            // a failed save is logged and must not stop the playback
+            self.save_positions().ok();
         }
         result
     }
 
     fn shutdown(&mut self) -> Result<(), Error> {
         let mut result = Ok(());
+        self.update_position();
+        let save_positions_result = self.save_positions();
+        if let Err(error) = save_positions_result {
+            result = Err(error)
+        }
+        let media_player_stop_result = self.media_player.stop();
+        if let Err(error) = media_player_stop_result {
+            error!("Stopping media player resulted in error: {}", error);
+            result = Err(Error::from(error))
+        }
+        // the reader is de-initialized last, since this waits for the current read
         let rfid_reader_deinit_result = self.rfid_reader.deinitialize();
         if let Err(error) = rfid_reader_deinit_result {
             error!(
@@ -84,43 +188,305 @@ where
             );
             result = Err(Error::from(error))
         }
-        let media_player_stop_result = self.media_player.stop();
-        if let Err(error) = media_player_stop_result {
-            error!("Stopping media player resulted in error: {}", error);
-            result = Err(Error::from(error))
-        }
         result
     }
+
+    fn reload(&mut self) -> Result<(), Error> {
+        if let Some(tracks_file) = &self.tracks_file {
+            match tracks_file.load() {
+                Ok(track_store) => {
+                    info!("Reloaded tracks from {}", tracks_file.path().display());
+                    self.track_store = track_store;
+                }
+                Err(error) => {
+                    error!(
+                        "Reloading tracks from {} resulted in error, keeping the previous tracks: {}",
+                        tracks_file.path().display(),
+                        error
+                    );
+                    return Err(error);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn canceller(&self) -> Canceller {
+        self.rfid_reader.canceller()
+    }
 }
 
-impl<M, R, T> RfidMediaPlayerImplementation<M, R, T>
+impl<M, T> RfidMediaPlayerImplementation<M, T>
 where
     M: MediaPlayer,
-    R: RfidReader,
-    T: TrackStore,
+    T: MutableTrackStore,
 {
-    fn open(media_player: M, rfid_reader: R, track_store: T) -> Self {
+    fn open(
+        media_player: M,
+        rfid_reader: RfidReaderHandle,
+        track_store: T,
+        tracks_file: Option<TracksFile<T>>,
+        same_card_policy: SameCardPolicy,
+        position_store: Option<PositionStore>,
+        learn_mode: Option<LearnMode>,
+    ) -> Self {
         RfidMediaPlayerImplementation {
             media_player: media_player,
             rfid_reader: rfid_reader,
             track_store: track_store,
+            tracks_file,
+            same_card_policy,
+            position_store,
+            learner: learn_mode.map(Learner::new),
+            current_playlist: Vec::new(),
+            current_entry: None,
+            last_save: Instant::now(),
+            sleep_deadline: None,
+            paused_by_removal: false,
+        }
+    }
+
+    /// Executes the action or plays the tracks of the card with the `id`.
+    fn read_card(&mut self, id: track_store::Id) -> Result<(), Error> {
+        debug!("Converted to id {}", id);
+        if let Some(action) = get_action(&self.track_store, &id) {
+            return self.execute(action);
+        }
+        let mut option_entry = get_entry(&self.track_store, &id).cloned();
+        if option_entry.is_none() && self.track_store.get_entry(&id).is_none() {
+            option_entry = self.learn(id);
+        }
+        match option_entry {
+            Some(entry) => self.play(entry),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the tracks of the card with the `id` are played currently.
+    fn is_current(&self, id: &track_store::Id) -> bool {
+        !self.current_playlist.is_empty()
+            && get_entry(&self.track_store, id).map(to_tracks).as_ref()
+                == Some(&self.current_playlist)
+    }
+
+    fn play(&mut self, entry: TrackEntry) -> Result<(), Error> {
+        let playlist = to_tracks(&entry);
+        if self.current_playlist == playlist {
+            return play_playlist(&mut self.media_player, &playlist, self.same_card_policy);
+        }
+        self.update_position();
+        let (index, start) = match &self.position_store {
+            Some(position_store) => position_store.position(playlist[0].as_ref()),
+            None => (0, Duration::from_secs(0)),
+        };
+        play_playlist_from(&mut self.media_player, &playlist, index, start)?;
+        info!("Playing {}", entry);
+        let shuffles = entry.shuffles();
+        self.current_playlist = playlist;
+        self.current_entry = Some(entry);
+        // a failed save is logged and must not stop the playback
+        self.save_positions().ok();
+        if shuffles {
+            self.execute(Action::Shuffle)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the current playlist loops and was played until its end.
+    fn has_finished_loop(&self) -> bool {
+        matches!(&self.current_entry, Some(entry) if entry.loops())
+            && !self.current_playlist.is_empty()
+            && self.media_player.playlist_index().is_none()
+    }
+
+    /// Plays the current playlist again from its beginning.
+    fn replay(&mut self) -> Result<(), Error> {
+        info!("Playlist finished, play it again");
+        play_playlist_from(
+            &mut self.media_player,
+            &self.current_playlist,
+            0,
+            Duration::from_secs(0),
+        )?;
+        match &self.current_entry {
+            Some(entry) if entry.shuffles() => self.execute(Action::Shuffle),
+            _ => Ok(()),
+        }
+    }
+
+    /// Assigns a track to the unknown card in learn mode and returns the entry to play.
+    ///
+    /// A track that cannot be played is not assigned.
+    /// A failed learning is logged and must not stop the playback.
+    fn learn(&mut self, id: track_store::Id) -> Option<TrackEntry> {
+        let canceller = self.rfid_reader.canceller();
+        let track_store = &self.track_store;
+        let track_path = self
+            .learner
+            .as_ref()?
+            .track_for(&id, &canceller, |track_path| {
+                track_store.contains_track(track_path)
+            })?;
+        let playlist = Playlist::from(track_path.clone());
+        if let Err(problem) = self.check_playlist(&playlist) {
+            warn!(
+                "Track {} is not assigned to RFID {}, {}",
+                track_path, id, problem
+            );
+            return None;
+        }
+        info!("Assign track {} to RFID {}.", track_path, id);
+        let learn_result = self.assign(id.clone(), playlist);
+        match learn_result {
+            Ok(()) => get_entry(&self.track_store, &id).cloned(),
+            Err(error) => {
+                error!(
+                    "Assigning track to RFID {} resulted in error: {}",
+                    id, error
+                );
+                None
+            }
+        }
+    }
+
+    /// Checks that the `playlist` has tracks and all of them exist and can be decoded,
+    /// so that neither the tracks file is written nor the playback fails because of a mistyped path.
+    fn check_playlist(&self, playlist: &Playlist) -> Result<(), String> {
+        let base_directory = self
+            .tracks_file
+            .as_ref()
+            .map_or(Path::new(""), |tracks_file| tracks_file.base_directory());
+        let mut playlist = playlist.clone();
+        playlist
+            .resolve_tracks(base_directory)
+            .map_err(|error| error.to_string())?;
+        if playlist.tracks().is_empty() {
+            return Err("it contains no audio files".to_string());
+        }
+        for track_path in playlist.tracks() {
+            media_player::check_track(&Track::from(track_path.to_string()))
+                .map_err(|error| format!("{} cannot be played: {}", track_path, error))?;
+        }
+        Ok(())
+    }
+
+    fn assign(&mut self, id: track_store::Id, playlist: Playlist) -> Result<(), Error> {
+        self.track_store.insert(id, playlist)?;
+        if let Some(tracks_file) = &mut self.tracks_file {
+            if tracks_file.format() == Format::Sqlite {
+                // the database is written by the track store itself
+                tracks_file.mark_saved();
+            } else {
+                // the tracks file is written in the format it was loaded
+                let string = self.track_store.save_as(tracks_file.format())?;
+                tracks_file.save(&string)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, action: Action) -> Result<(), Error> {
+        let action_result = match action {
+            Action::Stop => return self.stop_playback(),
+            Action::Pause => self.media_player.pause(),
+            Action::Next => self.media_player.next(),
+            Action::Previous => self.media_player.previous(),
+            Action::VolumeUp => self.media_player.volume_up(),
+            Action::VolumeDown => self.media_player.volume_down(),
+            Action::Shuffle => self.media_player.shuffle(),
+            Action::SleepTimer { minutes } => {
+                self.sleep_deadline = Some(Instant::now() + Duration::from_secs(minutes * 60));
+                Ok(())
+            }
+        };
+        match action_result {
+            Ok(()) => {
+                info!("Executed action {}", action);
+                Ok(())
+            }
+            Err(error) => {
+                error!(
+                    "Action {} could not be executed, received error: {}",
+                    action, error
+                );
+                Err(Error::from(error))
+            }
+        }
+    }
+
+    fn stop_playback(&mut self) -> Result<(), Error> {
+        // keep the position, so that the playlist can be resumed
+        self.update_position();
+        self.current_playlist.clear();
+        self.current_entry = None;
+        self.sleep_deadline = None;
+        match self.media_player.stop() {
+            Ok(()) => {
+                info!("Stopped playing");
+                Ok(())
+            }
+            Err(error) => {
+                error!("Stopping media player resulted in error: {}", error);
+                Err(Error::from(error))
+            }
+        }
+    }
+
+    fn update_position(&mut self) {
+        if let (Some(position_store), Some(first_track)) =
+            (&mut self.position_store, self.current_playlist.first())
+        {
+            match (
+                self.media_player.playlist_index(),
+                self.media_player.position(),
+            ) {
+                (Some(index), Some(position)) => {
+                    position_store.update(first_track.as_ref(), index, position)
+                }
+                _ => position_store.remove(first_track.as_ref()),
+            }
+        }
+    }
+
+    fn save_positions(&mut self) -> Result<(), Error> {
+        self.last_save = Instant::now();
+        if let Some(position_store) = &mut self.position_store {
+            let save_result = position_store.save();
+            if let Err(error) = &save_result {
+                error!("Saving playback positions resulted in error: {}", error);
+            }
+            save_result
+        } else {
+            Ok(())
         }
     }
 }
 
-fn get_track<'a>(
-    track_store: &'a impl TrackStore,
-    rfid_value: String,
-) -> Option<&'a track_store::TrackPath> {
-    debug!("Get track for rfid {}", rfid_value);
-    let id = track_store::Id::from(rfid_value);
-    debug!("Converted to id {}", id);
-    let option = track_store.get_path(&id);
-    debug!("Optional path to id {:?}", option);
+fn get_action(track_store: &impl TrackStore, id: &track_store::Id) -> Option<Action> {
+    let option = track_store.get_action(id).copied();
+    if let Some(action) = option {
+        info!("Found action {} for RFID {}.", action, id);
+    }
+    option
+}
+
+fn get_entry<'a>(track_store: &'a impl TrackStore, id: &track_store::Id) -> Option<&'a TrackEntry> {
+    debug!("Get playlist for id {}", id);
+    let option = track_store.get_entry(id);
+    debug!("Optional playlist to id {:?}", option);
     match option {
-        Some(track_path) => {
-            info!("Found track {} for RFID {}.", track_path, id);
-            Some(track_path)
+        Some(entry) if !entry.playlist().tracks().is_empty() => {
+            info!(
+                "Found playlist {} with {} track(s) for RFID {}.",
+                entry,
+                entry.playlist().tracks().len(),
+                id
+            );
+            Some(entry)
+        }
+        Some(_) => {
+            warn!("found empty playlist for RFID {}.", id);
+            None
         }
         None => {
             warn!("found no track for RFID {}.", id);
@@ -129,21 +495,71 @@ fn get_track<'a>(
     }
 }
 
-fn play_track(
+/// Returns the tracks of the playlist of the `entry` with its offsets and volume.
+fn to_tracks(entry: &TrackEntry) -> Vec<Track> {
+    entry
+        .playlist()
+        .tracks()
+        .iter()
+        .map(|track_path| {
+            let mut track = Track::from(track_path.to_string());
+            if let Some(start) = entry.start() {
+                track = track.with_start(start);
+            }
+            if let Some(end) = entry.end() {
+                track = track.with_end(end);
+            }
+            if let Some(volume) = entry.volume() {
+                track = track.with_volume(volume);
+            }
+            track
+        })
+        .collect()
+}
+
+fn play_playlist(
+    media_player: &mut impl MediaPlayer,
+    playlist: &[Track],
+    same_card_policy: SameCardPolicy,
+) -> Result<(), Error> {
+    let play_result = match same_card_policy {
+        SameCardPolicy::Ignore => media_player.play_playlist(playlist),
+        SameCardPolicy::Toggle => media_player.toggle_playlist(playlist),
+    };
+    match play_result {
+        Ok(()) => {
+            info!("Start playing playlist {:?}", playlist);
+            Ok(())
+        }
+        Err(error) => {
+            error!(
+                "Playlist {:?} could not be played, received error: {}",
+                playlist, error
+            );
+            Err(Error::from(error))
+        }
+    }
+}
+
+fn play_playlist_from(
     media_player: &mut impl MediaPlayer,
-    track_path: &track_store::TrackPath,
+    playlist: &[Track],
+    index: usize,
+    start: Duration,
 ) -> Result<(), Error> {
-    let track: media_player::Track = media_player::Track::from(track_path.as_ref());
-    let play_result = media_player.play(&track);
+    let play_result = media_player.play_playlist_from(playlist, index, start);
     match play_result {
         Ok(()) => {
-            info!("Start playing track {}", track);
+            info!(
+                "Start playing playlist {:?} with track {} at {:?}",
+                playlist, index, start
+            );
             Ok(())
         }
         Err(error) => {
             error!(
-                "Track {} could not be played, received error: {}",
-                track, error
+                "Playlist {:?} could not be played, received error: {}",
+                playlist, error
             );
             Err(Error::from(error))
         }
@@ -154,46 +570,276 @@ fn play_track(
 #[cfg(not(tarpaulin_include))]
 mod tests {
     use super::*;
+    use rfid_reader::{RfidReader, RfidValue};
 
     struct OkMediaPlayer;
     impl MediaPlayer for OkMediaPlayer {
-        fn play(&mut self, _track: &media_player::Track) -> Result<(), media_player::Error> {
+        fn play_playlist_from(
+            &mut self,
+            _playlist: &[Track],
+            _index: usize,
+            _position: Duration,
+        ) -> Result<(), media_player::Error> {
             Ok(())
         }
         fn stop(&mut self) -> Result<(), media_player::Error> {
             Ok(())
         }
+        fn pause(&mut self) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn resume(&mut self) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn toggle_playlist(&mut self, _playlist: &[Track]) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn next(&mut self) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn previous(&mut self) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn shuffle(&mut self) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn set_volume(&mut self, _volume: f32) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn volume(&self) -> f32 {
+            1.0
+        }
+        fn set_max_volume(&mut self, _max_volume: f32) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn max_volume(&self) -> f32 {
+            1.0
+        }
+        fn position(&self) -> Option<Duration> {
+            None
+        }
+        fn playlist_index(&self) -> Option<usize> {
+            None
+        }
     }
 
     struct ErrMediaPlayer;
     impl MediaPlayer for ErrMediaPlayer {
-        fn play(&mut self, _track: &media_player::Track) -> Result<(), media_player::Error> {
-            Err(media_player::Error::AudioLibError("play".to_string()))
+        fn play_playlist_from(
+            &mut self,
+            _playlist: &[Track],
+            _index: usize,
+            _position: Duration,
+        ) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError(
+                "play_playlist_from".to_string(),
+            ))
         }
         fn stop(&mut self) -> Result<(), media_player::Error> {
             Err(media_player::Error::AudioLibError("stop".to_string()))
         }
+        fn pause(&mut self) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError("pause".to_string()))
+        }
+        fn resume(&mut self) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError("resume".to_string()))
+        }
+        fn toggle_playlist(&mut self, _playlist: &[Track]) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError(
+                "toggle_playlist".to_string(),
+            ))
+        }
+        fn next(&mut self) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError("next".to_string()))
+        }
+        fn previous(&mut self) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError("previous".to_string()))
+        }
+        fn shuffle(&mut self) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError("shuffle".to_string()))
+        }
+        fn set_volume(&mut self, _volume: f32) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError("set_volume".to_string()))
+        }
+        fn volume(&self) -> f32 {
+            1.0
+        }
+        fn set_max_volume(&mut self, _max_volume: f32) -> Result<(), media_player::Error> {
+            Err(media_player::Error::AudioLibError(
+                "set_max_volume".to_string(),
+            ))
+        }
+        fn max_volume(&self) -> f32 {
+            1.0
+        }
+        fn position(&self) -> Option<Duration> {
+            None
+        }
+        fn playlist_index(&self) -> Option<usize> {
+            None
+        }
     }
 
-    struct SomeTrackStore(track_store::TrackPath);
+    #[derive(Default)]
+    struct PositionMediaPlayer {
+        index: Option<usize>,
+        position: Option<Duration>,
+        paused: bool,
+    }
+    impl MediaPlayer for PositionMediaPlayer {
+        fn play_playlist_from(
+            &mut self,
+            _playlist: &[Track],
+            index: usize,
+            position: Duration,
+        ) -> Result<(), media_player::Error> {
+            self.index = Some(index);
+            self.position = Some(position);
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<(), media_player::Error> {
+            self.index = None;
+            self.position = None;
+            Ok(())
+        }
+        fn pause(&mut self) -> Result<(), media_player::Error> {
+            self.paused = true;
+            Ok(())
+        }
+        fn resume(&mut self) -> Result<(), media_player::Error> {
+            self.paused = false;
+            Ok(())
+        }
+        fn toggle_playlist(&mut self, playlist: &[Track]) -> Result<(), media_player::Error> {
+            self.play_playlist(playlist)
+        }
+        fn next(&mut self) -> Result<(), media_player::Error> {
+            self.index = self.index.map(|index| index + 1);
+            Ok(())
+        }
+        fn previous(&mut self) -> Result<(), media_player::Error> {
+            self.index = self.index.map(|index| index.saturating_sub(1));
+            Ok(())
+        }
+        fn shuffle(&mut self) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn set_volume(&mut self, _volume: f32) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn volume(&self) -> f32 {
+            1.0
+        }
+        fn set_max_volume(&mut self, _max_volume: f32) -> Result<(), media_player::Error> {
+            Ok(())
+        }
+        fn max_volume(&self) -> f32 {
+            1.0
+        }
+        fn position(&self) -> Option<Duration> {
+            self.position
+        }
+        fn playlist_index(&self) -> Option<usize> {
+            self.index
+        }
+    }
+
+    struct SomeTrackStore(TrackEntry);
     impl TrackStore for SomeTrackStore {
-        fn get_path(&self, _id: &track_store::Id) -> Option<&track_store::TrackPath> {
+        fn get_entry(&self, _id: &track_store::Id) -> Option<&TrackEntry> {
             Some(&self.0)
         }
+        fn get_action(&self, _id: &track_store::Id) -> Option<&Action> {
+            None
+        }
+        fn contains_track(&self, _track_path: &track_store::TrackPath) -> bool {
+            false
+        }
+    }
+
+    impl MutableTrackStore for SomeTrackStore {
+        fn insert(
+            &mut self,
+            _id: track_store::Id,
+            _playlist: Playlist,
+        ) -> Result<(), track_store::Error> {
+            Ok(())
+        }
+        fn remove(&mut self, _id: &track_store::Id) -> Result<bool, track_store::Error> {
+            Ok(false)
+        }
+        fn save_as(&self, _format: track_store::Format) -> Result<String, track_store::Error> {
+            Ok(String::new())
+        }
     }
 
     struct NoneTrackStore;
     impl TrackStore for NoneTrackStore {
-        fn get_path(&self, _id: &track_store::Id) -> Option<&track_store::TrackPath> {
+        fn get_entry(&self, _id: &track_store::Id) -> Option<&TrackEntry> {
             None
         }
+        fn get_action(&self, _id: &track_store::Id) -> Option<&Action> {
+            None
+        }
+        fn contains_track(&self, _track_path: &track_store::TrackPath) -> bool {
+            false
+        }
+    }
+
+    impl MutableTrackStore for NoneTrackStore {
+        fn insert(
+            &mut self,
+            _id: track_store::Id,
+            _playlist: Playlist,
+        ) -> Result<(), track_store::Error> {
+            Ok(())
+        }
+        fn remove(&mut self, _id: &track_store::Id) -> Result<bool, track_store::Error> {
+            Ok(false)
+        }
+        fn save_as(&self, _format: track_store::Format) -> Result<String, track_store::Error> {
+            Ok(String::new())
+        }
+    }
+
+    struct ActionTrackStore(Action);
+    impl TrackStore for ActionTrackStore {
+        fn get_entry(&self, _id: &track_store::Id) -> Option<&TrackEntry> {
+            None
+        }
+        fn get_action(&self, _id: &track_store::Id) -> Option<&Action> {
+            Some(&self.0)
+        }
+        fn contains_track(&self, _track_path: &track_store::TrackPath) -> bool {
+            false
+        }
+    }
+
+    impl MutableTrackStore for ActionTrackStore {
+        fn insert(
+            &mut self,
+            _id: track_store::Id,
+            _playlist: Playlist,
+        ) -> Result<(), track_store::Error> {
+            Ok(())
+        }
+        fn remove(&mut self, _id: &track_store::Id) -> Result<bool, track_store::Error> {
+            Ok(false)
+        }
+        fn save_as(&self, _format: track_store::Format) -> Result<String, track_store::Error> {
+            Ok(String::new())
+        }
+    }
+
+    fn spawn<R: RfidReader + Send + 'static>(rfid_reader: R) -> RfidReaderHandle {
+        rfid_reader::spawn(move || Ok(rfid_reader)).unwrap()
     }
 
     #[derive(Debug)]
     struct OkRfidReader;
     impl RfidReader for OkRfidReader {
-        fn read(&self) -> Result<String, rfid_reader::Error> {
-            Ok("1234".to_string())
+        fn read(&self) -> Result<RfidValue, rfid_reader::Error> {
+            Ok(RfidValue::from(1234))
         }
         fn deinitialize(&mut self) -> Result<(), rfid_reader::Error> {
             Ok(())
@@ -203,7 +849,7 @@ mod tests {
     #[derive(Debug)]
     struct ErrRfidReader;
     impl RfidReader for ErrRfidReader {
-        fn read(&self) -> Result<String, rfid_reader::Error> {
+        fn read(&self) -> Result<RfidValue, rfid_reader::Error> {
             Err(rfid_reader::Error::OtherUsbError("read".to_string()))
         }
         fn deinitialize(&mut self) -> Result<(), rfid_reader::Error> {
@@ -216,7 +862,9 @@ mod tests {
     #[derive(Debug)]
     struct TimeoutRfidReader;
     impl RfidReader for TimeoutRfidReader {
-        fn read(&self) -> Result<String, rfid_reader::Error> {
+        fn read(&self) -> Result<RfidValue, rfid_reader::Error> {
+            // like a device, which waits for a card
+            std::thread::sleep(Duration::from_millis(10));
             Err(rfid_reader::Error::Timeout)
         }
         fn deinitialize(&mut self) -> Result<(), rfid_reader::Error> {
@@ -225,28 +873,55 @@ mod tests {
     }
 
     #[test]
-    fn test_play_track() {
+    fn test_play_playlist() {
         let mut ok = OkMediaPlayer;
-        let result = play_track(&mut ok, &track_store::TrackPath::from(""));
+        let result = play_playlist(&mut ok, &[Track::from("")], SameCardPolicy::Ignore);
         assert_eq!(Ok(()), result);
 
         let mut err = ErrMediaPlayer;
-        let result = play_track(&mut err, &track_store::TrackPath::from(""));
+        let result = play_playlist(&mut err, &[Track::from("")], SameCardPolicy::Ignore);
         assert_eq!(
             Err(Error::MediaPlayerError(
-                "AudioLibError(\"play\")".to_string()
+                "AudioLibError(\"play_playlist_from\")".to_string()
             )),
             result
         );
     }
+
     #[test]
-    fn test_get_track() {
-        let some = SomeTrackStore(track_store::TrackPath::from("path"));
-        let option = get_track(&some, "".to_string());
-        assert_eq!(Some(&track_store::TrackPath::from("path")), option);
+    fn test_toggle_playlist() {
+        let mut ok = OkMediaPlayer;
+        let result = play_playlist(&mut ok, &[Track::from("")], SameCardPolicy::Toggle);
+        assert_eq!(Ok(()), result);
+
+        let mut err = ErrMediaPlayer;
+        let result = play_playlist(&mut err, &[Track::from("")], SameCardPolicy::Toggle);
+        assert_eq!(
+            Err(Error::MediaPlayerError(
+                "AudioLibError(\"toggle_playlist\")".to_string()
+            )),
+            result
+        );
+    }
+    #[test]
+    fn test_get_entry() {
+        let some = SomeTrackStore(TrackEntry::from(Playlist::from(
+            track_store::TrackPath::from("path"),
+        )));
+        let option = get_entry(&some, &"".into());
+        assert_eq!(
+            Some(&TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path")
+            ))),
+            option
+        );
+
+        let empty = SomeTrackStore(TrackEntry::from(Playlist::from(Vec::new())));
+        let option = get_entry(&empty, &"".into());
+        assert_eq!(None, option);
 
         let none = NoneTrackStore;
-        let option = get_track(&none, "".to_string());
+        let option = get_entry(&none, &"".into());
         assert_eq!(None, option);
     }
 
@@ -254,8 +929,14 @@ mod tests {
     fn test_ok_run_and_shutdown() {
         let mut rfid_media_player = open(
             OkMediaPlayer,
-            OkRfidReader,
-            SomeTrackStore(track_store::TrackPath::from("path")),
+            spawn(OkRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
         );
 
         let result = rfid_media_player.run();
@@ -271,8 +952,14 @@ mod tests {
     fn test_err_run_and_shutdown() {
         let mut rfid_media_player = open(
             OkMediaPlayer,
-            ErrRfidReader,
-            SomeTrackStore(track_store::TrackPath::from("path")),
+            spawn(ErrRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
         );
 
         let result = rfid_media_player.run();
@@ -298,8 +985,14 @@ mod tests {
     fn test_err_media_player_shutdown() {
         let mut rfid_media_player = open(
             ErrMediaPlayer,
-            OkRfidReader,
-            SomeTrackStore(track_store::TrackPath::from("path")),
+            spawn(OkRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
         );
 
         let result = rfid_media_player.shutdown();
@@ -316,12 +1009,488 @@ mod tests {
     fn test_timeout_run() {
         let mut rfid_media_player = open(
             OkMediaPlayer,
-            TimeoutRfidReader,
-            SomeTrackStore(track_store::TrackPath::from("path")),
+            spawn(TimeoutRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
         );
 
         let result = rfid_media_player.run();
 
         assert_eq!(Ok(()), result);
     }
+
+    #[derive(Debug)]
+    struct DisconnectedRfidReader;
+    impl RfidReader for DisconnectedRfidReader {
+        fn read(&self) -> Result<RfidValue, rfid_reader::Error> {
+            std::thread::sleep(Duration::from_millis(10));
+            Err(rfid_reader::Error::Disconnected)
+        }
+        fn deinitialize(&mut self) -> Result<(), rfid_reader::Error> {
+            Ok(())
+        }
+    }
+
+    /// Reads the card while it lies on the reader, it is removed after some reads and put back later on.
+    #[derive(Debug, Default)]
+    struct PresenceRfidReader {
+        reads: std::cell::Cell<usize>,
+    }
+    impl RfidReader for PresenceRfidReader {
+        fn read(&self) -> Result<RfidValue, rfid_reader::Error> {
+            let reads = self.reads.get();
+            self.reads.set(reads + 1);
+            std::thread::sleep(Duration::from_millis(10));
+            match reads {
+                0..=2 | 10..=12 => Ok(RfidValue::from(1234)),
+                _ => Err(rfid_reader::Error::Timeout),
+            }
+        }
+        fn deinitialize(&mut self) -> Result<(), rfid_reader::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_play_while_present() {
+        let rfid_reader = rfid_reader::spawn_with_presence(
+            || Ok(PresenceRfidReader::default()),
+            Duration::from_millis(50),
+        )
+        .unwrap();
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            PositionMediaPlayer::default(),
+            rfid_reader,
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+        rfid_media_player.run().unwrap();
+        assert_eq!(Some(0), rfid_media_player.media_player.playlist_index());
+
+        for _ in 0..10 {
+            if rfid_media_player.media_player.paused {
+                break;
+            }
+            rfid_media_player.run().unwrap();
+        }
+        assert!(rfid_media_player.media_player.paused);
+        assert!(rfid_media_player.paused_by_removal);
+
+        // putting the card back resumes the track
+        for _ in 0..10 {
+            if !rfid_media_player.media_player.paused {
+                break;
+            }
+            rfid_media_player.run().unwrap();
+        }
+        assert!(!rfid_media_player.media_player.paused);
+        assert!(!rfid_media_player.paused_by_removal);
+        assert_eq!(Some(0), rfid_media_player.media_player.playlist_index());
+    }
+
+    #[test]
+    fn test_disconnected_run() {
+        let mut rfid_media_player = open(
+            OkMediaPlayer,
+            spawn(DisconnectedRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+
+        // a disconnected reader does not stop the application
+        for _ in 0..3 {
+            assert_eq!(Ok(()), rfid_media_player.run());
+        }
+        assert_eq!(Ok(()), rfid_media_player.shutdown());
+    }
+
+    #[test]
+    fn test_play_playlist_from() {
+        let mut ok = OkMediaPlayer;
+        let result = play_playlist_from(&mut ok, &[Track::from("")], 0, Duration::from_secs(1));
+        assert_eq!(Ok(()), result);
+
+        let mut err = ErrMediaPlayer;
+        let result = play_playlist_from(&mut err, &[Track::from("")], 0, Duration::from_secs(1));
+        assert_eq!(
+            Err(Error::MediaPlayerError(
+                "AudioLibError(\"play_playlist_from\")".to_string()
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn test_resume_position() {
+        let path = std::env::temp_dir().join(format!(
+            "rfid_media_player_resume_position_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "path:\n  track: 2\n  position: 65000\n  updated: 0\n",
+        )
+        .unwrap();
+        let position_store = PositionStore::load(&path, Duration::from_secs(5), None);
+
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            PositionMediaPlayer::default(),
+            spawn(OkRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            Some(position_store),
+            None,
+        );
+
+        let result = rfid_media_player.run();
+        assert_eq!(Ok(()), result);
+        assert_eq!(Some(2), rfid_media_player.media_player.playlist_index());
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            rfid_media_player.media_player.position()
+        );
+        assert_eq!(
+            vec![Track::from("path")],
+            rfid_media_player.current_playlist
+        );
+
+        // the played track is finished, therefore its position is forgotten
+        rfid_media_player.media_player.stop().unwrap();
+        let result = rfid_media_player.shutdown();
+        assert_eq!(Ok(()), result);
+        let position_store = PositionStore::load(&path, Duration::from_secs(5), None);
+        assert_eq!((0, Duration::from_secs(0)), position_store.position("path"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_get_action() {
+        let action = ActionTrackStore(Action::Next);
+        assert_eq!(Some(Action::Next), get_action(&action, &"".into()));
+
+        let none = NoneTrackStore;
+        assert_eq!(None, get_action(&none, &"".into()));
+    }
+
+    #[test]
+    fn test_execute_action() {
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            ErrMediaPlayer,
+            spawn(OkRfidReader),
+            ActionTrackStore(Action::VolumeUp),
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+        // the volume is increased by setting it
+        let result = rfid_media_player.run();
+        assert_eq!(
+            Err(Error::MediaPlayerError(
+                "AudioLibError(\"set_volume\")".to_string()
+            )),
+            result
+        );
+
+        let result = rfid_media_player.execute(Action::Stop);
+        assert_eq!(
+            Err(Error::MediaPlayerError(
+                "AudioLibError(\"stop\")".to_string()
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn test_stop_keeps_position() {
+        let path = std::env::temp_dir().join(format!(
+            "rfid_media_player_stop_keeps_position_{}.yaml",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let position_store = PositionStore::load(&path, Duration::from_secs(0), None);
+
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            PositionMediaPlayer::default(),
+            spawn(OkRfidReader),
+            SomeTrackStore(TrackEntry::from(Playlist::from(
+                track_store::TrackPath::from("path"),
+            ))),
+            None,
+            SameCardPolicy::Ignore,
+            Some(position_store),
+            None,
+        );
+        rfid_media_player.run().unwrap();
+        rfid_media_player.execute(Action::Next).unwrap();
+        assert_eq!(Some(1), rfid_media_player.media_player.playlist_index());
+
+        rfid_media_player.execute(Action::Stop).unwrap();
+        assert!(rfid_media_player.current_playlist.is_empty());
+        assert_eq!(None, rfid_media_player.media_player.position());
+        assert_eq!(
+            (1, Duration::from_secs(0)),
+            rfid_media_player
+                .position_store
+                .as_ref()
+                .unwrap()
+                .position("path")
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_loop() {
+        let track_store =
+            track_store::load("1234:\n  path: path\n  title: Title\n  start: 5\n  loop: true")
+                .unwrap();
+        let entry = track_store.get_entry(&"1234".into()).unwrap().clone();
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            PositionMediaPlayer::default(),
+            spawn(TimeoutRfidReader),
+            NoneTrackStore,
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+        rfid_media_player.play(entry).unwrap();
+        assert_eq!(
+            vec![Track::from("path").with_start(Duration::from_secs(5))],
+            rfid_media_player.current_playlist
+        );
+
+        // the finished playlist is played again from its beginning
+        rfid_media_player.media_player.next().unwrap();
+        rfid_media_player.media_player.stop().unwrap();
+        rfid_media_player.run().unwrap();
+        assert_eq!(Some(0), rfid_media_player.media_player.playlist_index());
+
+        // a stopped playlist is not played again
+        rfid_media_player.stop_playback().unwrap();
+        rfid_media_player.run().unwrap();
+        assert_eq!(None, rfid_media_player.media_player.playlist_index());
+    }
+
+    #[test]
+    fn test_sleep_timer() {
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            PositionMediaPlayer::default(),
+            spawn(TimeoutRfidReader),
+            NoneTrackStore,
+            None,
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+        rfid_media_player
+            .media_player
+            .play_playlist(&[Track::from("path")])
+            .unwrap();
+
+        rfid_media_player
+            .execute(Action::SleepTimer { minutes: 1 })
+            .unwrap();
+        rfid_media_player.run().unwrap();
+        assert!(rfid_media_player.media_player.position().is_some());
+
+        rfid_media_player.sleep_deadline = Some(Instant::now());
+        rfid_media_player.run().unwrap();
+        assert_eq!(None, rfid_media_player.media_player.position());
+        assert_eq!(None, rfid_media_player.sleep_deadline);
+    }
+
+    #[test]
+    fn test_reload() {
+        let path = std::env::temp_dir().join(format!(
+            "rfid_media_player_reload_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "1234: path").unwrap();
+        let tracks_file = TracksFile::new(&path, track_store::load_file);
+        let track_store = tracks_file.load().unwrap();
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            OkMediaPlayer,
+            spawn(OkRfidReader),
+            track_store,
+            Some(tracks_file),
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+        let id = track_store::Id::from("1234");
+
+        // relative paths are resolved against the directory of the tracks file
+        std::fs::write(&path, "1234: other/path").unwrap();
+        assert_eq!(Ok(()), rfid_media_player.reload());
+        let other_path = track_store::TrackPath::from(std::env::temp_dir().join("other/path"));
+        assert_eq!(
+            Some(&other_path),
+            rfid_media_player.track_store.get_path(&id)
+        );
+
+        // the previous tracks are kept if the tracks file is invalid
+        std::fs::write(&path, "invalid").unwrap();
+        assert!(matches!(
+            rfid_media_player.reload(),
+            Err(Error::TrackStoreError(_))
+        ));
+        assert_eq!(
+            Some(&other_path),
+            rfid_media_player.track_store.get_path(&id)
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Copies a decodable track to `track` after the card was read, like dropping it into the drop directory.
+    fn drop_after_read(track: &std::path::Path) -> std::thread::JoinHandle<()> {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("../media_player/tests/rand1.wav");
+        let track = track.to_path_buf();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            std::fs::copy(source, track).unwrap();
+        })
+    }
+
+    #[test]
+    fn test_learn() {
+        let directory =
+            std::env::temp_dir().join(format!("rfid_media_player_learn_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("tracks.yaml");
+        std::fs::write(&path, "0000: path").unwrap();
+        let drop_directory = directory.join("drop");
+        std::fs::create_dir_all(&drop_directory).unwrap();
+        let track = drop_directory.join("track.wav");
+
+        let tracks_file = TracksFile::new(&path, track_store::load_file);
+        let track_store = tracks_file.load().unwrap();
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            OkMediaPlayer,
+            spawn(OkRfidReader),
+            track_store,
+            Some(tracks_file),
+            SameCardPolicy::Ignore,
+            None,
+            Some(LearnMode::DropDirectory(drop_directory)),
+        );
+
+        // the unknown card is assigned, saved and played
+        let drop = drop_after_read(&track);
+        assert_eq!(Ok(()), rfid_media_player.run());
+        drop.join().unwrap();
+        let track = Track::from(track.to_string_lossy().as_ref());
+        assert_eq!(vec![track.clone()], rfid_media_player.current_playlist);
+        let track_store = track_store::load(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            Some(&track_store::TrackPath::from(track.as_ref())),
+            track_store.get_path(&"0000001234".into())
+        );
+        assert!(track_store.get_path(&"0000".into()).is_some());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn test_learn_sqlite() {
+        let directory = std::env::temp_dir().join(format!(
+            "rfid_media_player_learn_sqlite_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("tracks.sqlite");
+        track_store::migrate_to_sqlite("0000: path", Format::Yaml, &path).unwrap();
+        let drop_directory = directory.join("drop");
+        std::fs::create_dir_all(&drop_directory).unwrap();
+        let track = drop_directory.join("track.wav");
+
+        let tracks_file = TracksFile::new(&path, track_store::load_file);
+        let track_store = tracks_file.load().unwrap();
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            OkMediaPlayer,
+            spawn(OkRfidReader),
+            track_store,
+            Some(tracks_file),
+            SameCardPolicy::Ignore,
+            None,
+            Some(LearnMode::DropDirectory(drop_directory)),
+        );
+
+        // the assigned card is written to the database by the track store
+        let drop = drop_after_read(&track);
+        assert_eq!(Ok(()), rfid_media_player.run());
+        drop.join().unwrap();
+        let track_store = track_store::load_file(&path, Format::Sqlite, &directory).unwrap();
+        assert_eq!(
+            Some(&track_store::TrackPath::from(
+                track.to_string_lossy().as_ref()
+            )),
+            track_store.get_path(&"0000001234".into())
+        );
+        assert!(track_store.get_path(&"0000".into()).is_some());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_learn_non_existing_track() {
+        let path = std::env::temp_dir().join(format!(
+            "rfid_media_player_learn_non_existing_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "0000: path").unwrap();
+        let tracks_file = TracksFile::new(&path, track_store::load_file);
+        let track_store = tracks_file.load().unwrap();
+        let mut rfid_media_player = RfidMediaPlayerImplementation::open(
+            ErrMediaPlayer,
+            spawn(OkRfidReader),
+            track_store,
+            Some(tracks_file),
+            SameCardPolicy::Ignore,
+            None,
+            None,
+        );
+        let (sender, lines) = std::sync::mpsc::channel();
+        rfid_media_player.learner = Some(Learner::with(
+            LearnMode::Stdin,
+            Some(lines),
+            Duration::from_secs(5),
+        ));
+
+        // the mistyped track is neither saved nor played, which would fail
+        let answer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            sender.send("/non/existing/track.mp3".to_string()).unwrap();
+        });
+        assert_eq!(Ok(()), rfid_media_player.run());
+        answer.join().unwrap();
+        assert!(rfid_media_player.current_playlist.is_empty());
+        assert!(rfid_media_player
+            .track_store
+            .get_entry(&"0000001234".into())
+            .is_none());
+        assert_eq!("0000: path", std::fs::read_to_string(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
 }
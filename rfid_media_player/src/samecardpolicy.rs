@@ -0,0 +1,59 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use std::str::FromStr;
+
+/// The policy applied when the card of the current track is read again.
+///
+/// Can be parsed from the strings `"ignore"` and `"toggle"`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SameCardPolicy {
+    /// The card is ignored, the playback of the current track continues.
+    Ignore,
+    /// The playback of the current track is paused or, if already paused, resumed.
+    Toggle,
+}
+
+impl FromStr for SameCardPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(SameCardPolicy::Ignore),
+            "toggle" => Ok(SameCardPolicy::Toggle),
+            _ => Err(format!("Unknown same card policy: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SameCardPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SameCardPolicy::Ignore => write!(f, "ignore"),
+            SameCardPolicy::Toggle => write!(f, "toggle"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(SameCardPolicy::Ignore), "ignore".parse());
+        assert_eq!(Ok(SameCardPolicy::Toggle), "toggle".parse());
+        assert_eq!(
+            Err("Unknown same card policy: pause".to_string()),
+            "pause".parse::<SameCardPolicy>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("ignore", format!("{}", SameCardPolicy::Ignore));
+        assert_eq!("toggle", format!("{}", SameCardPolicy::Toggle));
+    }
+}
@@ -0,0 +1,214 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+
+use log::debug;
+use track_store::Format;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Interval in which the modification time of the tracks file is polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The tracks file from which the track store is loaded.
+///
+/// Detects changes of the file by polling its modification time, so that the track store can be reloaded.
+pub(crate) struct TracksFile<T> {
+    path: PathBuf,
+    format: Format,
+    load_track_store: fn(&Path, Format, &Path) -> Result<T, track_store::Error>,
+    modified: Option<SystemTime>,
+    last_poll: Instant,
+}
+
+impl<T> TracksFile<T> {
+    /// Creates the tracks file at `path` which is loaded by `load_track_store`, e.g. [`load_file`](track_store::load_file).
+    ///
+    /// The [`Format`](track_store::Format) is selected by the extension of the file, YAML if it is unknown.
+    /// The directory of the file is passed to `load_track_store` as base directory of the relative paths.
+    pub(crate) fn new(
+        path: impl Into<PathBuf>,
+        load_track_store: fn(&Path, Format, &Path) -> Result<T, track_store::Error>,
+    ) -> Self {
+        let path = path.into();
+        let modified = modified(&path);
+        TracksFile {
+            format: Format::from_path(&path).unwrap_or_default(),
+            path,
+            load_track_store,
+            modified,
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// Loads the track store from the file.
+    ///
+    /// Returns a [`TrackStoreError`](crate::Error::TrackStoreError) if the file cannot be read or is invalid.
+    pub(crate) fn load(&self) -> Result<T, Error> {
+        let track_store = (self.load_track_store)(&self.path, self.format, self.base_directory())?;
+        Ok(track_store)
+    }
+
+    /// Writes the `string` of the changed track store in the [`format`](TracksFile::format) of the file to the file.
+    ///
+    /// Writing the file is not detected as a change.
+    /// Returns a [`TrackStoreError`](crate::Error::TrackStoreError) if the file cannot be written.
+    pub(crate) fn save(&mut self, string: &str) -> Result<(), Error> {
+        // write to a temporary file first, so that the tracks file is never left half written
+        let temporary_path = self.path.with_extension("tmp");
+        std::fs::write(&temporary_path, string)
+            .and_then(|_| std::fs::rename(&temporary_path, &self.path))
+            .map_err(|error| {
+                Error::TrackStoreError(format!("{}: {}", self.path.display(), error))
+            })?;
+        self.mark_saved();
+        Ok(())
+    }
+
+    /// Marks the file as saved after the track store wrote it itself, e.g. an [`Sqlite`](track_store::Format::Sqlite)
+    /// database, so that writing the file is not detected as a change.
+    pub(crate) fn mark_saved(&mut self) {
+        self.modified = modified(&self.path);
+    }
+
+    /// Returns `true` if the file was modified since the last change was detected.
+    ///
+    /// The modification time is polled at most every [`POLL_INTERVAL`].
+    pub(crate) fn has_changed(&mut self) -> bool {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return false;
+        }
+        self.last_poll = Instant::now();
+        let modified = modified(&self.path);
+        if modified != self.modified {
+            debug!("Tracks file {} has changed", self.path.display());
+            self.modified = modified;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the path of the file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the format of the file.
+    pub(crate) fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the directory of the file, against which relative paths of the tracks are resolved.
+    pub(crate) fn base_directory(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new(""))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn temporary_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "rfid_media_player_tracks_file_{}_{}.yaml",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn load_string(path: &Path, _: Format, _: &Path) -> Result<String, track_store::Error> {
+        let yaml_string = std::fs::read_to_string(path)
+            .map_err(|error| track_store::Error::LoadError(error.to_string()))?;
+        if yaml_string.is_empty() {
+            Err(track_store::Error::LoadError("empty".to_string()))
+        } else {
+            Ok(yaml_string.to_string())
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let path = temporary_file("load");
+        std::fs::write(&path, "1234: path").unwrap();
+        let tracks_file = TracksFile::new(&path, load_string);
+        assert_eq!(Ok("1234: path".to_string()), tracks_file.load());
+
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(
+            Err(Error::TrackStoreError("LoadError(\"empty\")".to_string())),
+            tracks_file.load()
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(tracks_file.load(), Err(Error::TrackStoreError(_))));
+    }
+
+    #[test]
+    fn test_has_changed() {
+        let path = temporary_file("has_changed");
+        std::fs::write(&path, "1234: path").unwrap();
+        let mut tracks_file = TracksFile::new(&path, load_string);
+        assert!(!tracks_file.has_changed());
+
+        // pretend that the file was modified and the poll interval elapsed
+        tracks_file.modified = Some(SystemTime::UNIX_EPOCH);
+        assert!(!tracks_file.has_changed());
+        tracks_file.last_poll = Instant::now() - POLL_INTERVAL;
+        assert!(tracks_file.has_changed());
+
+        // the change is only reported once
+        tracks_file.last_poll = Instant::now() - POLL_INTERVAL;
+        assert!(!tracks_file.has_changed());
+
+        std::fs::remove_file(&path).unwrap();
+        tracks_file.last_poll = Instant::now() - POLL_INTERVAL;
+        assert!(tracks_file.has_changed());
+    }
+
+    #[test]
+    fn test_save() {
+        let path = temporary_file("save");
+        std::fs::write(&path, "1234: path").unwrap();
+        let mut tracks_file = TracksFile::new(&path, load_string);
+        tracks_file.modified = Some(SystemTime::UNIX_EPOCH);
+
+        assert_eq!(Ok(()), tracks_file.save("5678: path"));
+        assert_eq!(Ok("5678: path".to_string()), tracks_file.load());
+        tracks_file.last_poll = Instant::now() - POLL_INTERVAL;
+        assert!(!tracks_file.has_changed());
+        std::fs::remove_file(&path).unwrap();
+
+        let mut tracks_file = TracksFile::new("/non/existing/tracks.yaml", load_string);
+        assert!(matches!(
+            tracks_file.save("5678: path"),
+            Err(Error::TrackStoreError(_))
+        ));
+    }
+
+    #[test]
+    fn test_format() {
+        let tracks_file = TracksFile::new("/path/to/tracks.json", load_string);
+        assert_eq!(Format::Json, tracks_file.format());
+        let tracks_file = TracksFile::new("/path/to/tracks", load_string);
+        assert_eq!(Format::Yaml, tracks_file.format());
+    }
+
+    #[test]
+    fn test_base_directory() {
+        let tracks_file = TracksFile::new("/path/to/tracks.yaml", load_string);
+        assert_eq!(Path::new("/path/to"), tracks_file.base_directory());
+        let tracks_file = TracksFile::new("tracks.yaml", load_string);
+        assert_eq!(Path::new(""), tracks_file.base_directory());
+    }
+}
@@ -0,0 +1,58 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::rfidevent::RfidEvent;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::SyncSender;
+use std::sync::Arc;
+
+/// Cancels the reader thread of an [RfidReaderHandle](crate::RfidReaderHandle) from any thread, e.g. from a signal handler.
+#[derive(Debug, Clone)]
+pub struct Canceller {
+    running: Arc<AtomicBool>,
+    sender: SyncSender<RfidEvent>,
+}
+
+impl Canceller {
+    pub(crate) fn new(running: Arc<AtomicBool>, sender: SyncSender<RfidEvent>) -> Self {
+        Canceller { running, sender }
+    }
+
+    /// Cancels reading.
+    ///
+    /// A pending [recv_timeout](crate::RfidReaderHandle::recv_timeout) returns [RfidEvent::Cancelled](crate::RfidEvent::Cancelled)
+    /// immediately. The reader thread stops as soon as the current read returns, i.e. after at most the timeout of the reader.
+    pub fn cancel(&self) {
+        if self.running.swap(false, Ordering::SeqCst) {
+            // a full channel wakes up the receiver anyway
+            self.sender.try_send(RfidEvent::Cancelled).ok();
+        }
+    }
+
+    /// Returns `true` if reading was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        !self.running.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        let canceller = Canceller::new(Arc::new(AtomicBool::new(true)), sender);
+        assert!(!canceller.is_cancelled());
+
+        canceller.clone().cancel();
+        canceller.cancel();
+        assert!(canceller.is_cancelled());
+        // the event is sent only once
+        assert_eq!(Ok(RfidEvent::Cancelled), receiver.try_recv());
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,62 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::debouncedrfidreader;
+use crate::debouncestatistics::DebounceStatistics;
+use crate::rfidreader::RfidReader;
+
+use std::time::Duration;
+
+/// Debounces the reads of any [RfidReader](crate::RfidReader).
+///
+/// A card lying on the reader is read repeatedly. Reads of the same value within the `min_interval` after its last read
+/// are suppressed, also reads of a different value within the `cooldown` after the last passed read.
+/// A suppressed read returns [Error::Timeout](crate::Error::Timeout), like a read without card.
+/// A zero `min_interval` or `cooldown` disables the respective suppression.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use rfid_reader::{Debounce, ProductId, RfidReader, VendorId};
+///
+/// let debounce = Debounce::new(Duration::from_secs(2), Duration::from_millis(500));
+/// let rfid_reader = rfid_reader::open(VendorId::from(0x16c0), ProductId::from(0x27db), Duration::from_secs(1)).unwrap();
+/// let rfid_reader = debounce.wrap(rfid_reader);
+/// let data = rfid_reader.read();
+/// println!("Reads: {}", debounce.statistics());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Debounce {
+    min_interval: Duration,
+    cooldown: Duration,
+    statistics: DebounceStatistics,
+}
+
+impl Debounce {
+    /// Creates the debounce with the minimum interval between reads of the same value and the cooldown after a different value.
+    pub fn new(min_interval: Duration, cooldown: Duration) -> Self {
+        Debounce {
+            min_interval,
+            cooldown,
+            statistics: DebounceStatistics::default(),
+        }
+    }
+
+    /// Wraps the [RfidReader](crate::RfidReader), whose reads are debounced.
+    ///
+    /// All wrapped readers count their reads in the same [DebounceStatistics](crate::DebounceStatistics).
+    pub fn wrap<R: RfidReader>(&self, rfid_reader: R) -> impl RfidReader {
+        debouncedrfidreader::from(
+            rfid_reader,
+            self.min_interval,
+            self.cooldown,
+            self.statistics.clone(),
+        )
+    }
+
+    /// Returns the statistics of the debounced reads.
+    pub fn statistics(&self) -> DebounceStatistics {
+        self.statistics.clone()
+    }
+}
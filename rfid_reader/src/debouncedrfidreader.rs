@@ -0,0 +1,193 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::debouncestatistics::DebounceStatistics;
+use crate::error::Error;
+use crate::rfidreader::RfidReader;
+use rfid_value::RfidValue;
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Suppresses repeated reads of the same value and reads of a different value during the cooldown.
+struct DebouncedRfidReader<R: RfidReader> {
+    rfid_reader: R,
+    min_interval: Duration,
+    cooldown: Duration,
+    statistics: DebounceStatistics,
+    last_read: Cell<Option<(RfidValue, Instant)>>,
+    last_passed: Cell<Option<(RfidValue, Instant)>>,
+}
+
+impl<R: RfidReader> DebouncedRfidReader<R> {
+    /// Returns the `rfid_value` read at `now` if it is not suppressed.
+    fn filter(&self, rfid_value: RfidValue, now: Instant) -> Option<RfidValue> {
+        let last_read = self.last_read.replace(Some((rfid_value, now)));
+        if let Some((last_value, last_time)) = last_read {
+            // the interval starts again with every read, i.e. a card lying on the reader is suppressed
+            if last_value == rfid_value && now.duration_since(last_time) < self.min_interval {
+                self.statistics.count_repeated();
+                return None;
+            }
+        }
+        if let Some((passed_value, passed_time)) = self.last_passed.get() {
+            if passed_value != rfid_value && now.duration_since(passed_time) < self.cooldown {
+                self.statistics.count_in_cooldown();
+                return None;
+            }
+        }
+        self.last_passed.set(Some((rfid_value, now)));
+        self.statistics.count_passed();
+        Some(rfid_value)
+    }
+}
+
+impl<R: RfidReader> std::fmt::Debug for DebouncedRfidReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebouncedRfidReader")
+            .field("rfid_reader", &self.rfid_reader)
+            .field("min_interval", &self.min_interval)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl<R: RfidReader> RfidReader for DebouncedRfidReader<R> {
+    fn read(&self) -> Result<RfidValue, Error> {
+        let rfid_value = self.rfid_reader.read()?;
+        // a suppressed read is handled like a read without card
+        self.filter(rfid_value, Instant::now())
+            .ok_or(Error::Timeout)
+    }
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        self.rfid_reader.deinitialize()
+    }
+}
+
+pub(crate) fn from<R: RfidReader>(
+    rfid_reader: R,
+    min_interval: Duration,
+    cooldown: Duration,
+    statistics: DebounceStatistics,
+) -> impl RfidReader {
+    DebouncedRfidReader {
+        rfid_reader,
+        min_interval,
+        cooldown,
+        statistics,
+        last_read: Cell::new(None),
+        last_passed: Cell::new(None),
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockRfidReader;
+
+    impl RfidReader for MockRfidReader {
+        fn read(&self) -> Result<RfidValue, Error> {
+            Ok(RfidValue::from(1))
+        }
+        fn deinitialize(&mut self) -> Result<(), Error> {
+            Err(Error::Access)
+        }
+    }
+
+    fn debounced(min_interval: u64, cooldown: u64) -> DebouncedRfidReader<MockRfidReader> {
+        DebouncedRfidReader {
+            rfid_reader: MockRfidReader,
+            min_interval: Duration::from_millis(min_interval),
+            cooldown: Duration::from_millis(cooldown),
+            statistics: DebounceStatistics::default(),
+            last_read: Cell::new(None),
+            last_passed: Cell::new(None),
+        }
+    }
+
+    #[test]
+    fn test_repeated() {
+        let debounced_rfid_reader = debounced(500, 0);
+        let now = Instant::now();
+        let after = |millis| now + Duration::from_millis(millis);
+        assert_eq!(
+            Some(RfidValue::from(1)),
+            debounced_rfid_reader.filter(RfidValue::from(1), now)
+        );
+        assert_eq!(
+            None,
+            debounced_rfid_reader.filter(RfidValue::from(1), after(400))
+        );
+        // the interval started again with the suppressed read
+        assert_eq!(
+            None,
+            debounced_rfid_reader.filter(RfidValue::from(1), after(800))
+        );
+        assert_eq!(
+            Some(RfidValue::from(1)),
+            debounced_rfid_reader.filter(RfidValue::from(1), after(1300))
+        );
+        assert_eq!(
+            Some(RfidValue::from(2)),
+            debounced_rfid_reader.filter(RfidValue::from(2), after(1400))
+        );
+        assert_eq!(
+            Some(RfidValue::from(1)),
+            debounced_rfid_reader.filter(RfidValue::from(1), after(1500))
+        );
+        assert_eq!(4, debounced_rfid_reader.statistics.passed());
+        assert_eq!(2, debounced_rfid_reader.statistics.suppressed_repeated());
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let debounced_rfid_reader = debounced(0, 1000);
+        let now = Instant::now();
+        let after = |millis| now + Duration::from_millis(millis);
+        assert_eq!(
+            Some(RfidValue::from(1)),
+            debounced_rfid_reader.filter(RfidValue::from(1), now)
+        );
+        assert_eq!(
+            None,
+            debounced_rfid_reader.filter(RfidValue::from(2), after(500))
+        );
+        // the same card is not in cooldown
+        assert_eq!(
+            Some(RfidValue::from(1)),
+            debounced_rfid_reader.filter(RfidValue::from(1), after(600))
+        );
+        assert_eq!(
+            Some(RfidValue::from(2)),
+            debounced_rfid_reader.filter(RfidValue::from(2), after(1600))
+        );
+        assert_eq!(1, debounced_rfid_reader.statistics.suppressed_in_cooldown());
+    }
+
+    #[test]
+    fn test_read() {
+        let statistics = DebounceStatistics::default();
+        let mut debounced_rfid_reader = from(
+            MockRfidReader,
+            Duration::from_secs(60),
+            Duration::from_secs(0),
+            statistics.clone(),
+        );
+        assert_eq!(Ok(RfidValue::from(1)), debounced_rfid_reader.read());
+        assert_eq!(Err(Error::Timeout), debounced_rfid_reader.read());
+        assert_eq!(1, statistics.suppressed_repeated());
+        assert_eq!(Err(Error::Access), debounced_rfid_reader.deinitialize());
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            "DebouncedRfidReader { rfid_reader: MockRfidReader, min_interval: 500ms, cooldown: 0ns }",
+            format!("{:?}", debounced(500, 0))
+        );
+    }
+}
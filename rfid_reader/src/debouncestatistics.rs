@@ -0,0 +1,96 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+struct Counters {
+    passed: AtomicU64,
+    suppressed_repeated: AtomicU64,
+    suppressed_in_cooldown: AtomicU64,
+}
+
+/// Statistics of the reads of a debounced [RfidReader](crate::RfidReader), see [Debounce](crate::Debounce).
+///
+/// Clones share the statistics, i.e. the statistics can be read on another thread than the one reading.
+#[derive(Debug, Default, Clone)]
+pub struct DebounceStatistics {
+    counters: Arc<Counters>,
+}
+
+impl DebounceStatistics {
+    /// Returns the number of reads that were passed on.
+    pub fn passed(&self) -> u64 {
+        self.counters.passed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of suppressed reads of the same value within the minimum interval.
+    pub fn suppressed_repeated(&self) -> u64 {
+        self.counters.suppressed_repeated.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of suppressed reads of a different value within the cooldown.
+    pub fn suppressed_in_cooldown(&self) -> u64 {
+        self.counters.suppressed_in_cooldown.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of all suppressed reads.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed_repeated() + self.suppressed_in_cooldown()
+    }
+
+    pub(crate) fn count_passed(&self) {
+        self.counters.passed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_repeated(&self) {
+        self.counters
+            .suppressed_repeated
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_in_cooldown(&self) {
+        self.counters
+            .suppressed_in_cooldown
+            .fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl std::fmt::Display for DebounceStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} suppressed ({} repeated, {} in cooldown)",
+            self.passed(),
+            self.suppressed(),
+            self.suppressed_repeated(),
+            self.suppressed_in_cooldown()
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_counters() {
+        let statistics = DebounceStatistics::default();
+        let shared_statistics = statistics.clone();
+        statistics.count_passed();
+        statistics.count_repeated();
+        statistics.count_repeated();
+        statistics.count_in_cooldown();
+        assert_eq!(1, shared_statistics.passed());
+        assert_eq!(2, shared_statistics.suppressed_repeated());
+        assert_eq!(1, shared_statistics.suppressed_in_cooldown());
+        assert_eq!(3, shared_statistics.suppressed());
+        assert_eq!(
+            "1 passed, 3 suppressed (2 repeated, 1 in cooldown)",
+            format!("{}", shared_statistics)
+        );
+    }
+}
@@ -3,6 +3,9 @@
 #![forbid(unsafe_code)]
 
 use crate::id::{ProductId, VendorId};
+
+use std::path::PathBuf;
+
 /// RFID Reader errors.
 ///
 /// Defines all runtime errors.
@@ -42,10 +45,52 @@ pub enum Error {
     /// Returned whenever an other error from the used USB library
     /// that is of minor importance for the RFID reader is returned.
     OtherUsbError(String),
+    /// Input Device Not Found.
+    ///
+    /// Returned whenever the input device (or recorded event stream) at the path does not exist.
+    InputDeviceNotFound(PathBuf),
+    /// Other Input Error.
+    ///
+    /// Returned whenever reading from or grabbing the input device fails for another reason.
+    OtherInputError(String),
+    /// Invalid Script.
+    ///
+    /// Returned whenever the script of a simulated RFID reader contains an invalid line.
+    InvalidScript(String),
+    /// Reader Stopped.
+    ///
+    /// Returned by an [RfidReaderHandle](crate::RfidReaderHandle) whenever its reader thread has stopped,
+    /// e.g. after an error or when it was cancelled, and all events were received.
+    ReaderStopped,
+    /// Disconnected.
+    ///
+    /// Returned whenever the USB device was disconnected, e.g. unplugged.
+    /// The device is reopened as soon as it is connected again.
+    Disconnected,
+    /// Invalid Rfid Value.
+    ///
+    /// Returned whenever a value is not a valid [RfidValue](crate::RfidValue), e.g. it has more than 20 digits.
+    InvalidRfidValue(String),
+    /// No Known Reader.
+    ///
+    /// Returned whenever no known RFID reader is attached, see [find_known_reader](crate::find_known_reader).
+    NoKnownReader,
+    /// Ambiguous Readers.
+    ///
+    /// Returned whenever the given number of known RFID readers is attached, i.e. the reader must be selected explicitly.
+    AmbiguousReaders(usize),
 }
 
 impl std::error::Error for Error {}
 
+impl From<rfid_value::Error> for Error {
+    fn from(error: rfid_value::Error) -> Self {
+        match error {
+            rfid_value::Error::InvalidRfidValue(value) => Error::InvalidRfidValue(value),
+        }
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
@@ -0,0 +1,106 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::humbleinputdevice::{HumbleInputDevice, KeyEvent};
+use crate::ioerror::from_io_error;
+use crate::Error;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use evdev::{Device, EventType};
+use nix::poll::{poll, PollFd, PollFlags};
+
+struct EvdevHumbleInputDevice {
+    device: RefCell<Device>,
+    path: PathBuf,
+    key_events: RefCell<VecDeque<KeyEvent>>,
+    timeout: Duration,
+    deinitialized: bool,
+}
+
+impl EvdevHumbleInputDevice {
+    /// Waits at most the timeout until the device is readable, then fetches all available key events.
+    #[cfg(not(tarpaulin_include))]
+    fn fetch_key_events(&self) -> Result<(), Error> {
+        let timeout = i32::try_from(self.timeout.as_millis()).unwrap_or(i32::MAX);
+        let mut poll_fds = [PollFd::new(
+            self.device.borrow().as_raw_fd(),
+            PollFlags::POLLIN,
+        )];
+        match poll(&mut poll_fds, timeout) {
+            Ok(0) => return Err(Error::Timeout),
+            Ok(_) => {}
+            // a signal interrupted the wait, which is handled like a timeout
+            Err(nix::Error::EINTR) => return Err(Error::Timeout),
+            Err(error) => return Err(Error::OtherInputError(error.to_string())),
+        }
+        let mut device = self.device.borrow_mut();
+        let events = device
+            .fetch_events()
+            .map_err(|error| from_io_error(error, &self.path))?;
+        self.key_events.borrow_mut().extend(
+            events
+                .filter(|event| event.event_type() == EventType::KEY)
+                .map(|event| KeyEvent {
+                    code: event.code(),
+                    value: event.value(),
+                }),
+        );
+        Ok(())
+    }
+}
+
+impl HumbleInputDevice for EvdevHumbleInputDevice {
+    #[cfg(not(tarpaulin_include))]
+    fn grab(&mut self) -> Result<(), Error> {
+        self.device
+            .get_mut()
+            .grab()
+            .map_err(|error| from_io_error(error, &self.path))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn ungrab(&mut self) -> Result<(), Error> {
+        self.device
+            .get_mut()
+            .ungrab()
+            .map_err(|error| from_io_error(error, &self.path))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn read(&self) -> Result<KeyEvent, Error> {
+        loop {
+            if let Some(key_event) = self.key_events.borrow_mut().pop_front() {
+                return Ok(key_event);
+            }
+            self.fetch_key_events()?;
+        }
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn set_deinitialized(&mut self) {
+        self.deinitialized = true;
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn deinitialized(&self) -> bool {
+        self.deinitialized
+    }
+}
+
+/// Opens the Linux input event device at `path`, e.g. `/dev/input/event0`.
+///
+/// The device is grabbed exclusively on initialization, so that the key events are not delivered to other applications.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn open(path: &Path, timeout: Duration) -> Result<impl HumbleInputDevice, Error> {
+    let device = Device::open(path).map_err(|error| from_io_error(error, path))?;
+    Ok(EvdevHumbleInputDevice {
+        device: RefCell::new(device),
+        path: path.to_path_buf(),
+        key_events: RefCell::new(VecDeque::new()),
+        timeout,
+        deinitialized: false,
+    })
+}
@@ -0,0 +1,81 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::keymap::{Key, KeyMap};
+
+/// Maps the Linux key codes (see `linux/input-event-codes.h`) of the main and the numeric keypad.
+pub struct EvdevKeyMap;
+
+impl KeyMap for EvdevKeyMap {
+    fn map(&self, key: u8) -> Result<Key, Error> {
+        match key {
+            // KEY_1 to KEY_9 are 2-10, KEY_0 is 11
+            2..=10 => Ok(Key::Digit(char::from(b'1' + key - 2))),
+            11 => Ok(Key::Digit('0')),
+            // KEY_KP7, KEY_KP8, KEY_KP9
+            71..=73 => Ok(Key::Digit(char::from(b'7' + key - 71))),
+            // KEY_KP4, KEY_KP5, KEY_KP6
+            75..=77 => Ok(Key::Digit(char::from(b'4' + key - 75))),
+            // KEY_KP1, KEY_KP2, KEY_KP3
+            79..=81 => Ok(Key::Digit(char::from(b'1' + key - 79))),
+            // KEY_KP0
+            82 => Ok(Key::Digit('0')),
+            // KEY_ENTER and KEY_KPENTER
+            28 | 96 => Ok(Key::Enter),
+            _ => Err(Error::KeyNotExisting(key)),
+        }
+    }
+}
+
+impl std::fmt::Debug for EvdevKeyMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvdevKeyMap").finish()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        let keymap = EvdevKeyMap;
+        let digits: Vec<Result<Key, Error>> = ('1'..='9').map(|c| Ok(Key::Digit(c))).collect();
+        let mapped_keys: Vec<Result<Key, Error>> = (2..=10).map(|i| keymap.map(i)).collect();
+        assert_eq!(digits, mapped_keys);
+        assert_eq!(Ok(Key::Digit('0')), keymap.map(11));
+    }
+
+    #[test]
+    fn test_keypad_digits() {
+        let keymap = EvdevKeyMap;
+        let mapped_keys: String = [82, 79, 80, 81, 75, 76, 77, 71, 72, 73]
+            .iter()
+            .map(|i| -> char { keymap.map(*i).unwrap().into() })
+            .collect();
+        assert_eq!("0123456789", mapped_keys);
+    }
+
+    #[test]
+    fn test_enter() {
+        let keymap = EvdevKeyMap;
+        assert_eq!(Ok(Key::Enter), keymap.map(28));
+        assert_eq!(Ok(Key::Enter), keymap.map(96));
+    }
+
+    #[test]
+    fn test_error() {
+        let keymap = EvdevKeyMap;
+        assert_eq!(Err(Error::KeyNotExisting(42)), keymap.map(42));
+        assert_eq!(Err(Error::KeyNotExisting(74)), keymap.map(74));
+    }
+
+    #[test]
+    fn test_debug() {
+        let key_map = EvdevKeyMap;
+        assert_eq!("EvdevKeyMap", format!("{:?}", key_map));
+    }
+}
@@ -0,0 +1,13 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+mod evdevhumbleinputdevice;
+mod keymap;
+mod recordedhumbleinputdevice;
+mod usbreader;
+
+pub(crate) use evdevhumbleinputdevice::open;
+pub(crate) use keymap::EvdevKeyMap;
+pub(crate) use recordedhumbleinputdevice::open_recording;
+pub(crate) use usbreader::new;
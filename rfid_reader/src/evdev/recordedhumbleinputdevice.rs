@@ -0,0 +1,128 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::humbleinputdevice::{HumbleInputDevice, KeyEvent};
+use crate::ioerror::from_io_error;
+use crate::Error;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::path::Path;
+
+/// Size of the `struct input_event` of the kernel, the `struct timeval` is followed by type, code and value.
+#[cfg(target_pointer_width = "64")]
+const INPUT_EVENT_SIZE: usize = 24;
+#[cfg(not(target_pointer_width = "64"))]
+const INPUT_EVENT_SIZE: usize = 16;
+
+/// Event type `EV_KEY` of key events.
+const EV_KEY: u16 = 1;
+
+/// Replays the key events of an event stream that was recorded from an input device,
+/// e.g. with `cat /dev/input/event0 > card.events`.
+struct RecordedHumbleInputDevice {
+    key_events: RefCell<VecDeque<KeyEvent>>,
+    deinitialized: bool,
+}
+
+impl RecordedHumbleInputDevice {
+    fn from(recording: &[u8]) -> Result<Self, Error> {
+        let events = recording.chunks_exact(INPUT_EVENT_SIZE);
+        if !events.remainder().is_empty() {
+            return Err(Error::TooFewReceivedData(events.remainder().len()));
+        }
+        let type_offset = INPUT_EVENT_SIZE - 8;
+        let key_events = events
+            .map(|event| event.split_at(type_offset).1)
+            .filter(|event| u16::from_ne_bytes([event[0], event[1]]) == EV_KEY)
+            .map(|event| KeyEvent {
+                code: u16::from_ne_bytes([event[2], event[3]]),
+                // the slice has exactly 4 bytes, therefore unwrapping is safe
+                value: i32::from_ne_bytes(event[4..8].try_into().unwrap()),
+            })
+            .collect();
+        Ok(RecordedHumbleInputDevice {
+            key_events: RefCell::new(key_events),
+            deinitialized: false,
+        })
+    }
+}
+
+impl HumbleInputDevice for RecordedHumbleInputDevice {
+    fn grab(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn ungrab(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn read(&self) -> Result<KeyEvent, Error> {
+        // the end of the recording behaves like a reader without cards
+        self.key_events
+            .borrow_mut()
+            .pop_front()
+            .ok_or(Error::Timeout)
+    }
+    fn set_deinitialized(&mut self) {
+        self.deinitialized = true;
+    }
+    fn deinitialized(&self) -> bool {
+        self.deinitialized
+    }
+}
+
+/// Opens the event stream recorded from an input device in the file at `path`.
+pub(crate) fn open_recording(path: &Path) -> Result<impl HumbleInputDevice, Error> {
+    let recording = std::fs::read(path).map_err(|error| from_io_error(error, path))?;
+    RecordedHumbleInputDevice::from(&recording)
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn input_event(event_type: u16, code: u16, value: i32) -> Vec<u8> {
+        let mut event = vec![0; INPUT_EVENT_SIZE - 8];
+        event.extend_from_slice(&event_type.to_ne_bytes());
+        event.extend_from_slice(&code.to_ne_bytes());
+        event.extend_from_slice(&value.to_ne_bytes());
+        event
+    }
+
+    #[test]
+    fn test_from() {
+        let mut recording = input_event(4, 4, 458_782);
+        recording.extend(input_event(EV_KEY, 2, 1));
+        recording.extend(input_event(0, 0, 0));
+        recording.extend(input_event(EV_KEY, 2, 0));
+
+        let mut device = RecordedHumbleInputDevice::from(&recording).unwrap();
+        device.initialize().unwrap();
+        assert_eq!(Ok(KeyEvent { code: 2, value: 1 }), device.read());
+        assert_eq!(Ok(KeyEvent { code: 2, value: 0 }), device.read());
+        assert_eq!(Err(Error::Timeout), device.read());
+        device.deinitialize().unwrap();
+        assert!(device.deinitialized());
+    }
+
+    #[test]
+    fn test_truncated_recording() {
+        let mut recording = input_event(EV_KEY, 2, 1);
+        recording.pop();
+        assert!(matches!(
+            RecordedHumbleInputDevice::from(&recording),
+            Err(Error::TooFewReceivedData(_))
+        ));
+    }
+
+    #[test]
+    fn test_open_recording_not_found() {
+        let path = Path::new("/non/existing/card.events");
+        assert!(matches!(
+            open_recording(path),
+            Err(Error::InputDeviceNotFound(_))
+        ));
+    }
+}
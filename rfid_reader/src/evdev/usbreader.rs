@@ -0,0 +1,146 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::humbleinputdevice::{HumbleInputDevice, KEY_PRESSED};
+
+use crate::usbreader::UsbReader;
+
+use std::convert::TryFrom;
+
+/// Key codes of KEY_ENTER and KEY_KPENTER, which terminate an RFID value.
+const ENTER_KEY_CODES: [u16; 2] = [28, 96];
+
+pub(crate) struct EvdevUsbReader<T>
+where
+    T: HumbleInputDevice,
+{
+    input_device: T,
+}
+
+impl<T: HumbleInputDevice> std::fmt::Debug for EvdevUsbReader<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvdevUsbReader").finish()
+    }
+}
+
+pub(crate) fn new<T: HumbleInputDevice>(humble_input_device: T) -> Result<impl UsbReader, Error> {
+    EvdevUsbReader::new(humble_input_device)
+}
+
+impl<T: HumbleInputDevice> EvdevUsbReader<T> {
+    fn new(input_device: T) -> Result<Self, Error> {
+        let mut input_device = input_device;
+        input_device.initialize()?;
+        Ok(EvdevUsbReader { input_device })
+    }
+}
+
+impl<T: HumbleInputDevice> UsbReader for EvdevUsbReader<T> {
+    fn read(&self) -> Result<Box<[u8]>, Error> {
+        let mut data = Vec::with_capacity(10);
+        loop {
+            let key_event = self.input_device.read()?;
+            // releases and auto repeats of keys are ignored
+            if key_event.value != KEY_PRESSED {
+                continue;
+            }
+            if ENTER_KEY_CODES.contains(&key_event.code) {
+                if !data.is_empty() {
+                    return Ok(data.into_boxed_slice());
+                }
+            } else if let Ok(code) = u8::try_from(key_event.code) {
+                // codes that are not digits are dropped by the key map
+                data.push(code);
+            }
+        }
+    }
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        self.input_device.deinitialize()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::humbleinputdevice::KeyEvent;
+
+    struct DummyHumbleInputDevice {
+        events: std::cell::RefCell<std::collections::VecDeque<KeyEvent>>,
+        grabbed: bool,
+        deinitialized: bool,
+    }
+
+    impl DummyHumbleInputDevice {
+        fn from(events: &[(u16, i32)]) -> Self {
+            DummyHumbleInputDevice {
+                events: events
+                    .iter()
+                    .map(|(code, value)| KeyEvent {
+                        code: *code,
+                        value: *value,
+                    })
+                    .collect::<std::collections::VecDeque<KeyEvent>>()
+                    .into(),
+                grabbed: false,
+                deinitialized: false,
+            }
+        }
+    }
+
+    impl HumbleInputDevice for DummyHumbleInputDevice {
+        fn grab(&mut self) -> Result<(), Error> {
+            self.grabbed = true;
+            Ok(())
+        }
+        fn ungrab(&mut self) -> Result<(), Error> {
+            self.grabbed = false;
+            Ok(())
+        }
+        fn read(&self) -> Result<KeyEvent, Error> {
+            self.events.borrow_mut().pop_front().ok_or(Error::Timeout)
+        }
+        fn set_deinitialized(&mut self) {
+            self.deinitialized = true;
+        }
+        fn deinitialized(&self) -> bool {
+            self.deinitialized
+        }
+    }
+
+    #[test]
+    fn test_usb_reader_successful_read() {
+        let dummy_device = DummyHumbleInputDevice::from(&[
+            (28, 0),
+            (28, KEY_PRESSED),
+            (2, KEY_PRESSED),
+            (2, 0),
+            (3, KEY_PRESSED),
+            (3, 2),
+            (42, KEY_PRESSED),
+            (400, KEY_PRESSED),
+            (96, KEY_PRESSED),
+            (11, KEY_PRESSED),
+        ]);
+        let mut usb_reader = EvdevUsbReader::new(dummy_device).unwrap();
+        assert!(usb_reader.input_device.grabbed);
+
+        let result = usb_reader.read();
+        assert_eq!(vec![2, 3, 42], result.unwrap().into_vec());
+        // the value is incomplete until enter is pressed
+        assert_eq!(Err(Error::Timeout), usb_reader.read());
+
+        usb_reader.deinitialize().unwrap();
+        assert!(!usb_reader.input_device.grabbed);
+        assert!(usb_reader.input_device.deinitialized);
+    }
+
+    #[test]
+    fn test_debug() {
+        let dummy_device = DummyHumbleInputDevice::from(&[]);
+        let usb_reader = new(dummy_device).unwrap();
+        assert_eq!("EvdevUsbReader", format!("{:?}", usb_reader));
+    }
+}
@@ -0,0 +1,123 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::keymap::{Key, KeyMap};
+use rfid_value::RfidValue;
+
+/// Number of hexadecimal digits of an EM4100 card number including the version byte.
+const EM4100_DIGITS: usize = 10;
+
+/// Maps the HID usage codes (see the HID Usage Tables, keyboard/keypad page) of the main and the numeric keypad.
+///
+/// With `hex_digits`, the keys A to F are hexadecimal digits as well.
+pub struct HidKeyMap {
+    hex_digits: bool,
+}
+
+impl HidKeyMap {
+    pub(crate) fn new(hex_digits: bool) -> Self {
+        HidKeyMap { hex_digits }
+    }
+}
+
+impl KeyMap for HidKeyMap {
+    fn map(&self, key: u8) -> Result<Key, Error> {
+        match key {
+            // keyboard A to F are 0x04-0x09
+            0x04..=0x09 if self.hex_digits => Ok(Key::Digit(char::from(b'A' + key - 0x04))),
+            // keyboard 1 to 9 are 0x1E-0x26, keyboard 0 is 0x27
+            0x1e..=0x26 => Ok(Key::Digit(char::from(b'1' + key - 0x1e))),
+            0x27 => Ok(Key::Digit('0')),
+            // keypad 1 to 9 are 0x59-0x61, keypad 0 is 0x62
+            0x59..=0x61 => Ok(Key::Digit(char::from(b'1' + key - 0x59))),
+            0x62 => Ok(Key::Digit('0')),
+            // keyboard enter and keypad enter
+            0x28 | 0x58 => Ok(Key::Enter),
+            _ => Err(Error::KeyNotExisting(key)),
+        }
+    }
+    fn parse(&self, value: &str) -> Result<RfidValue, Error> {
+        let rfid_value = if !self.hex_digits {
+            value.parse()
+        } else if value.len() == EM4100_DIGITS {
+            RfidValue::from_em4100(value)
+        } else {
+            RfidValue::from_hex(value)
+        };
+        Ok(rfid_value?)
+    }
+}
+
+impl std::fmt::Debug for HidKeyMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HidKeyMap")
+            .field("hex_digits", &self.hex_digits)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        let keymap = HidKeyMap::new(false);
+        let digits: Vec<Result<Key, Error>> = ('1'..='9').map(|c| Ok(Key::Digit(c))).collect();
+        let mapped_keys: Vec<Result<Key, Error>> = (30..39).map(|i| keymap.map(i)).collect();
+        assert_eq!(digits, mapped_keys);
+        assert_eq!(Ok(Key::Digit('0')), keymap.map(39));
+    }
+
+    #[test]
+    fn test_keypad_digits() {
+        let keymap = HidKeyMap::new(false);
+        let mapped_keys: String = (0x59..=0x62)
+            .map(|i| -> char { keymap.map(i).unwrap().into() })
+            .collect();
+        assert_eq!("1234567890", mapped_keys);
+    }
+
+    #[test]
+    fn test_hex_digits() {
+        let keymap = HidKeyMap::new(true);
+        let mapped_keys: String = (0x04..=0x09)
+            .map(|i| -> char { keymap.map(i).unwrap().into() })
+            .collect();
+        assert_eq!("ABCDEF", mapped_keys);
+        assert_eq!(Err(Error::KeyNotExisting(4)), HidKeyMap::new(false).map(4));
+        assert_eq!(Err(Error::KeyNotExisting(10)), keymap.map(10));
+    }
+
+    #[test]
+    fn test_enter() {
+        let keymap = HidKeyMap::new(false);
+        assert_eq!(Ok(Key::Enter), keymap.map(40));
+        assert_eq!(Ok(Key::Enter), keymap.map(0x58));
+    }
+
+    #[test]
+    fn test_error() {
+        let keymap = HidKeyMap::new(false);
+        assert_eq!(Err(Error::KeyNotExisting(17)), keymap.map(17));
+    }
+
+    #[test]
+    fn test_parse() {
+        let keymap = HidKeyMap::new(false);
+        assert_eq!(Ok(RfidValue::from(12_345_678)), keymap.parse("0012345678"));
+        let keymap = HidKeyMap::new(true);
+        assert_eq!(Ok(RfidValue::from(0x00bc_614e)), keymap.parse("BC614E"));
+        assert_eq!(Ok(RfidValue::from(0x00bc_614e)), keymap.parse("1A00BC614E"));
+        assert!(keymap.parse("").is_err());
+    }
+
+    #[test]
+    fn test_debug() {
+        let key_map = HidKeyMap::new(true);
+        assert_eq!("HidKeyMap { hex_digits: true }", format!("{:?}", key_map));
+    }
+}
@@ -5,5 +5,5 @@
 mod keymap;
 mod usbreader;
 
-pub(crate) use keymap::NeuftechKeyMap;
+pub(crate) use keymap::HidKeyMap;
 pub(crate) use usbreader::new;
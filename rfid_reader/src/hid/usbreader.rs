@@ -0,0 +1,306 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::hidformat::HidFormat;
+use crate::humbleusbdevice::HumbleUsbDevice;
+use crate::terminatorkey::TerminatorKey;
+
+use crate::usbreader::UsbReader;
+
+use std::cell::RefCell;
+
+/// Offset of the key codes in a report, which are preceded by the modifier byte and a reserved byte.
+const KEY_CODES_OFFSET: usize = 2;
+/// Key code reported in all slots when too many keys are pressed at once.
+const ERROR_ROLL_OVER: u8 = 0x01;
+
+/// Decodes the reports of a USB device that behaves like a HID boot keyboard.
+///
+/// The key codes typed until the terminator key are returned, the key map interprets them.
+pub(crate) struct HidUsbReader<T>
+where
+    T: HumbleUsbDevice,
+{
+    usb_device: T,
+    report_size: usize,
+    terminator: TerminatorKey,
+    pressed_keys: RefCell<Vec<u8>>,
+}
+
+impl<T: HumbleUsbDevice> std::fmt::Debug for HidUsbReader<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HidUsbReader")
+            .field("report_size", &self.report_size)
+            .field("terminator", &self.terminator)
+            .finish()
+    }
+}
+
+pub(crate) fn new<T: HumbleUsbDevice>(
+    humble_usb_device: T,
+    hid_format: HidFormat,
+) -> Result<impl UsbReader, Error> {
+    HidUsbReader::new(humble_usb_device, hid_format)
+}
+
+impl<T: HumbleUsbDevice> HidUsbReader<T> {
+    fn new(usb_device: T, hid_format: HidFormat) -> Result<Self, Error> {
+        let mut usb_device = usb_device;
+        usb_device.initialize()?;
+        Ok(HidUsbReader {
+            usb_device,
+            report_size: hid_format.report_size(),
+            terminator: hid_format.terminator(),
+            pressed_keys: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Returns the keys of the report that were not pressed in the previous report, in the order of the report.
+    fn newly_pressed_keys(&self, report: &[u8]) -> Result<Vec<u8>, Error> {
+        if report.len() <= KEY_CODES_OFFSET {
+            return Err(Error::TooFewReceivedData(report.len()));
+        }
+        let keys: Vec<u8> = report[KEY_CODES_OFFSET..]
+            .iter()
+            .copied()
+            .filter(|key| *key != 0)
+            .collect();
+        // the pressed keys are unknown during a roll over, i.e. the report is ignored
+        if keys.contains(&ERROR_ROLL_OVER) {
+            return Ok(Vec::new());
+        }
+        let mut pressed_keys = self.pressed_keys.borrow_mut();
+        let newly_pressed_keys = keys
+            .iter()
+            .copied()
+            .filter(|key| !pressed_keys.contains(key))
+            .collect();
+        *pressed_keys = keys;
+        Ok(newly_pressed_keys)
+    }
+}
+
+impl<T: HumbleUsbDevice> UsbReader for HidUsbReader<T> {
+    fn read(&self) -> Result<Box<[u8]>, Error> {
+        let mut data = Vec::with_capacity(10);
+        let mut report = vec![0; self.report_size];
+        loop {
+            self.usb_device.read(&mut report)?;
+            for key in self.newly_pressed_keys(&report)? {
+                if self.terminator.matches(key) {
+                    if !data.is_empty() {
+                        return Ok(data.into_boxed_slice());
+                    }
+                } else {
+                    // codes that are not digits are dropped by the key map
+                    data.push(key);
+                }
+            }
+        }
+    }
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        self.usb_device.deinitialize()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    struct ReadErrorHumbleUsbDevice {
+        deinitialized: bool,
+    }
+
+    impl HumbleUsbDevice for ReadErrorHumbleUsbDevice {
+        fn has_attached_kernel_driver(&self) -> Result<bool, Error> {
+            Ok(true)
+        }
+        fn detach_kernel_driver(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn attach_kernel_driver(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn read(&self, buffer: &mut [u8]) -> Result<(), Error> {
+            Err(Error::InvalidData(0))
+        }
+        fn claim_interface(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn release_interface(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_active_configuration(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_alternate_setting(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_deinitialized(&mut self) {
+            self.deinitialized = true;
+        }
+        fn deinitialized(&self) -> bool {
+            self.deinitialized
+        }
+    }
+
+    #[test]
+    fn test_newly_pressed_keys() {
+        let dummy_device = ReadErrorHumbleUsbDevice {
+            deinitialized: false,
+        };
+        let usb_reader = HidUsbReader::new(dummy_device, HidFormat::boot_keyboard()).unwrap();
+        assert_eq!(
+            Err(Error::TooFewReceivedData(2)),
+            usb_reader.newly_pressed_keys(&[0, 0])
+        );
+        assert_eq!(
+            Ok(vec![30, 31]),
+            usb_reader.newly_pressed_keys(&[0, 0, 30, 31, 0, 0, 0, 0])
+        );
+        // still pressed keys are not repeated
+        assert_eq!(
+            Ok(vec![32]),
+            usb_reader.newly_pressed_keys(&[0, 0, 31, 32, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            Ok(vec![]),
+            usb_reader.newly_pressed_keys(&[0, 0, 1, 1, 1, 1, 1, 1])
+        );
+        assert_eq!(Ok(vec![]), usb_reader.newly_pressed_keys(&[0; 8]));
+        assert_eq!(
+            Ok(vec![31]),
+            usb_reader.newly_pressed_keys(&[2, 0, 31, 0, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_usb_reader_read_error() {
+        let mut dummy_device = ReadErrorHumbleUsbDevice {
+            deinitialized: false,
+        };
+        let mut usb_reader = new(dummy_device, HidFormat::neuftech()).unwrap();
+        let result = usb_reader.read();
+        assert_eq!(Err(Error::InvalidData(0)), result);
+        usb_reader.deinitialize().unwrap();
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut dummy_device = ReadErrorHumbleUsbDevice {
+            deinitialized: false,
+        };
+        dummy_device.initialize().unwrap();
+        let mut usb_reader = HidUsbReader::new(dummy_device, HidFormat::neuftech()).unwrap();
+        assert_eq!(
+            "HidUsbReader { report_size: 3, terminator: Enter }",
+            format!("{:?}", usb_reader)
+        );
+        usb_reader.deinitialize().unwrap();
+    }
+
+    struct DummyHumbleUsbDevice {
+        reports: std::cell::RefCell<std::collections::VecDeque<Vec<u8>>>,
+        deinitialized: bool,
+    }
+
+    impl DummyHumbleUsbDevice {
+        fn from(reports: Vec<Vec<u8>>) -> Self {
+            DummyHumbleUsbDevice {
+                reports: std::cell::RefCell::new(reports.into()),
+                deinitialized: false,
+            }
+        }
+    }
+
+    impl HumbleUsbDevice for DummyHumbleUsbDevice {
+        fn has_attached_kernel_driver(&self) -> Result<bool, Error> {
+            Ok(true)
+        }
+        fn detach_kernel_driver(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn attach_kernel_driver(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn read(&self, buffer: &mut [u8]) -> Result<(), Error> {
+            let report = self
+                .reports
+                .borrow_mut()
+                .pop_front()
+                .ok_or(Error::Timeout)?;
+            buffer.copy_from_slice(&report);
+            Ok(())
+        }
+        fn claim_interface(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn release_interface(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_active_configuration(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_alternate_setting(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_deinitialized(&mut self) {
+            self.deinitialized = true;
+        }
+        fn deinitialized(&self) -> bool {
+            self.deinitialized
+        }
+    }
+
+    /// Reports of the Neuftech reader typing `keys`, each press is followed by a release.
+    fn neuftech_reports(keys: &[u8]) -> Vec<Vec<u8>> {
+        keys.iter()
+            .flat_map(|key| vec![vec![0, 0, *key], vec![0, 0, 0]])
+            .collect()
+    }
+
+    #[test]
+    fn test_usb_reader_successful_read() {
+        let mut keys = vec![30; 10];
+        keys.push(40);
+        let dummy_device = DummyHumbleUsbDevice::from(neuftech_reports(&keys));
+        let usb_reader = HidUsbReader::new(dummy_device, HidFormat::neuftech()).unwrap();
+        let result = usb_reader.read();
+
+        let expected_data: Vec<u8> = vec![30; 10];
+        assert_eq!(expected_data, result.unwrap().into_vec());
+        assert_eq!(Err(Error::Timeout), usb_reader.read());
+    }
+
+    #[test]
+    fn test_usb_reader_variable_length() {
+        let dummy_device = DummyHumbleUsbDevice::from(neuftech_reports(&[40, 31, 32, 40, 33]));
+        let usb_reader = HidUsbReader::new(dummy_device, HidFormat::neuftech()).unwrap();
+        // a leading terminator is ignored
+        assert_eq!(vec![31, 32], usb_reader.read().unwrap().into_vec());
+        // the value is incomplete until the terminator is pressed
+        assert_eq!(Err(Error::Timeout), usb_reader.read());
+    }
+
+    #[test]
+    fn test_usb_reader_boot_keyboard() {
+        let hid_format = HidFormat::new(8, TerminatorKey::Tab, true);
+        let dummy_device = DummyHumbleUsbDevice::from(vec![
+            // shifted A and 1 are pressed at once
+            vec![2, 0, 4, 30, 0, 0, 0, 0],
+            vec![0, 0, 30, 0, 0, 0, 0, 0],
+            vec![0, 0, 30, 31, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+            // enter is no terminator
+            vec![0, 0, 40, 0, 0, 0, 0, 0],
+            vec![0, 0, 43, 0, 0, 0, 0, 0],
+        ]);
+        let mut usb_reader = HidUsbReader::new(dummy_device, hid_format).unwrap();
+        assert_eq!(vec![4, 30, 31, 40], usb_reader.read().unwrap().into_vec());
+        usb_reader.deinitialize().unwrap();
+        assert!(usb_reader.usb_device.deinitialized);
+    }
+}
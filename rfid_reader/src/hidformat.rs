@@ -0,0 +1,131 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::terminatorkey::TerminatorKey;
+
+/// Size of the reports of a HID boot keyboard, i.e. the modifier byte, a reserved byte and up to 6 key codes.
+pub(crate) const BOOT_KEYBOARD_REPORT_SIZE: usize = 8;
+/// Minimum size of a report, i.e. the modifier byte, the reserved byte and one key code.
+pub(crate) const MIN_REPORT_SIZE: usize = 3;
+
+/// The format in which a USB RFID reader that behaves like a HID boot keyboard types the RFID values.
+///
+/// Each report of the reader contains the modifier byte, a reserved byte and the codes of the pressed keys.
+/// The RFID value consists of the digits typed until the `terminator` key, the number of digits is variable.
+/// With `hex_digits`, the value is typed in hexadecimal, i.e. the keys A to F are digits as well.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use rfid_reader::{HidFormat, ProductId, TerminatorKey, UsbDeviceSelector, VendorId};
+///
+/// // a reader with full boot keyboard reports, which types hexadecimal values terminated by tab
+/// let hid_format = HidFormat::new(8, TerminatorKey::Tab, true);
+/// let rfid_reader = rfid_reader::open_hid(
+///     VendorId::from(0x16c0),
+///     ProductId::from(0x27db),
+///     UsbDeviceSelector::Any,
+///     hid_format,
+///     Duration::from_secs(1),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HidFormat {
+    report_size: usize,
+    terminator: TerminatorKey,
+    hex_digits: bool,
+}
+
+impl HidFormat {
+    /// Creates the format of reports with `report_size` bytes (3 to 8), whose values are terminated by the `terminator` key.
+    ///
+    /// The `report_size` is limited to these bounds.
+    pub fn new(report_size: usize, terminator: TerminatorKey, hex_digits: bool) -> Self {
+        HidFormat {
+            report_size: report_size.clamp(MIN_REPORT_SIZE, BOOT_KEYBOARD_REPORT_SIZE),
+            terminator,
+            hex_digits,
+        }
+    }
+
+    /// The format of the Neuftech reader, which reports a single key code per report and decimal values terminated by enter.
+    pub fn neuftech() -> Self {
+        HidFormat::new(MIN_REPORT_SIZE, TerminatorKey::Enter, false)
+    }
+
+    /// The format of a reader with full boot keyboard reports and decimal values terminated by enter.
+    pub fn boot_keyboard() -> Self {
+        HidFormat::new(BOOT_KEYBOARD_REPORT_SIZE, TerminatorKey::Enter, false)
+    }
+
+    /// Returns the size of the reports in bytes.
+    pub fn report_size(&self) -> usize {
+        self.report_size
+    }
+
+    /// Returns the key that terminates a value.
+    pub fn terminator(&self) -> TerminatorKey {
+        self.terminator
+    }
+
+    /// Returns whether the values are typed in hexadecimal.
+    pub fn hex_digits(&self) -> bool {
+        self.hex_digits
+    }
+}
+
+impl std::fmt::Display for HidFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} byte reports with {} digits terminated by {}",
+            self.report_size,
+            if self.hex_digits {
+                "hexadecimal"
+            } else {
+                "decimal"
+            },
+            self.terminator
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let neuftech = HidFormat::neuftech();
+        assert_eq!(3, neuftech.report_size());
+        assert_eq!(TerminatorKey::Enter, neuftech.terminator());
+        assert!(!neuftech.hex_digits());
+        assert_eq!(8, HidFormat::boot_keyboard().report_size());
+    }
+
+    #[test]
+    fn test_report_size_bounds() {
+        assert_eq!(
+            3,
+            HidFormat::new(1, TerminatorKey::Enter, false).report_size()
+        );
+        assert_eq!(
+            8,
+            HidFormat::new(64, TerminatorKey::Enter, false).report_size()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "3 byte reports with decimal digits terminated by enter",
+            format!("{}", HidFormat::neuftech())
+        );
+        assert_eq!(
+            "8 byte reports with hexadecimal digits terminated by tab",
+            format!("{}", HidFormat::new(8, TerminatorKey::Tab, true))
+        );
+    }
+}
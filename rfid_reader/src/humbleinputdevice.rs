@@ -0,0 +1,33 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+
+/// Value of a [`KeyEvent`] when the key is pressed (released is 0, repeated is 2).
+pub(crate) const KEY_PRESSED: i32 = 1;
+
+/// Key event of a Linux input device, i.e. the code of the key and its value.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct KeyEvent {
+    pub(crate) code: u16,
+    pub(crate) value: i32,
+}
+
+pub(crate) trait HumbleInputDevice {
+    fn grab(&mut self) -> Result<(), Error>;
+    fn ungrab(&mut self) -> Result<(), Error>;
+    fn read(&self) -> Result<KeyEvent, Error>;
+    fn initialize(&mut self) -> Result<(), Error> {
+        self.grab()
+    }
+    fn set_deinitialized(&mut self);
+    fn deinitialized(&self) -> bool;
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        if !self.deinitialized() {
+            self.set_deinitialized();
+            self.ungrab()?;
+        }
+        Ok(())
+    }
+}
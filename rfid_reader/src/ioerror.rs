@@ -0,0 +1,39 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::Error;
+
+use std::path::Path;
+
+/// Converts an I/O error of the input device at `path` into an [`Error`](crate::Error).
+pub(crate) fn from_io_error(error: std::io::Error, path: &Path) -> Error {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => Error::InputDeviceNotFound(path.to_path_buf()),
+        std::io::ErrorKind::PermissionDenied => Error::Access,
+        _ => Error::OtherInputError(format!("{}: {}", path.display(), error)),
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_error() {
+        let path = Path::new("/dev/input/event42");
+        let error = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert_eq!(
+            Error::InputDeviceNotFound(path.to_path_buf()),
+            from_io_error(error, path)
+        );
+        let error = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert_eq!(Error::Access, from_io_error(error, path));
+        let error = std::io::Error::from(std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            Error::OtherInputError("/dev/input/event42: invalid data".to_string()),
+            from_io_error(error, path)
+        );
+    }
+}
@@ -3,6 +3,7 @@
 #![forbid(unsafe_code)]
 
 use crate::error::Error;
+use rfid_value::RfidValue;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum Key {
@@ -11,6 +12,10 @@ pub(crate) enum Key {
 }
 pub(crate) trait KeyMap: std::fmt::Debug {
     fn map(&self, key: u8) -> Result<Key, Error>;
+    /// Parses the digits of the mapped keys, which are decimal by default.
+    fn parse(&self, value: &str) -> Result<RfidValue, Error> {
+        Ok(value.parse()?)
+    }
 }
 
 impl Into<char> for Key {
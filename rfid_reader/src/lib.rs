@@ -1,11 +1,27 @@
 //! A crate for a RFID Reader USB device.
 //!
 //! The RFID Reader can be opened to get a [RfidReader](crate::RfidReader).
-//! One can read the processed data as String from this device.
+//! One can read the processed data as [RfidValue](crate::RfidValue) from this device.
 //! It makes also sense to de-initialize the [RfidReader](crate::RfidReader) upon finishing.
 //!
-//! This crate is specifically implemented for a Neuftech RFID reader,
-//! but may be extended to support other manufacturers.
+//! RFID readers that behave like a USB HID keyboard are supported, their format is configured with a [HidFormat](crate::HidFormat).
+//! [open](crate::open) uses the format of the Neuftech RFID reader, [open_hid](crate::open_hid) any other format.
+//!
+//! The attached USB devices are listed with [list_devices](crate::list_devices), which also tells the known readers apart.
+//!
+//! On Linux, RFID readers that present themselves as keyboards can alternatively be opened
+//! as input event device with [open_event_device](crate::open_event_device),
+//! which does not detach the kernel HID driver.
+//!
+//! Without any hardware, a simulated [RfidReader](crate::RfidReader) can be opened with
+//! [open_simulated](crate::open_simulated), which reads the RFID values from a [SimulatedInput](crate::SimulatedInput).
+//!
+//! Repeated reads of a card lying on the reader can be suppressed by wrapping any [RfidReader](crate::RfidReader)
+//! with a [Debounce](crate::Debounce).
+//!
+//! Reading is blocking. In order to react to other events while waiting for an RFID value, e.g. to shut down immediately on a signal,
+//! [spawn](crate::spawn) reads on a thread of its own and delivers [RfidEvent](crate::RfidEvent)s via an [RfidReaderHandle](crate::RfidReaderHandle).
+//! With the `async` feature, [spawn_stream](crate::spawn_stream) delivers them as [Stream](futures_core::Stream) instead.
 //!
 //! # Example
 //! ```rust,no_run
@@ -23,7 +39,7 @@
 //! let mut rfid_reader = rfid_reader::open(vendor_id, product_id, timeout).unwrap();
 //!
 //! // Read from the device. This is blocking!
-//! // It should normally return a String containing numbers, but it may also fail.
+//! // It should normally return the RfidValue of the card, but it may also fail.
 //! // It will return Error::Timeout whenever the timeout has expired
 //! let data = rfid_reader.read().unwrap();
 //!
@@ -39,20 +55,54 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
+use std::path::Path;
 use std::time::Duration;
 
+mod canceller;
+mod debounce;
+mod debouncedrfidreader;
+mod debouncestatistics;
 mod error;
+#[cfg(target_os = "linux")]
+mod evdev;
+mod hid;
+mod hidformat;
+mod humbleinputdevice;
 mod humbleusbdevice;
 mod id;
+mod ioerror;
 mod keymap;
-mod neuftech;
+mod presencedetector;
+mod reconnectinghumbleusbdevice;
+mod rfidevent;
 mod rfidreader;
+mod rfidreaderhandle;
+#[cfg(feature = "async")]
+mod rfidstream;
 mod rusb;
+mod simulated;
+mod simulatedinput;
+mod terminatorkey;
+mod usbdeviceinfo;
+mod usbdeviceselector;
 mod usbreader;
 
+pub use canceller::Canceller;
+pub use debounce::Debounce;
+pub use debouncestatistics::DebounceStatistics;
 pub use error::Error;
+pub use hidformat::HidFormat;
 pub use id::{ProductId, VendorId};
+pub use rfid_value::RfidValue;
+pub use rfidevent::RfidEvent;
 pub use rfidreader::RfidReader;
+pub use rfidreaderhandle::RfidReaderHandle;
+#[cfg(feature = "async")]
+pub use rfidstream::RfidStream;
+pub use simulatedinput::SimulatedInput;
+pub use terminatorkey::TerminatorKey;
+pub use usbdeviceinfo::UsbDeviceInfo;
+pub use usbdeviceselector::UsbDeviceSelector;
 
 /// Tries to open an [RfidReader](crate::RfidReader).
 ///
@@ -60,17 +110,214 @@ pub use rfidreader::RfidReader;
 /// The `timeout` is used to wait blocking for an interrupt of the USB device.
 /// From my experience: The longer the timeout the better.
 ///
+/// The device is expected to report in the format of the Neuftech RFID reader, see [HidFormat::neuftech](crate::HidFormat::neuftech).
+/// If several devices have the vendor and product id, the first one is opened.
+/// Use [open_hid](crate::open_hid) for readers of other brands or in order to select one of several readers.
+///
 /// May fail. Most important errors are [Error::DeviceNotFound](crate::error::Error::DeviceNotFound),
 /// when the device could not be found. Or [Error::Access](crate::error::Error::Access) when there is
 /// insufficient rights to open device.
+///
+/// If the device is disconnected later on, reading returns [Error::Disconnected](crate::error::Error::Disconnected)
+/// until the device is connected again, then it is reopened and re-initialized.
 #[cfg(not(tarpaulin_include))]
 pub fn open(
     vendor_id: VendorId,
     product_id: ProductId,
     timeout: Duration,
 ) -> Result<impl RfidReader, Error> {
-    let key_map = neuftech::NeuftechKeyMap;
-    let rusb_device = rusb::open(vendor_id, product_id, timeout)?;
-    let usb_reader = neuftech::new(rusb_device)?;
+    open_hid(
+        vendor_id,
+        product_id,
+        UsbDeviceSelector::Any,
+        HidFormat::neuftech(),
+        timeout,
+    )
+}
+
+/// Like [open](crate::open), but opens the device with the vendor and product id that is selected by the [UsbDeviceSelector](crate::UsbDeviceSelector)
+/// and decodes its reports in the given [HidFormat](crate::HidFormat).
+///
+/// The selector distinguishes several identical readers, e.g. by the port they are plugged into.
+/// The format supports RFID readers that behave like a USB HID keyboard, e.g. with full boot keyboard reports,
+/// values of variable length, hexadecimal digits or another terminator key.
+#[cfg(not(tarpaulin_include))]
+pub fn open_hid(
+    vendor_id: VendorId,
+    product_id: ProductId,
+    selector: UsbDeviceSelector,
+    hid_format: HidFormat,
+    timeout: Duration,
+) -> Result<impl RfidReader, Error> {
+    let key_map = hid::HidKeyMap::new(hid_format.hex_digits());
+    let rusb_device = rusb::open(vendor_id, product_id, &selector, timeout)?;
+    let usb_device = reconnectinghumbleusbdevice::from(
+        rusb_device,
+        move || rusb::open(vendor_id, product_id, &selector, timeout),
+        timeout,
+    );
+    let usb_reader = hid::new(usb_device, hid_format)?;
     Ok(rfidreader::from(key_map, usb_reader))
 }
+
+/// Lists the USB devices attached to the system, e.g. in order to find the vendor and product id of the RFID reader.
+///
+/// See [UsbDeviceInfo::readable](crate::UsbDeviceInfo::readable) and [UsbDeviceInfo::known_reader](crate::UsbDeviceInfo::known_reader)
+/// for the devices that are candidates of RFID readers.
+///
+/// # Example
+/// ```rust,no_run
+/// for device in rfid_reader::list_devices().unwrap() {
+///     println!("{}", device);
+/// }
+/// ```
+#[cfg(not(tarpaulin_include))]
+pub fn list_devices() -> Result<Vec<UsbDeviceInfo>, Error> {
+    rusb::list()
+}
+
+/// Finds the only known RFID reader that is attached to the system and matches the `selector`, see [UsbDeviceInfo::known_reader](crate::UsbDeviceInfo::known_reader).
+///
+/// Fails with [Error::NoKnownReader](crate::error::Error::NoKnownReader) if no known reader is attached and with
+/// [Error::AmbiguousReaders](crate::error::Error::AmbiguousReaders) if several are attached.
+#[cfg(not(tarpaulin_include))]
+pub fn find_known_reader(selector: &UsbDeviceSelector) -> Result<UsbDeviceInfo, Error> {
+    usbdeviceinfo::select_known_reader(list_devices()?, selector)
+}
+
+/// Tries to open an [RfidReader](crate::RfidReader) from a Linux input event device.
+///
+/// The `path` is usually one of `/dev/input/event*`, preferably a stable link like `/dev/input/by-id/*-event-kbd`.
+/// The device is grabbed exclusively, i.e. the RFID values are no longer typed into other applications.
+/// The `timeout` is used to wait blocking for key events of the device.
+///
+/// If `path` is a regular file, it is treated as an event stream recorded from an input device
+/// (e.g. with `cat /dev/input/event0 > card.events`) and the recorded RFID values are replayed.
+/// After the last value, reading returns [Error::Timeout](crate::error::Error::Timeout).
+///
+/// May fail. Most important errors are [Error::InputDeviceNotFound](crate::error::Error::InputDeviceNotFound),
+/// when the device could not be found. Or [Error::Access](crate::error::Error::Access) when there is
+/// insufficient rights to open the device.
+#[cfg(target_os = "linux")]
+pub fn open_event_device(path: &Path, timeout: Duration) -> Result<impl RfidReader, Error> {
+    let key_map = evdev::EvdevKeyMap;
+    let rfid_reader: Box<dyn RfidReader> = if path.is_file() {
+        let input_device = evdev::open_recording(path)?;
+        Box::new(rfidreader::from(key_map, evdev::new(input_device)?))
+    } else {
+        let input_device = evdev::open(path, timeout)?;
+        Box::new(rfidreader::from(key_map, evdev::new(input_device)?))
+    };
+    Ok(rfid_reader)
+}
+
+/// Opens a simulated [RfidReader](crate::RfidReader), which reads the RFID values from the `input`.
+///
+/// Reading waits at most the `timeout` for the next RFID value and returns [Error::Timeout](crate::error::Error::Timeout) otherwise,
+/// also when the input is exhausted. Invalid lines of stdin or the pipe are skipped with a warning.
+///
+/// May fail if the pipe or the script of the `input` does not exist, the pipe is no named pipe
+/// or the script is invalid, see [Error::InvalidScript](crate::error::Error::InvalidScript).
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use rfid_reader::{RfidReader, SimulatedInput};
+///
+/// let input: SimulatedInput = "script:cards.txt".parse().unwrap();
+/// let rfid_reader = rfid_reader::open_simulated(input, Duration::from_secs(1)).unwrap();
+/// let data = rfid_reader.read().unwrap();
+/// ```
+pub fn open_simulated(input: SimulatedInput, timeout: Duration) -> Result<impl RfidReader, Error> {
+    simulated::open(input, timeout)
+}
+
+/// Reads the [RfidReader](crate::RfidReader) returned by `open` on a thread of its own.
+///
+/// The reader is opened on the thread, therefore it needs not be `Send`. Errors of `open` are returned immediately.
+/// The read RFID values are delivered as [RfidEvent](crate::RfidEvent)s by the returned [RfidReaderHandle](crate::RfidReaderHandle),
+/// which can be cancelled from any thread, see [Canceller](crate::Canceller).
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use rfid_reader::{ProductId, RfidEvent, VendorId};
+///
+/// let timeout = Duration::from_secs(1);
+/// let mut handle = rfid_reader::spawn(move || {
+///     rfid_reader::open(VendorId::from(0x16c0), ProductId::from(0x27db), timeout)
+/// })
+/// .unwrap();
+///
+/// // e.g. cancel from a signal handler
+/// let canceller = handle.canceller();
+///
+/// // does not block
+/// if let Ok(RfidEvent::Read(rfid_value)) = handle.try_recv() {
+///     println!("Read {}", rfid_value);
+/// }
+/// // blocks until an event is available, also if cancelled in the meantime
+/// let event = handle.recv_timeout(Duration::from_secs(60));
+///
+/// handle.deinitialize().unwrap();
+/// ```
+pub fn spawn<R, F>(open: F) -> Result<RfidReaderHandle, Error>
+where
+    R: RfidReader + 'static,
+    F: FnOnce() -> Result<R, Error> + Send + 'static,
+{
+    RfidReaderHandle::spawn(open, None)
+}
+
+/// Like [spawn](crate::spawn), but detects whether a card lies on the reader.
+///
+/// Readers read a card repeatedly as long as it lies on the reader. Instead of every read, the
+/// [RfidEvent::CardPresent](crate::RfidEvent::CardPresent) event is delivered when a card is put on the reader and
+/// [RfidEvent::CardRemoved](crate::RfidEvent::CardRemoved) when the card was not read within the `absence_timeout`.
+/// The `absence_timeout` must be longer than the interval of the repeated reads of the reader.
+/// The removal is detected after a read returned, i.e. it may be delayed up to the timeout of the [RfidReader](crate::RfidReader).
+pub fn spawn_with_presence<R, F>(
+    open: F,
+    absence_timeout: Duration,
+) -> Result<RfidReaderHandle, Error>
+where
+    R: RfidReader + 'static,
+    F: FnOnce() -> Result<R, Error> + Send + 'static,
+{
+    RfidReaderHandle::spawn(open, Some(absence_timeout))
+}
+
+/// Reads the [RfidReader](crate::RfidReader) returned by `open` on a thread of its own and returns the read
+/// [RfidEvent](crate::RfidEvent)s as [Stream](futures_core::Stream).
+///
+/// Only available with the `async` feature. Like [spawn](crate::spawn), errors of `open` are returned immediately.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use rfid_reader::{ProductId, RfidEvent, VendorId};
+/// use tokio_stream::StreamExt;
+///
+/// # async fn run() {
+/// let timeout = Duration::from_secs(1);
+/// let mut rfid_stream = rfid_reader::spawn_stream(move || {
+///     rfid_reader::open(VendorId::from(0x16c0), ProductId::from(0x27db), timeout)
+/// })
+/// .unwrap();
+///
+/// while let Some(event) = rfid_stream.next().await {
+///     if let RfidEvent::Read(rfid_value) = event {
+///         println!("Read {}", rfid_value);
+///     }
+/// }
+/// rfid_stream.deinitialize().await.unwrap();
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub fn spawn_stream<R, F>(open: F) -> Result<RfidStream, Error>
+where
+    R: RfidReader + 'static,
+    F: FnOnce() -> Result<R, Error> + Send + 'static,
+{
+    RfidStream::spawn(open)
+}
@@ -0,0 +1,101 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::rfidevent::RfidEvent;
+use rfid_value::RfidValue;
+
+use std::time::{Duration, Instant};
+
+/// Detects whether a card lies on the reader, which reads the card repeatedly as long as it is present.
+///
+/// A card is removed if it was not read within the absence timeout.
+pub(crate) struct PresenceDetector {
+    absence_timeout: Duration,
+    card: Option<(RfidValue, Instant)>,
+}
+
+impl PresenceDetector {
+    pub(crate) fn new(absence_timeout: Duration) -> Self {
+        PresenceDetector {
+            absence_timeout,
+            card: None,
+        }
+    }
+
+    /// Returns the events of reading the `rfid_value` at `now`, which are none if the card is already present.
+    pub(crate) fn read(&mut self, rfid_value: RfidValue, now: Instant) -> Vec<RfidEvent> {
+        let mut events = Vec::with_capacity(2);
+        match self.card.take() {
+            Some((card, _)) if card == rfid_value => {}
+            Some((card, _)) => {
+                events.push(RfidEvent::CardRemoved(card));
+                events.push(RfidEvent::CardPresent(rfid_value));
+            }
+            None => events.push(RfidEvent::CardPresent(rfid_value)),
+        }
+        self.card = Some((rfid_value, now));
+        events
+    }
+
+    /// Returns [RfidEvent::CardRemoved](crate::RfidEvent::CardRemoved) if the present card was not read within the absence timeout.
+    pub(crate) fn check(&mut self, now: Instant) -> Option<RfidEvent> {
+        match &self.card {
+            Some((_, last_read)) if now.duration_since(*last_read) >= self.absence_timeout => self
+                .card
+                .take()
+                .map(|(card, _)| RfidEvent::CardRemoved(card)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_and_removed() {
+        let mut presence_detector = PresenceDetector::new(Duration::from_millis(500));
+        let now = Instant::now();
+        assert_eq!(
+            vec![RfidEvent::CardPresent(RfidValue::from(1))],
+            presence_detector.read(RfidValue::from(1), now)
+        );
+        // repeated reads keep the card present
+        let later = now + Duration::from_millis(400);
+        assert!(presence_detector.read(RfidValue::from(1), later).is_empty());
+        assert_eq!(
+            None,
+            presence_detector.check(now + Duration::from_millis(800))
+        );
+
+        assert_eq!(
+            Some(RfidEvent::CardRemoved(RfidValue::from(1))),
+            presence_detector.check(later + Duration::from_millis(500))
+        );
+        assert_eq!(
+            None,
+            presence_detector.check(later + Duration::from_secs(1))
+        );
+        assert_eq!(
+            vec![RfidEvent::CardPresent(RfidValue::from(1))],
+            presence_detector.read(RfidValue::from(1), later + Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_other_card() {
+        let mut presence_detector = PresenceDetector::new(Duration::from_millis(500));
+        let now = Instant::now();
+        presence_detector.read(RfidValue::from(1), now);
+        assert_eq!(
+            vec![
+                RfidEvent::CardRemoved(RfidValue::from(1)),
+                RfidEvent::CardPresent(RfidValue::from(2))
+            ],
+            presence_detector.read(RfidValue::from(2), now)
+        );
+    }
+}
@@ -0,0 +1,258 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::humbleusbdevice::HumbleUsbDevice;
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+/// Reopens and re-initializes the [HumbleUsbDevice](crate::humbleusbdevice::HumbleUsbDevice) after it was disconnected.
+///
+/// While the device is disconnected, reading waits for the timeout and returns [Error::Disconnected](crate::Error::Disconnected).
+struct ReconnectingHumbleUsbDevice<D, F>
+where
+    D: HumbleUsbDevice,
+    F: Fn() -> Result<D, Error>,
+{
+    device: RefCell<Option<D>>,
+    open: F,
+    timeout: Duration,
+    deinitialized: bool,
+}
+
+impl<D, F> ReconnectingHumbleUsbDevice<D, F>
+where
+    D: HumbleUsbDevice,
+    F: Fn() -> Result<D, Error>,
+{
+    /// Opens and initializes the device again, returns `None` if it is still disconnected.
+    fn reopen(&self) -> Option<D> {
+        let mut device = (self.open)().ok()?;
+        device.initialize().ok()?;
+        Some(device)
+    }
+
+    fn with_device<T>(&self, f: impl FnOnce(&D) -> Result<T, Error>) -> Result<T, Error> {
+        match self.device.borrow().as_ref() {
+            Some(device) => f(device),
+            None => Err(Error::Disconnected),
+        }
+    }
+
+    fn with_device_mut<T>(
+        &mut self,
+        f: impl FnOnce(&mut D) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        match self.device.get_mut() {
+            Some(device) => f(device),
+            None => Err(Error::Disconnected),
+        }
+    }
+}
+
+impl<D, F> HumbleUsbDevice for ReconnectingHumbleUsbDevice<D, F>
+where
+    D: HumbleUsbDevice,
+    F: Fn() -> Result<D, Error>,
+{
+    fn has_attached_kernel_driver(&self) -> Result<bool, Error> {
+        self.with_device(|device| device.has_attached_kernel_driver())
+    }
+    fn detach_kernel_driver(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.detach_kernel_driver())
+    }
+    fn attach_kernel_driver(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.attach_kernel_driver())
+    }
+    fn read(&self, buffer: &mut [u8]) -> Result<(), Error> {
+        let mut device = self.device.borrow_mut();
+        if device.is_none() {
+            *device = self.reopen();
+        }
+        let result = match device.as_ref() {
+            Some(device) => device.read(buffer),
+            None => {
+                // waits like a read of a connected device, which receives no data
+                std::thread::sleep(self.timeout);
+                return Err(Error::Disconnected);
+            }
+        };
+        if result == Err(Error::Disconnected) {
+            if let Some(mut disconnected_device) = device.take() {
+                // a disconnected device cannot be de-initialized anymore
+                disconnected_device.set_deinitialized();
+            }
+        }
+        result
+    }
+    fn claim_interface(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.claim_interface())
+    }
+    fn release_interface(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.release_interface())
+    }
+    fn set_active_configuration(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.set_active_configuration())
+    }
+    fn set_alternate_setting(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.set_alternate_setting())
+    }
+    fn initialize(&mut self) -> Result<(), Error> {
+        self.with_device_mut(|device| device.initialize())
+    }
+    fn set_deinitialized(&mut self) {
+        self.deinitialized = true;
+    }
+    fn deinitialized(&self) -> bool {
+        self.deinitialized
+    }
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        self.set_deinitialized();
+        match self.device.get_mut() {
+            Some(device) => device.deinitialize(),
+            // nothing to de-initialize
+            None => Ok(()),
+        }
+    }
+}
+
+/// Wraps the connected `device`, which is reopened with `open` after it was disconnected.
+///
+/// The `timeout` is waited by each read while the device is disconnected.
+pub(crate) fn from<D, F>(device: D, open: F, timeout: Duration) -> impl HumbleUsbDevice
+where
+    D: HumbleUsbDevice,
+    F: Fn() -> Result<D, Error>,
+{
+    ReconnectingHumbleUsbDevice {
+        device: RefCell::new(Some(device)),
+        open,
+        timeout,
+        deinitialized: false,
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Reads successfully until it is disconnected.
+    struct MockHumbleUsbDevice {
+        connected: Rc<Cell<bool>>,
+        initialized: bool,
+        deinitialized: bool,
+    }
+
+    impl MockHumbleUsbDevice {
+        fn new(connected: &Rc<Cell<bool>>) -> Self {
+            MockHumbleUsbDevice {
+                connected: connected.clone(),
+                initialized: false,
+                deinitialized: false,
+            }
+        }
+    }
+
+    impl HumbleUsbDevice for MockHumbleUsbDevice {
+        fn has_attached_kernel_driver(&self) -> Result<bool, Error> {
+            Ok(true)
+        }
+        fn detach_kernel_driver(&mut self) -> Result<(), Error> {
+            self.initialized = true;
+            Ok(())
+        }
+        fn attach_kernel_driver(&mut self) -> Result<(), Error> {
+            self.initialized = false;
+            Ok(())
+        }
+        fn read(&self, _buffer: &mut [u8]) -> Result<(), Error> {
+            assert!(self.initialized);
+            if self.connected.get() {
+                Ok(())
+            } else {
+                Err(Error::Disconnected)
+            }
+        }
+        fn claim_interface(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn release_interface(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_active_configuration(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_alternate_setting(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn set_deinitialized(&mut self) {
+            self.deinitialized = true;
+        }
+        fn deinitialized(&self) -> bool {
+            self.deinitialized
+        }
+    }
+
+    #[test]
+    fn test_reconnect() {
+        let connected = Rc::new(Cell::new(true));
+        let opened = Rc::new(Cell::new(0));
+        let open_connected = connected.clone();
+        let open_opened = opened.clone();
+        let mut device = ReconnectingHumbleUsbDevice {
+            device: RefCell::new(Some(MockHumbleUsbDevice::new(&connected))),
+            open: move || {
+                open_opened.set(open_opened.get() + 1);
+                if open_connected.get() {
+                    Ok(MockHumbleUsbDevice::new(&open_connected))
+                } else {
+                    Err(Error::OtherUsbError("Not found".to_string()))
+                }
+            },
+            timeout: Duration::from_millis(1),
+            deinitialized: false,
+        };
+        device.initialize().unwrap();
+        let mut buffer = [0; 3];
+        assert_eq!(Ok(()), device.read(&mut buffer));
+
+        connected.set(false);
+        assert_eq!(Err(Error::Disconnected), device.read(&mut buffer));
+        assert!(device.device.borrow().is_none());
+        assert_eq!(Err(Error::Disconnected), device.read(&mut buffer));
+        assert_eq!(1, opened.get());
+
+        // the reopened device is initialized before reading
+        connected.set(true);
+        assert_eq!(Ok(()), device.read(&mut buffer));
+        assert_eq!(2, opened.get());
+
+        device.deinitialize().unwrap();
+        assert!(device.deinitialized());
+        assert!(!device.device.borrow().as_ref().unwrap().initialized);
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let connected = Rc::new(Cell::new(false));
+        let mut device = from(
+            MockHumbleUsbDevice::new(&connected),
+            || Err(Error::OtherUsbError("Not found".to_string())),
+            Duration::from_millis(1),
+        );
+        device.initialize().unwrap();
+        assert_eq!(Err(Error::Disconnected), device.read(&mut [0; 3]));
+        assert_eq!(
+            Err(Error::Disconnected),
+            device.has_attached_kernel_driver()
+        );
+        assert_eq!(Err(Error::Disconnected), device.claim_interface());
+        assert_eq!(Ok(()), device.deinitialize());
+    }
+}
@@ -0,0 +1,31 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use rfid_value::RfidValue;
+
+/// Events delivered by an [RfidReaderHandle](crate::RfidReaderHandle).
+#[derive(Debug, PartialEq)]
+pub enum RfidEvent {
+    /// An RFID value was read.
+    Read(RfidValue),
+    /// A card was put on the reader, only delivered if the presence of cards is detected.
+    ///
+    /// The [RfidEvent::Read](crate::RfidEvent::Read) events are replaced by this event then.
+    CardPresent(RfidValue),
+    /// The card was removed from the reader, i.e. it was not read within the absence timeout.
+    CardRemoved(RfidValue),
+    /// Reading failed, the reader thread stops after this event.
+    ///
+    /// Timeouts of the reader are not delivered.
+    Error(Error),
+    /// The USB device of the reader was disconnected, e.g. unplugged.
+    ///
+    /// Reading goes on as soon as the device is connected again.
+    Disconnected,
+    /// The USB device of the reader was connected again after it was [Disconnected](crate::RfidEvent::Disconnected).
+    Reconnected,
+    /// The reader was cancelled, e.g. by a [Canceller](crate::Canceller), no more values are read.
+    Cancelled,
+}
@@ -5,28 +5,40 @@
 use crate::error::Error;
 use crate::keymap::{Key, KeyMap};
 use crate::usbreader::UsbReader;
+use rfid_value::RfidValue;
 
 /// Trait defining an [RfidReader](crate::RfidReader).
 ///
-/// An [RfidReader](crate::RfidReader) will read from an USB Rfid Reader device and convert the raw data into a string but may fail reading.
+/// An [RfidReader](crate::RfidReader) will read from an USB Rfid Reader device and convert the raw data into an [RfidValue](crate::RfidValue) but may fail reading.
 ///
 /// It also makes sense to call [`deinitialize`](crate::RfidReader::deinitialize) when the RfidReader is not used anymore in order to de-initialize the
 /// used USB device. This makes sense since de-initialize may fail. However, Drop is implemented to de-initialize as well.
 pub trait RfidReader: std::fmt::Debug {
     /// Tries to read from the RfidReader.
     ///
-    /// A String of the processed raw data is returned on success. Otherwise an error is returned.
+    /// The [RfidValue](crate::RfidValue) of the processed raw data is returned on success. Otherwise an error is returned.
     /// Method is blocking, this means it will try to read data, until a valid RFID was read or an error has occurred.
     ///
     /// The error [`Timeout`](crate::Error::Timeout) will occur after the defined timeout expired.
     /// One can simply call this method again, since it is not a fatal error. However as mentioned above, the call to this method
     /// is blocking. This means when the [`Timeout`](crate::Error::Timeout) occurs, one can do something different like checking
     /// for OS signals that may signal that the application has to be terminated.
-    fn read(&self) -> Result<String, Error>;
+    /// Use [spawn](crate::spawn) in order to not block while reading.
+    fn read(&self) -> Result<RfidValue, Error>;
     /// Tries to de-initialize the USB device of the RfidReader, which may fail.
     fn deinitialize(&mut self) -> Result<(), Error>;
 }
 
+/// Allows choosing the [RfidReader](crate::RfidReader) at runtime, e.g. depending on the configured device.
+impl<R: RfidReader + ?Sized> RfidReader for Box<R> {
+    fn read(&self) -> Result<RfidValue, Error> {
+        (**self).read()
+    }
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        (**self).deinitialize()
+    }
+}
+
 struct GenericRfidReader<K: KeyMap, U: UsbReader> {
     key_map: K,
     usb_reader: U,
@@ -51,7 +63,7 @@ impl<K: KeyMap, U: UsbReader> std::fmt::Debug for GenericRfidReader<K, U> {
 }
 
 impl<K: KeyMap, U: UsbReader> RfidReader for GenericRfidReader<K, U> {
-    fn read(&self) -> Result<String, Error> {
+    fn read(&self) -> Result<RfidValue, Error> {
         let raw_data = self.usb_reader.read()?;
         let mut rfid_value = String::with_capacity(10);
         for raw_value in raw_data.iter() {
@@ -63,7 +75,7 @@ impl<K: KeyMap, U: UsbReader> RfidReader for GenericRfidReader<K, U> {
                 }
             }
         }
-        Ok(rfid_value)
+        self.key_map.parse(&rfid_value)
     }
     fn deinitialize(&mut self) -> Result<(), Error> {
         self.usb_reader.deinitialize()
@@ -119,7 +131,35 @@ mod tests {
         let key_map = MockKeyMap;
         let mut rfid_reader = from(key_map, usb_reader);
         let rfid = rfid_reader.read().unwrap();
-        assert_eq!("0123456789", rfid);
+        assert_eq!(RfidValue::from(123_456_789), rfid);
+        assert_eq!(Ok(()), rfid_reader.deinitialize());
+    }
+
+    #[derive(Debug)]
+    struct EmptyUsbReader;
+
+    impl UsbReader for EmptyUsbReader {
+        fn read(&self) -> Result<Box<[u8]>, Error> {
+            Ok(Vec::new().into_boxed_slice())
+        }
+        fn deinitialize(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_read_invalid_value() {
+        let rfid_reader = from(MockKeyMap, EmptyUsbReader);
+        assert_eq!(
+            Err(Error::InvalidRfidValue("".to_string())),
+            rfid_reader.read()
+        );
+    }
+
+    #[test]
+    fn test_boxed_read() {
+        let mut rfid_reader: Box<dyn RfidReader> = Box::new(from(MockKeyMap, MockUsbReader));
+        assert_eq!(Ok(RfidValue::from(123_456_789)), rfid_reader.read());
         assert_eq!(Ok(()), rfid_reader.deinitialize());
     }
 
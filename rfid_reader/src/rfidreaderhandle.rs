@@ -0,0 +1,407 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::canceller::Canceller;
+use crate::error::Error;
+use crate::presencedetector::PresenceDetector;
+use crate::rfidevent::RfidEvent;
+use crate::rfidreader::RfidReader;
+
+use log::warn;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, sync_channel, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Number of events that are buffered until the reader thread waits for the receiver.
+pub(crate) const EVENT_CAPACITY: usize = 16;
+
+/// Handle of an [RfidReader](crate::RfidReader) that is read on its own thread.
+///
+/// The read RFID values are delivered as [RfidEvent](crate::RfidEvent)s, which are received without blocking
+/// with [try_recv](crate::RfidReaderHandle::try_recv) or waiting at most a given time with
+/// [recv_timeout](crate::RfidReaderHandle::recv_timeout).
+///
+/// The [RfidReader](crate::RfidReader) is de-initialized by [deinitialize](crate::RfidReaderHandle::deinitialize)
+/// or on drop.
+pub struct RfidReaderHandle {
+    receiver: Receiver<RfidEvent>,
+    canceller: Canceller,
+    thread: Option<JoinHandle<Result<(), Error>>>,
+    /// Set when the reader thread ended, also if it panicked.
+    finished: Arc<AtomicBool>,
+}
+
+/// Sets the flag when dropped, i.e. at the end of the reader thread, even if it panicked.
+struct FinishedGuard(Arc<AtomicBool>);
+
+impl Drop for FinishedGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+impl std::fmt::Debug for RfidReaderHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RfidReaderHandle")
+            .field("cancelled", &self.canceller.is_cancelled())
+            .finish()
+    }
+}
+
+/// Reads until cancelled or an error occurred, then de-initializes the reader.
+///
+/// A disconnected device is no error, since it is reopened when it is connected again.
+/// Neither is an invalid value, e.g. of a card that was moved while it was read, which is skipped with a warning.
+///
+/// The events are passed to `send`, which returns `false` if the receiver was dropped.
+/// With an `absence_timeout`, the presence of cards is detected, see [PresenceDetector](crate::presencedetector::PresenceDetector).
+pub(crate) fn read_until_cancelled<R: RfidReader>(
+    mut rfid_reader: R,
+    running: &AtomicBool,
+    absence_timeout: Option<Duration>,
+    mut send: impl FnMut(RfidEvent) -> bool,
+) -> Result<(), Error> {
+    let mut presence_detector = absence_timeout.map(PresenceDetector::new);
+    let mut disconnected = false;
+    while running.load(Ordering::SeqCst) {
+        let result = rfid_reader.read();
+        // only changes of the connection are delivered
+        if (result == Err(Error::Disconnected)) != disconnected {
+            disconnected = !disconnected;
+            let event = if disconnected {
+                RfidEvent::Disconnected
+            } else {
+                RfidEvent::Reconnected
+            };
+            if !send(event) {
+                break;
+            }
+        }
+        let mut events = match result {
+            Ok(rfid_value) => match &mut presence_detector {
+                Some(presence_detector) => presence_detector.read(rfid_value, Instant::now()),
+                None => vec![RfidEvent::Read(rfid_value)],
+            },
+            Err(Error::Timeout) | Err(Error::Disconnected) => Vec::new(),
+            Err(Error::InvalidRfidValue(value)) => {
+                warn!("Skipping the invalid RFID value {:?}", value);
+                Vec::new()
+            }
+            Err(error) => {
+                send(RfidEvent::Error(error));
+                break;
+            }
+        };
+        if let Some(presence_detector) = &mut presence_detector {
+            events.extend(presence_detector.check(Instant::now()));
+        }
+        if !events.into_iter().all(&mut send) {
+            break;
+        }
+    }
+    rfid_reader.deinitialize()
+}
+
+impl RfidReaderHandle {
+    pub(crate) fn spawn<R, F>(open: F, absence_timeout: Option<Duration>) -> Result<Self, Error>
+    where
+        R: RfidReader + 'static,
+        F: FnOnce() -> Result<R, Error> + Send + 'static,
+    {
+        let running = Arc::new(AtomicBool::new(true));
+        let (sender, receiver) = sync_channel(EVENT_CAPACITY);
+        let (opened_sender, opened_receiver) = channel();
+        let canceller = Canceller::new(running.clone(), sender.clone());
+        let finished = Arc::new(AtomicBool::new(false));
+        let finished_guard = FinishedGuard(finished.clone());
+        let thread = std::thread::spawn(move || {
+            let _finished_guard = finished_guard;
+            match open() {
+                Ok(rfid_reader) => {
+                    opened_sender.send(Ok(())).ok();
+                    read_until_cancelled(rfid_reader, &running, absence_timeout, |event| {
+                        sender.send(event).is_ok()
+                    })
+                }
+                Err(error) => {
+                    opened_sender.send(Err(error)).ok();
+                    Ok(())
+                }
+            }
+        });
+        // the thread ended without sending the result only if opening panicked
+        opened_receiver
+            .recv()
+            .unwrap_or(Err(Error::ReaderStopped))?;
+        Ok(RfidReaderHandle {
+            receiver,
+            canceller,
+            thread: Some(thread),
+            finished,
+        })
+    }
+
+    /// Returns the next [RfidEvent](crate::RfidEvent) without blocking.
+    ///
+    /// Returns [Error::Timeout](crate::Error::Timeout) if there is no event yet and
+    /// [Error::ReaderStopped](crate::Error::ReaderStopped) if the reader thread has stopped and all events were received.
+    pub fn try_recv(&self) -> Result<RfidEvent, Error> {
+        self.recv_timeout(Duration::from_secs(0))
+    }
+
+    /// Waits at most `timeout` for the next [RfidEvent](crate::RfidEvent).
+    ///
+    /// Returns [Error::Timeout](crate::Error::Timeout) if there is no event within the timeout and
+    /// [Error::ReaderStopped](crate::Error::ReaderStopped) if the reader thread has stopped and all events were received.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<RfidEvent, Error> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => Ok(event),
+            Err(RecvTimeoutError::Disconnected) => Err(Error::ReaderStopped),
+            Err(RecvTimeoutError::Timeout) => {
+                // the channel stays connected as long as a canceller exists
+                if self.is_stopped() {
+                    self.receiver.try_recv().map_err(|_| Error::ReaderStopped)
+                } else {
+                    Err(Error::Timeout)
+                }
+            }
+        }
+    }
+
+    /// Returns a [Canceller](crate::Canceller), which cancels reading from another thread.
+    pub fn canceller(&self) -> Canceller {
+        self.canceller.clone()
+    }
+
+    /// Cancels reading, see [Canceller::cancel](crate::Canceller::cancel).
+    pub fn cancel(&self) {
+        self.canceller.cancel()
+    }
+
+    /// Cancels reading, waits until the reader thread stopped and returns the result of de-initializing the
+    /// [RfidReader](crate::RfidReader).
+    ///
+    /// Waits at most the timeout of the [RfidReader](crate::RfidReader). Events that were not received yet are dropped.
+    pub fn deinitialize(&mut self) -> Result<(), Error> {
+        self.cancel();
+        match self.thread.take() {
+            Some(thread) => {
+                // frees the reader thread, if it waits for the receiver
+                while self.receiver.try_recv().is_ok() {}
+                thread.join().unwrap_or(Err(Error::ReaderStopped))
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.thread.is_none() || self.finished.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for RfidReaderHandle {
+    fn drop(&mut self) {
+        self.deinitialize().ok();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use rfid_value::RfidValue;
+
+    use std::cell::Cell;
+    use std::sync::Mutex;
+
+    /// Reads the values one after another (`None` fails, `Some("")` is disconnected), then times out after a short wait.
+    #[derive(Debug)]
+    struct MockRfidReader {
+        values: Vec<Option<&'static str>>,
+        index: Cell<usize>,
+        deinitialized: Arc<Mutex<bool>>,
+    }
+
+    impl MockRfidReader {
+        fn from(values: Vec<Option<&'static str>>) -> Self {
+            MockRfidReader {
+                values,
+                index: Cell::new(0),
+                deinitialized: Arc::new(Mutex::new(false)),
+            }
+        }
+    }
+
+    impl RfidReader for MockRfidReader {
+        fn read(&self) -> Result<RfidValue, Error> {
+            let index = self.index.get();
+            self.index.set(index + 1);
+            match self.values.get(index) {
+                Some(Some("")) => Err(Error::Disconnected),
+                Some(Some(value)) => Ok(value.parse()?),
+                Some(None) => Err(Error::OtherUsbError("read".to_string())),
+                None => {
+                    std::thread::sleep(Duration::from_millis(10));
+                    Err(Error::Timeout)
+                }
+            }
+        }
+        fn deinitialize(&mut self) -> Result<(), Error> {
+            *self.deinitialized.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_read_events() {
+        let rfid_reader = MockRfidReader::from(vec![Some("1"), Some("2")]);
+        let deinitialized = rfid_reader.deinitialized.clone();
+        let mut handle = RfidReaderHandle::spawn(move || Ok(rfid_reader), None).unwrap();
+
+        let timeout = Duration::from_secs(1);
+        assert_eq!(
+            Ok(RfidEvent::Read(RfidValue::from(1))),
+            handle.recv_timeout(timeout)
+        );
+        assert_eq!(
+            Ok(RfidEvent::Read(RfidValue::from(2))),
+            handle.recv_timeout(timeout)
+        );
+        // timeouts of the reader are not delivered
+        assert_eq!(Err(Error::Timeout), handle.try_recv());
+
+        assert_eq!(Ok(()), handle.deinitialize());
+        assert!(*deinitialized.lock().unwrap());
+        assert_eq!(Ok(()), handle.deinitialize());
+    }
+
+    #[test]
+    fn test_invalid_value_is_skipped() {
+        let rfid_reader = MockRfidReader::from(vec![Some("card"), Some("1")]);
+        let mut handle = RfidReaderHandle::spawn(move || Ok(rfid_reader), None).unwrap();
+
+        assert_eq!(
+            Ok(RfidEvent::Read(RfidValue::from(1))),
+            handle.recv_timeout(Duration::from_secs(1))
+        );
+        assert_eq!(Ok(()), handle.deinitialize());
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let rfid_reader = MockRfidReader::from(vec![Some(""), Some(""), Some("1"), Some("")]);
+        let mut handle = RfidReaderHandle::spawn(move || Ok(rfid_reader), None).unwrap();
+
+        let timeout = Duration::from_secs(1);
+        assert_eq!(Ok(RfidEvent::Disconnected), handle.recv_timeout(timeout));
+        assert_eq!(Ok(RfidEvent::Reconnected), handle.recv_timeout(timeout));
+        assert_eq!(
+            Ok(RfidEvent::Read(RfidValue::from(1))),
+            handle.recv_timeout(timeout)
+        );
+        assert_eq!(Ok(RfidEvent::Disconnected), handle.recv_timeout(timeout));
+        // the reader times out after the last value, i.e. it is connected again
+        assert_eq!(Ok(RfidEvent::Reconnected), handle.recv_timeout(timeout));
+        assert_eq!(Ok(()), handle.deinitialize());
+    }
+
+    #[test]
+    fn test_presence() {
+        let rfid_reader = MockRfidReader::from(vec![Some("1"), Some("1"), Some("2")]);
+        let handle =
+            RfidReaderHandle::spawn(move || Ok(rfid_reader), Some(Duration::from_millis(50)))
+                .unwrap();
+
+        let timeout = Duration::from_secs(1);
+        assert_eq!(
+            Ok(RfidEvent::CardPresent(RfidValue::from(1))),
+            handle.recv_timeout(timeout)
+        );
+        assert_eq!(
+            Ok(RfidEvent::CardRemoved(RfidValue::from(1))),
+            handle.recv_timeout(timeout)
+        );
+        assert_eq!(
+            Ok(RfidEvent::CardPresent(RfidValue::from(2))),
+            handle.recv_timeout(timeout)
+        );
+        // the reader times out after the last value
+        assert_eq!(
+            Ok(RfidEvent::CardRemoved(RfidValue::from(2))),
+            handle.recv_timeout(timeout)
+        );
+    }
+
+    #[test]
+    fn test_error_stops_reader() {
+        let rfid_reader = MockRfidReader::from(vec![None, Some("1")]);
+        let deinitialized = rfid_reader.deinitialized.clone();
+        let handle = RfidReaderHandle::spawn(move || Ok(rfid_reader), None).unwrap();
+
+        let timeout = Duration::from_secs(1);
+        assert_eq!(
+            Ok(RfidEvent::Error(Error::OtherUsbError("read".to_string()))),
+            handle.recv_timeout(timeout)
+        );
+        // the reader is de-initialized after the error and no further values are read
+        assert_eq!(Err(Error::ReaderStopped), handle.recv_timeout(timeout));
+        assert!(*deinitialized.lock().unwrap());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let rfid_reader = MockRfidReader::from(Vec::new());
+        let deinitialized = rfid_reader.deinitialized.clone();
+        let mut handle = RfidReaderHandle::spawn(move || Ok(rfid_reader), None).unwrap();
+        let canceller = handle.canceller();
+
+        let cancelling_thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+        // a long wait is cancelled immediately
+        assert_eq!(
+            Ok(RfidEvent::Cancelled),
+            handle.recv_timeout(Duration::from_secs(60))
+        );
+        cancelling_thread.join().unwrap();
+
+        assert_eq!(Ok(()), handle.deinitialize());
+        assert!(*deinitialized.lock().unwrap());
+        assert_eq!(Err(Error::ReaderStopped), handle.try_recv());
+    }
+
+    #[test]
+    fn test_open_error() {
+        let result = RfidReaderHandle::spawn(
+            || -> Result<MockRfidReader, Error> { Err(Error::Access) },
+            None,
+        );
+        assert_eq!(Error::Access, result.unwrap_err());
+    }
+
+    #[test]
+    fn test_drop_with_full_channel() {
+        let rfid_reader = MockRfidReader::from(vec![Some("1"); EVENT_CAPACITY * 2]);
+        let deinitialized = rfid_reader.deinitialized.clone();
+        let handle = RfidReaderHandle::spawn(move || Ok(rfid_reader), None).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+
+        drop(handle);
+        assert!(*deinitialized.lock().unwrap());
+    }
+
+    #[test]
+    fn test_debug() {
+        let handle =
+            RfidReaderHandle::spawn(|| Ok(MockRfidReader::from(Vec::new())), None).unwrap();
+        assert_eq!(
+            "RfidReaderHandle { cancelled: false }",
+            format!("{:?}", handle)
+        );
+    }
+}
@@ -0,0 +1,174 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::rfidevent::RfidEvent;
+use crate::rfidreader::RfidReader;
+use crate::rfidreaderhandle::{read_until_cancelled, EVENT_CAPACITY};
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::sync::{mpsc, oneshot};
+
+/// [Stream](futures_core::Stream) of the [RfidEvent](crate::RfidEvent)s of an [RfidReader](crate::RfidReader),
+/// which is read on a thread of its own.
+///
+/// The stream ends after an [RfidEvent::Error](crate::RfidEvent::Error) or when it was cancelled.
+/// Dropping the stream stops reading as well, the [RfidReader](crate::RfidReader) is de-initialized after the current read.
+#[derive(Debug)]
+pub struct RfidStream {
+    receiver: mpsc::Receiver<RfidEvent>,
+    running: Arc<AtomicBool>,
+    deinitialized: oneshot::Receiver<Result<(), Error>>,
+}
+
+impl RfidStream {
+    pub(crate) fn spawn<R, F>(open: F) -> Result<Self, Error>
+    where
+        R: RfidReader + 'static,
+        F: FnOnce() -> Result<R, Error> + Send + 'static,
+    {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let (sender, receiver) = mpsc::channel(EVENT_CAPACITY);
+        let (opened_sender, opened_receiver) = std::sync::mpsc::channel();
+        let (deinitialized_sender, deinitialized) = oneshot::channel();
+        std::thread::spawn(move || match open() {
+            Ok(rfid_reader) => {
+                opened_sender.send(Ok(())).ok();
+                let result = read_until_cancelled(rfid_reader, &thread_running, None, |event| {
+                    sender.blocking_send(event).is_ok()
+                });
+                deinitialized_sender.send(result).ok();
+            }
+            Err(error) => {
+                opened_sender.send(Err(error)).ok();
+            }
+        });
+        // the thread ended without sending the result only if opening panicked
+        opened_receiver
+            .recv()
+            .unwrap_or(Err(Error::ReaderStopped))?;
+        Ok(RfidStream {
+            receiver,
+            running,
+            deinitialized,
+        })
+    }
+
+    /// Cancels reading, the stream ends immediately after the already received events.
+    pub fn cancel(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.receiver.close();
+    }
+
+    /// Cancels reading and waits until the [RfidReader](crate::RfidReader) is de-initialized, which returns its result.
+    ///
+    /// Waits at most the timeout of the [RfidReader](crate::RfidReader).
+    pub async fn deinitialize(mut self) -> Result<(), Error> {
+        self.cancel();
+        (&mut self.deinitialized)
+            .await
+            .unwrap_or(Err(Error::ReaderStopped))
+    }
+}
+
+impl Stream for RfidStream {
+    type Item = RfidEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<RfidEvent>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for RfidStream {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use rfid_value::RfidValue;
+
+    use std::time::Duration;
+    use tokio_stream::StreamExt;
+
+    #[derive(Debug)]
+    struct MockRfidReader {
+        values: std::cell::RefCell<Vec<Result<RfidValue, Error>>>,
+    }
+
+    impl RfidReader for MockRfidReader {
+        fn read(&self) -> Result<RfidValue, Error> {
+            self.values.borrow_mut().pop().unwrap_or_else(|| {
+                std::thread::sleep(Duration::from_millis(10));
+                Err(Error::Timeout)
+            })
+        }
+        fn deinitialize(&mut self) -> Result<(), Error> {
+            Err(Error::OtherUsbError("deinitialize".to_string()))
+        }
+    }
+
+    fn spawn(mut values: Vec<Result<RfidValue, Error>>) -> RfidStream {
+        // the mock pops the values from the back
+        values.reverse();
+        RfidStream::spawn(move || {
+            Ok(MockRfidReader {
+                values: std::cell::RefCell::new(values),
+            })
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream() {
+        let mut stream = spawn(vec![
+            Ok(RfidValue::from(1)),
+            Err(Error::Timeout),
+            Ok(RfidValue::from(2)),
+            Err(Error::Access),
+            Ok(RfidValue::from(3)),
+        ]);
+        assert_eq!(
+            Some(RfidEvent::Read(RfidValue::from(1))),
+            stream.next().await
+        );
+        assert_eq!(
+            Some(RfidEvent::Read(RfidValue::from(2))),
+            stream.next().await
+        );
+        assert_eq!(Some(RfidEvent::Error(Error::Access)), stream.next().await);
+        // the stream ends after an error
+        assert_eq!(None, stream.next().await);
+        assert_eq!(
+            Err(Error::OtherUsbError("deinitialize".to_string())),
+            stream.deinitialize().await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let mut stream = spawn(Vec::new());
+        stream.cancel();
+        assert_eq!(None, stream.next().await);
+        assert_eq!(
+            Err(Error::OtherUsbError("deinitialize".to_string())),
+            stream.deinitialize().await
+        );
+    }
+
+    #[test]
+    fn test_open_error() {
+        let result = RfidStream::spawn(|| -> Result<MockRfidReader, Error> { Err(Error::Access) });
+        assert_eq!(Error::Access, result.unwrap_err());
+    }
+}
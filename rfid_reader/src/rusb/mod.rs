@@ -5,4 +5,4 @@
 mod rusbhumbleusbdevice;
 mod utils;
 
-pub(crate) use rusbhumbleusbdevice::open;
+pub(crate) use rusbhumbleusbdevice::{list, open};
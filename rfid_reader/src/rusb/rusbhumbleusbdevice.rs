@@ -4,7 +4,9 @@
 
 use crate::humbleusbdevice::HumbleUsbDevice;
 use crate::id::{ProductId, VendorId};
-use crate::rusb::utils::{get_device, get_readable_endpoint, EndPoint};
+use crate::rusb::utils::{get_device, get_readable_endpoint, list_devices, EndPoint};
+use crate::usbdeviceinfo::UsbDeviceInfo;
+use crate::usbdeviceselector::UsbDeviceSelector;
 use crate::Error;
 
 use std::time::Duration;
@@ -74,7 +76,7 @@ impl<T: UsbContext> HumbleUsbDevice for RusbHumbleUsbDevice<T> {
     }
     #[cfg(not(tarpaulin_include))]
     fn set_deinitialized(&mut self) {
-        self.deinitialized = false;
+        self.deinitialized = true;
     }
     #[cfg(not(tarpaulin_include))]
     fn deinitialized(&self) -> bool {
@@ -85,10 +87,8 @@ impl<T: UsbContext> HumbleUsbDevice for RusbHumbleUsbDevice<T> {
 impl<T: UsbContext> Drop for RusbHumbleUsbDevice<T> {
     #[cfg(not(tarpaulin_include))]
     fn drop(&mut self) {
-        if !self.deinitialized() {
-            self.set_deinitialized();
-            self.deinitialize().unwrap();
-        }
+        // errors cannot be handled anymore, e.g. when the device was disconnected
+        self.deinitialize().ok();
     }
 }
 
@@ -96,10 +96,11 @@ impl<T: UsbContext> Drop for RusbHumbleUsbDevice<T> {
 pub(crate) fn open(
     vendor_id: VendorId,
     product_id: ProductId,
+    selector: &UsbDeviceSelector,
     timeout: Duration,
 ) -> Result<impl HumbleUsbDevice, Error> {
     let context = Context::new()?;
-    let (device, device_descriptor) = get_device(&context, vendor_id, product_id)?;
+    let (device, device_descriptor) = get_device(&context, vendor_id, product_id, selector)?;
     let endpoint =
         get_readable_endpoint(&device, &device_descriptor, rusb::TransferType::Interrupt)?;
     let device_handle = device.open()?;
@@ -110,3 +111,10 @@ pub(crate) fn open(
         deinitialized: false,
     })
 }
+
+/// Lists the USB devices attached to the system.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn list() -> Result<Vec<UsbDeviceInfo>, Error> {
+    let context = Context::new()?;
+    list_devices(&context)
+}
@@ -4,7 +4,9 @@
 
 use crate::error::Error;
 use crate::id::{ProductId, VendorId};
-use rusb::{Device, DeviceDescriptor, Direction, TransferType, UsbContext};
+use crate::usbdeviceinfo::UsbDeviceInfo;
+use crate::usbdeviceselector::UsbDeviceSelector;
+use rusb::{Device, DeviceDescriptor, DeviceHandle, Direction, TransferType, UsbContext};
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct EndPoint {
@@ -33,12 +35,14 @@ pub(crate) fn get_device<T: UsbContext>(
     context: &T,
     vendor_id: VendorId,
     product_id: ProductId,
+    selector: &UsbDeviceSelector,
 ) -> Result<(Device<T>, DeviceDescriptor), Error> {
     let devices = context.devices()?;
     for device in devices.iter() {
         let device_descriptor = device.device_descriptor()?;
-        if device_descriptor.vendor_id() == vendor_id.into()
-            && device_descriptor.product_id() == product_id.into()
+        if device_descriptor.vendor_id() == Into::<u16>::into(vendor_id)
+            && device_descriptor.product_id() == Into::<u16>::into(product_id)
+            && selector.matches(&get_device_info(&device, &device_descriptor))
         {
             return Ok((device, device_descriptor));
         }
@@ -79,11 +83,47 @@ pub(crate) fn get_readable_endpoint<T: UsbContext>(
     ))
 }
 
+pub(crate) fn get_device_info<T: UsbContext>(
+    device: &Device<T>,
+    device_descriptor: &DeviceDescriptor,
+) -> UsbDeviceInfo {
+    let readable =
+        get_readable_endpoint(device, device_descriptor, TransferType::Interrupt).is_ok();
+    // the strings can only be read if the device can be opened, e.g. with sufficient rights
+    let device_handle = device.open().ok();
+    let read_string = |read: fn(&DeviceHandle<T>, &DeviceDescriptor) -> rusb::Result<String>| {
+        device_handle
+            .as_ref()
+            .and_then(|device_handle| read(device_handle, device_descriptor).ok())
+    };
+    UsbDeviceInfo {
+        vendor_id: VendorId::from(device_descriptor.vendor_id()),
+        product_id: ProductId::from(device_descriptor.product_id()),
+        bus_number: device.bus_number(),
+        address: device.address(),
+        port_numbers: device.port_numbers().unwrap_or_default(),
+        manufacturer: read_string(DeviceHandle::read_manufacturer_string_ascii),
+        product: read_string(DeviceHandle::read_product_string_ascii),
+        serial_number: read_string(DeviceHandle::read_serial_number_string_ascii),
+        readable,
+    }
+}
+
+pub(crate) fn list_devices<T: UsbContext>(context: &T) -> Result<Vec<UsbDeviceInfo>, Error> {
+    let mut device_infos = Vec::new();
+    for device in context.devices()?.iter() {
+        let device_descriptor = device.device_descriptor()?;
+        device_infos.push(get_device_info(&device, &device_descriptor));
+    }
+    Ok(device_infos)
+}
+
 impl From<rusb::Error> for Error {
     fn from(error: rusb::Error) -> Self {
         match error {
             rusb::Error::Timeout => Error::Timeout,
             rusb::Error::Access => Error::Access,
+            rusb::Error::NoDevice => Error::Disconnected,
             _ => Error::OtherUsbError(error.to_string()),
         }
     }
@@ -96,7 +136,12 @@ mod tests {
     #[test]
     fn test_get_device_not_found() {
         let context = rusb::Context::new().unwrap();
-        let device = get_device(&context, VendorId::from(0), ProductId::from(0));
+        let device = get_device(
+            &context,
+            VendorId::from(0),
+            ProductId::from(0),
+            &UsbDeviceSelector::Any,
+        );
         assert!(device.is_err());
     }
 
@@ -107,7 +152,7 @@ mod tests {
         let product_id = ProductId::from(0x3005);
 
         let context = rusb::Context::new().unwrap();
-        let device = get_device(&context, vendor_id, product_id);
+        let device = get_device(&context, vendor_id, product_id, &UsbDeviceSelector::Any);
 
         assert!(device.is_ok());
     }
@@ -119,7 +164,7 @@ mod tests {
         // readable device in my system, change these two values in your system
         let vendor_id = VendorId::from(0x0cf3);
         let product_id = ProductId::from(0x3005);
-        let result = get_device(&context, vendor_id, product_id);
+        let result = get_device(&context, vendor_id, product_id, &UsbDeviceSelector::Any);
         assert!(result.is_ok());
         let (device, device_descriptor) = result.unwrap();
         let endpoint = get_readable_endpoint(&device, &device_descriptor, TransferType::Interrupt);
@@ -142,7 +187,7 @@ mod tests {
         // device in my system without readable bulk endpoint, change these two values in your system
         let vendor_id = VendorId::from(0x058f);
         let product_id = ProductId::from(0xa004);
-        let result = get_device(&context, vendor_id, product_id);
+        let result = get_device(&context, vendor_id, product_id, &UsbDeviceSelector::Any);
         assert!(result.is_ok());
         let (device, device_descriptor) = result.unwrap();
         let endpoint = get_readable_endpoint(&device, &device_descriptor, TransferType::Bulk);
@@ -180,5 +225,8 @@ mod tests {
         let rusb_error = rusb::Error::Access;
         let error = Error::from(rusb_error);
         assert_eq!(Error::Access, error);
+        let rusb_error = rusb::Error::NoDevice;
+        let error = Error::from(rusb_error);
+        assert_eq!(Error::Disconnected, error);
     }
 }
@@ -0,0 +1,8 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+mod script;
+mod simulatedrfidreader;
+
+pub(crate) use simulatedrfidreader::open;
@@ -0,0 +1,77 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use rfid_value::RfidValue;
+
+use std::time::Duration;
+
+/// Parses the `<delay in milliseconds> <rfid value>` lines of a script.
+///
+/// Empty lines and lines starting with `#` are ignored.
+/// Returns an [InvalidScript](crate::Error::InvalidScript) error with the number of the first invalid line,
+/// which includes lines with an invalid [RfidValue](crate::RfidValue).
+pub(crate) fn parse(script: &str) -> Result<Vec<(Duration, RfidValue)>, Error> {
+    script
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let mut fields = line.split_whitespace();
+            let delay = fields.next().and_then(|delay| delay.parse::<u64>().ok());
+            match (delay, fields.next(), fields.next()) {
+                (Some(delay), Some(rfid_value), None) => match rfid_value.parse() {
+                    Ok(rfid_value) => Ok((Duration::from_millis(delay), rfid_value)),
+                    Err(_) => Err(Error::InvalidScript(format!(
+                        "line {}: invalid rfid value \"{}\"",
+                        number, rfid_value
+                    ))),
+                },
+                _ => Err(Error::InvalidScript(format!(
+                    "line {}: expected \"<delay in milliseconds> <rfid value>\" but got \"{}\"",
+                    number, line
+                ))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let script = "# cards of the demo\n500 0012345678\n\n  2000   0087654321  \n";
+        assert_eq!(
+            Ok(vec![
+                (Duration::from_millis(500), RfidValue::from(12_345_678)),
+                (Duration::from_millis(2000), RfidValue::from(87_654_321))
+            ]),
+            parse(script)
+        );
+        assert_eq!(Ok(vec![]), parse(""));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let expected_error = |number, line| {
+            Err(Error::InvalidScript(format!(
+                "line {}: expected \"<delay in milliseconds> <rfid value>\" but got \"{}\"",
+                number, line
+            )))
+        };
+        assert_eq!(expected_error(2, "0012345678"), parse("# ok\n0012345678"));
+        assert_eq!(expected_error(1, "-5 0012345678"), parse("-5 0012345678"));
+        assert_eq!(expected_error(1, "5 0012 345678"), parse("5 0012 345678"));
+        assert_eq!(
+            Err(Error::InvalidScript(
+                "line 1: invalid rfid value \"card\"".to_string()
+            )),
+            parse("5 card")
+        );
+    }
+}
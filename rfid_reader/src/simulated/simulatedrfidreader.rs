@@ -0,0 +1,242 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::ioerror::from_io_error;
+use crate::rfidreader::RfidReader;
+use crate::simulated::script;
+use crate::simulatedinput::SimulatedInput;
+use rfid_value::RfidValue;
+
+use log::warn;
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+/// Reads the RFID values from a thread that reads the [SimulatedInput](crate::SimulatedInput).
+struct SimulatedRfidReader {
+    input: SimulatedInput,
+    receiver: Receiver<RfidValue>,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for SimulatedRfidReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatedRfidReader")
+            .field("input", &self.input)
+            .finish()
+    }
+}
+
+impl RfidReader for SimulatedRfidReader {
+    fn read(&self) -> Result<RfidValue, Error> {
+        match self.receiver.recv_timeout(self.timeout) {
+            Ok(rfid_value) => Ok(rfid_value),
+            Err(RecvTimeoutError::Timeout) => Err(Error::Timeout),
+            Err(RecvTimeoutError::Disconnected) => {
+                // the input is exhausted, behave like a reader without cards
+                std::thread::sleep(self.timeout);
+                Err(Error::Timeout)
+            }
+        }
+    }
+    fn deinitialize(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Sends the non-empty lines of `reader` parsed as [RfidValue](crate::RfidValue) until its end,
+/// returns `false` if the [RfidReader](crate::RfidReader) was dropped.
+///
+/// Invalid lines are skipped with a warning, e.g. a typo in the input does not look like a read error of a device.
+fn send_lines(reader: impl BufRead, sender: &Sender<RfidValue>) -> bool {
+    for line in reader.lines() {
+        match line {
+            Ok(line) => {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match line.parse::<RfidValue>() {
+                    Ok(rfid_value) => {
+                        if sender.send(rfid_value).is_err() {
+                            return false;
+                        }
+                    }
+                    Err(error) => warn!("Skipping invalid line of the simulated reader: {}", error),
+                }
+            }
+            // an unreadable input is handled like its end
+            Err(_) => break,
+        }
+    }
+    true
+}
+
+/// Returns whether the file is a named pipe, which only exist on unix, elsewhere any file is read like a pipe.
+#[cfg(unix)]
+fn is_pipe(metadata: &std::fs::Metadata) -> bool {
+    metadata.file_type().is_fifo()
+}
+
+#[cfg(not(unix))]
+fn is_pipe(_metadata: &std::fs::Metadata) -> bool {
+    true
+}
+
+/// Opens a simulated [RfidReader](crate::RfidReader), whose `read` waits at most `timeout` for the next value of the `input`.
+pub(crate) fn open(input: SimulatedInput, timeout: Duration) -> Result<impl RfidReader, Error> {
+    let (sender, receiver) = channel();
+    match &input {
+        SimulatedInput::Stdin => {
+            std::thread::spawn(move || {
+                let stdin = std::io::stdin();
+                send_lines(stdin.lock(), &sender);
+            });
+        }
+        SimulatedInput::Pipe(path) => {
+            let metadata = std::fs::metadata(path).map_err(|error| from_io_error(error, path))?;
+            if !is_pipe(&metadata) {
+                return Err(Error::OtherInputError(format!(
+                    "{} is not a named pipe",
+                    path.display()
+                )));
+            }
+            let path = path.clone();
+            std::thread::spawn(move || {
+                // opening blocks until a writer opens the pipe, which is reopened after the writer closed it
+                while let Ok(file) = File::open(&path) {
+                    if !send_lines(BufReader::new(file), &sender) {
+                        break;
+                    }
+                }
+            });
+        }
+        SimulatedInput::Script(path) => {
+            let script =
+                std::fs::read_to_string(path).map_err(|error| from_io_error(error, path))?;
+            let lines = script::parse(&script)?;
+            std::thread::spawn(move || {
+                for (delay, rfid_value) in lines {
+                    std::thread::sleep(delay);
+                    if sender.send(rfid_value).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+    Ok(SimulatedRfidReader {
+        input,
+        receiver,
+        timeout,
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temporary_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "rfid_reader_simulated_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_send_lines() {
+        let (sender, receiver) = channel();
+        // the invalid line is skipped
+        assert!(send_lines(" 1234 \n\ncard\n5678".as_bytes(), &sender));
+        assert_eq!(
+            vec![RfidValue::from(1234), RfidValue::from(5678)],
+            receiver.try_iter().collect::<Vec<RfidValue>>()
+        );
+        drop(receiver);
+        assert!(!send_lines("1234".as_bytes(), &sender));
+    }
+
+    #[test]
+    fn test_script() {
+        let path = temporary_path("script");
+        std::fs::write(&path, "0 1234\n100 5678\n").unwrap();
+        let mut rfid_reader = open(
+            SimulatedInput::Script(path.clone()),
+            Duration::from_millis(60),
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(Ok(RfidValue::from(1234)), rfid_reader.read());
+        assert_eq!(Err(Error::Timeout), rfid_reader.read());
+        assert_eq!(Ok(RfidValue::from(5678)), rfid_reader.read());
+        assert_eq!(Err(Error::Timeout), rfid_reader.read());
+        assert_eq!(Ok(()), rfid_reader.deinitialize());
+    }
+
+    #[test]
+    fn test_invalid_script() {
+        let path = temporary_path("invalid_script");
+        std::fs::write(&path, "1234\n").unwrap();
+        let result = open(
+            SimulatedInput::Script(path.clone()),
+            Duration::from_millis(20),
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(Error::InvalidScript(_))));
+
+        let result = open(
+            SimulatedInput::Script(PathBuf::from("/non/existing/script")),
+            Duration::from_millis(20),
+        );
+        assert!(matches!(result, Err(Error::InputDeviceNotFound(_))));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pipe() {
+        let path = temporary_path("pipe");
+        nix::unistd::mkfifo(&path, nix::sys::stat::Mode::S_IRWXU).unwrap();
+        let rfid_reader = open(
+            SimulatedInput::Pipe(path.clone()),
+            Duration::from_millis(1000),
+        )
+        .unwrap();
+
+        // every write opens and closes the pipe
+        std::fs::write(&path, "1234\n").unwrap();
+        assert_eq!(Ok(RfidValue::from(1234)), rfid_reader.read());
+        std::fs::write(&path, "5678\n").unwrap();
+        assert_eq!(Ok(RfidValue::from(5678)), rfid_reader.read());
+        std::fs::remove_file(&path).unwrap();
+
+        let result = open(
+            SimulatedInput::Pipe(PathBuf::from("Cargo.toml")),
+            Duration::from_millis(20),
+        );
+        assert_eq!(
+            Some(Error::OtherInputError(
+                "Cargo.toml is not a named pipe".to_string()
+            )),
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let rfid_reader = open(SimulatedInput::Stdin, Duration::from_millis(20)).unwrap();
+        assert_eq!(
+            "SimulatedRfidReader { input: Stdin }",
+            format!("{:?}", rfid_reader)
+        );
+    }
+}
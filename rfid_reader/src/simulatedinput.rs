@@ -0,0 +1,81 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The input of a simulated [RfidReader](crate::RfidReader), see [open_simulated](crate::open_simulated).
+///
+/// Can be parsed from the strings `"stdin"`, `"pipe:<path>"` and `"script:<path>"`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SimulatedInput {
+    /// Each line entered on stdin is read as RFID value.
+    Stdin,
+    /// Each line written to the named pipe (FIFO) at the path is read as RFID value.
+    ///
+    /// The pipe is reopened whenever the writer closes it, e.g. `echo 0012345678 > <path>` can be used repeatedly.
+    Pipe(PathBuf),
+    /// The file at the path contains `<delay in milliseconds> <rfid value>` lines.
+    ///
+    /// Each RFID value is read after its delay elapsed, counted from the previous value.
+    /// Empty lines and lines starting with `#` are ignored.
+    Script(PathBuf),
+}
+
+impl FromStr for SimulatedInput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "stdin" {
+            Ok(SimulatedInput::Stdin)
+        } else if let Some(path) = s.strip_prefix("pipe:") {
+            Ok(SimulatedInput::Pipe(PathBuf::from(path)))
+        } else if let Some(path) = s.strip_prefix("script:") {
+            Ok(SimulatedInput::Script(PathBuf::from(path)))
+        } else {
+            Err(format!("Unknown simulated input: {}", s))
+        }
+    }
+}
+
+impl std::fmt::Display for SimulatedInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimulatedInput::Stdin => write!(f, "stdin"),
+            SimulatedInput::Pipe(path) => write!(f, "pipe:{}", path.display()),
+            SimulatedInput::Script(path) => write!(f, "script:{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(SimulatedInput::Stdin), "stdin".parse());
+        assert_eq!(
+            Ok(SimulatedInput::Pipe(PathBuf::from("/tmp/rfid"))),
+            "pipe:/tmp/rfid".parse()
+        );
+        assert_eq!(
+            Ok(SimulatedInput::Script(PathBuf::from("cards.txt"))),
+            "script:cards.txt".parse()
+        );
+        assert_eq!(
+            Err("Unknown simulated input: usb".to_string()),
+            "usb".parse::<SimulatedInput>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for input in &["stdin", "pipe:/tmp/rfid", "script:cards.txt"] {
+            let simulated_input: SimulatedInput = input.parse().unwrap();
+            assert_eq!(*input, format!("{}", simulated_input));
+        }
+    }
+}
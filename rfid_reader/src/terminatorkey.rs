@@ -0,0 +1,88 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use std::str::FromStr;
+
+/// HID usage codes of the keyboard/keypad usage page.
+const USAGE_ENTER: u8 = 0x28;
+const USAGE_TAB: u8 = 0x2b;
+const USAGE_SPACE: u8 = 0x2c;
+const USAGE_KEYPAD_ENTER: u8 = 0x58;
+
+/// The key that terminates an RFID value of a reader that behaves like a keyboard, see [HidFormat](crate::HidFormat).
+///
+/// Can be parsed from the strings `"enter"`, `"tab"` and `"space"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TerminatorKey {
+    /// The enter key of the main keyboard or of the keypad.
+    Enter,
+    /// The tab key.
+    Tab,
+    /// The space key.
+    Space,
+}
+
+impl TerminatorKey {
+    /// Returns whether the HID usage code is this key.
+    pub(crate) fn matches(&self, usage: u8) -> bool {
+        match self {
+            TerminatorKey::Enter => usage == USAGE_ENTER || usage == USAGE_KEYPAD_ENTER,
+            TerminatorKey::Tab => usage == USAGE_TAB,
+            TerminatorKey::Space => usage == USAGE_SPACE,
+        }
+    }
+}
+
+impl FromStr for TerminatorKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "enter" => Ok(TerminatorKey::Enter),
+            "tab" => Ok(TerminatorKey::Tab),
+            "space" => Ok(TerminatorKey::Space),
+            _ => Err(format!("Unknown terminator key: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for TerminatorKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TerminatorKey::Enter => write!(f, "enter"),
+            TerminatorKey::Tab => write!(f, "tab"),
+            TerminatorKey::Space => write!(f, "space"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(TerminatorKey::Enter.matches(0x28));
+        assert!(TerminatorKey::Enter.matches(0x58));
+        assert!(!TerminatorKey::Enter.matches(0x2b));
+        assert!(TerminatorKey::Tab.matches(0x2b));
+        assert!(TerminatorKey::Space.matches(0x2c));
+    }
+
+    #[test]
+    fn test_from_str_and_display() {
+        for key in &[
+            TerminatorKey::Enter,
+            TerminatorKey::Tab,
+            TerminatorKey::Space,
+        ] {
+            assert_eq!(Ok(*key), key.to_string().parse());
+        }
+        assert_eq!(
+            Err("Unknown terminator key: esc".to_string()),
+            "esc".parse::<TerminatorKey>()
+        );
+    }
+}
@@ -0,0 +1,219 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::id::{ProductId, VendorId};
+use crate::usbdeviceselector::UsbDeviceSelector;
+
+/// Vendor and product ids of the RFID readers that are known to work, i.e. the Neuftech RFID reader.
+const KNOWN_READERS: [(u16, u16); 1] = [(0x16c0, 0x27db)];
+
+/// Describes a USB device attached to the system, see [list_devices](crate::list_devices).
+///
+/// The manufacturer, product and serial number strings are only available if the device can be opened,
+/// i.e. with sufficient rights and if the device provides them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsbDeviceInfo {
+    pub(crate) vendor_id: VendorId,
+    pub(crate) product_id: ProductId,
+    pub(crate) bus_number: u8,
+    pub(crate) address: u8,
+    pub(crate) port_numbers: Vec<u8>,
+    pub(crate) manufacturer: Option<String>,
+    pub(crate) product: Option<String>,
+    pub(crate) serial_number: Option<String>,
+    pub(crate) readable: bool,
+}
+
+impl UsbDeviceInfo {
+    /// Returns the vendor id.
+    pub fn vendor_id(&self) -> VendorId {
+        self.vendor_id
+    }
+
+    /// Returns the product id.
+    pub fn product_id(&self) -> ProductId {
+        self.product_id
+    }
+
+    /// Returns the number of the bus the device is attached to.
+    pub fn bus_number(&self) -> u8 {
+        self.bus_number
+    }
+
+    /// Returns the address of the device on its bus.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Returns the port chain of the device, i.e. the port of the root hub followed by the ports of the hubs in between.
+    pub fn port_numbers(&self) -> &[u8] {
+        &self.port_numbers
+    }
+
+    /// Returns the manufacturer string, if available.
+    pub fn manufacturer(&self) -> Option<&str> {
+        self.manufacturer.as_deref()
+    }
+
+    /// Returns the product string, if available.
+    pub fn product(&self) -> Option<&str> {
+        self.product.as_deref()
+    }
+
+    /// Returns the serial number string, if available.
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+
+    /// Returns whether the device has a readable interrupt endpoint, which RFID readers that behave like keyboards have.
+    pub fn readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Returns whether the device is an RFID reader that is known to work.
+    pub fn known_reader(&self) -> bool {
+        let ids = (self.vendor_id.into(), self.product_id.into());
+        self.readable && KNOWN_READERS.contains(&ids)
+    }
+}
+
+/// Selects the only known reader of the `devices` that matches the `selector`.
+///
+/// Fails with [Error::NoKnownReader](crate::Error::NoKnownReader) if there is none and
+/// with [Error::AmbiguousReaders](crate::Error::AmbiguousReaders) if there are several.
+pub(crate) fn select_known_reader(
+    devices: Vec<UsbDeviceInfo>,
+    selector: &UsbDeviceSelector,
+) -> Result<UsbDeviceInfo, Error> {
+    let mut known_readers: Vec<UsbDeviceInfo> = devices
+        .into_iter()
+        .filter(|device| device.known_reader() && selector.matches(device))
+        .collect();
+    match known_readers.len() {
+        0 => Err(Error::NoKnownReader),
+        1 => Ok(known_readers.remove(0)),
+        count => Err(Error::AmbiguousReaders(count)),
+    }
+}
+
+impl std::fmt::Display for UsbDeviceInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Bus {:03} Device {:03} ID {}:{}",
+            self.bus_number, self.address, self.vendor_id, self.product_id
+        )?;
+        if !self.port_numbers.is_empty() {
+            write!(
+                f,
+                " at {}",
+                UsbDeviceSelector::Port(self.bus_number, self.port_numbers.clone())
+            )?;
+        }
+        for string in [&self.manufacturer, &self.product]
+            .iter()
+            .copied()
+            .flatten()
+        {
+            write!(f, " {}", string)?;
+        }
+        if let Some(serial_number) = &self.serial_number {
+            write!(f, ", serial {}", serial_number)?;
+        }
+        if self.known_reader() {
+            write!(f, ", known reader")
+        } else if self.readable {
+            write!(f, ", readable")
+        } else {
+            write!(f, ", not readable")
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn device(vendor_id: u16, product_id: u16, readable: bool) -> UsbDeviceInfo {
+        UsbDeviceInfo {
+            vendor_id: VendorId::from(vendor_id),
+            product_id: ProductId::from(product_id),
+            bus_number: 2,
+            address: 4,
+            port_numbers: vec![1],
+            manufacturer: None,
+            product: None,
+            serial_number: None,
+            readable,
+        }
+    }
+
+    #[test]
+    fn test_known_reader() {
+        assert!(device(0x16c0, 0x27db, true).known_reader());
+        assert!(!device(0x16c0, 0x27db, false).known_reader());
+        assert!(!device(0x0cf3, 0x3005, true).known_reader());
+    }
+
+    #[test]
+    fn test_select_known_reader() {
+        let reader = device(0x16c0, 0x27db, true);
+        let other = device(0x0cf3, 0x3005, true);
+        let mut second_reader = reader.clone();
+        second_reader.port_numbers = vec![2];
+        let any = UsbDeviceSelector::Any;
+        assert_eq!(
+            Ok(reader.clone()),
+            select_known_reader(vec![other.clone(), reader.clone()], &any)
+        );
+        assert_eq!(
+            Err(Error::NoKnownReader),
+            select_known_reader(vec![other.clone()], &any)
+        );
+        let devices = vec![reader.clone(), other, second_reader.clone()];
+        assert_eq!(
+            Err(Error::AmbiguousReaders(2)),
+            select_known_reader(devices.clone(), &any)
+        );
+        assert_eq!(
+            Ok(second_reader),
+            select_known_reader(devices, &"port:2-2".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let mut reader = device(0x16c0, 0x27db, true);
+        assert_eq!(
+            "Bus 002 Device 004 ID 0x16c0:0x27db at port:2-1, known reader",
+            format!("{}", reader)
+        );
+        reader.manufacturer = Some("Sycreader".to_string());
+        reader.product = Some("RFID Reader".to_string());
+        reader.serial_number = Some("08FF20140315".to_string());
+        assert_eq!(
+            "Bus 002 Device 004 ID 0x16c0:0x27db at port:2-1 Sycreader RFID Reader, serial 08FF20140315, known reader",
+            format!("{}", reader)
+        );
+        assert_eq!(
+            "Bus 002 Device 004 ID 0x0cf3:0x3005 at port:2-1, readable",
+            format!("{}", device(0x0cf3, 0x3005, true))
+        );
+        assert_eq!(
+            "Bus 002 Device 004 ID 0x058f:0xa004 at port:2-1, not readable",
+            format!("{}", device(0x058f, 0xa004, false))
+        );
+        assert_eq!(Some("Sycreader"), reader.manufacturer());
+        assert_eq!(Some("RFID Reader"), reader.product());
+        assert_eq!(Some("08FF20140315"), reader.serial_number());
+        assert_eq!(2, reader.bus_number());
+        assert_eq!(4, reader.address());
+        assert_eq!(&[1], reader.port_numbers());
+        assert_eq!(VendorId::from(0x16c0), reader.vendor_id());
+        assert_eq!(ProductId::from(0x27db), reader.product_id());
+        assert!(reader.readable());
+    }
+}
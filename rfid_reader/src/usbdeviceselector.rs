@@ -0,0 +1,184 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::usbdeviceinfo::UsbDeviceInfo;
+
+use std::str::FromStr;
+
+/// Selects one of several USB devices with the same vendor and product id, see [open_hid](crate::open_hid).
+///
+/// Can be parsed from the strings `"any"`, `"port:<bus>-<port>[.<port>...]"` (e.g. `port:1-1.2` like in `/sys/bus/usb/devices`),
+/// `"address:<bus>:<address>"` (e.g. `address:2:4` like `Bus 002 Device 004` of `lsusb`) and `"serial:<serial number>"`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum UsbDeviceSelector {
+    /// The first device with the vendor and product id.
+    Any,
+    /// The device plugged into the port chain of the bus, i.e. the port of the root hub followed by the ports of the hubs in between.
+    ///
+    /// The port chain stays the same when the device is plugged in again into the same port.
+    Port(u8, Vec<u8>),
+    /// The device with the address on the bus.
+    ///
+    /// The address changes when the device is plugged in again, i.e. a disconnected device is not reopened.
+    Address(u8, u8),
+    /// The device with the serial number string, which is only available if the device provides one.
+    Serial(String),
+}
+
+impl UsbDeviceSelector {
+    /// Returns whether the `device` is selected.
+    pub fn matches(&self, device: &UsbDeviceInfo) -> bool {
+        match self {
+            UsbDeviceSelector::Any => true,
+            UsbDeviceSelector::Port(bus_number, port_numbers) => {
+                device.bus_number() == *bus_number && device.port_numbers() == &port_numbers[..]
+            }
+            UsbDeviceSelector::Address(bus_number, address) => {
+                device.bus_number() == *bus_number && device.address() == *address
+            }
+            UsbDeviceSelector::Serial(serial_number) => {
+                device.serial_number() == Some(serial_number.as_str())
+            }
+        }
+    }
+}
+
+fn parse_number(number: &str, s: &str) -> Result<u8, String> {
+    number
+        .parse()
+        .map_err(|_| format!("Invalid USB device selector: {}", s))
+}
+
+/// Selects [Any](crate::UsbDeviceSelector::Any) device.
+impl Default for UsbDeviceSelector {
+    fn default() -> Self {
+        UsbDeviceSelector::Any
+    }
+}
+
+impl FromStr for UsbDeviceSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid USB device selector: {}", s);
+        if s == "any" {
+            Ok(UsbDeviceSelector::Any)
+        } else if let Some(port) = s.strip_prefix("port:") {
+            let (bus_number, port_numbers) = port.split_once('-').ok_or_else(invalid)?;
+            Ok(UsbDeviceSelector::Port(
+                parse_number(bus_number, s)?,
+                port_numbers
+                    .split('.')
+                    .map(|port_number| parse_number(port_number, s))
+                    .collect::<Result<Vec<u8>, String>>()?,
+            ))
+        } else if let Some(address) = s.strip_prefix("address:") {
+            let (bus_number, address) = address.split_once(':').ok_or_else(invalid)?;
+            Ok(UsbDeviceSelector::Address(
+                parse_number(bus_number, s)?,
+                parse_number(address, s)?,
+            ))
+        } else if let Some(serial_number) = s.strip_prefix("serial:") {
+            if serial_number.is_empty() {
+                return Err(invalid());
+            }
+            Ok(UsbDeviceSelector::Serial(serial_number.to_string()))
+        } else {
+            Err(format!("Unknown USB device selector: {}", s))
+        }
+    }
+}
+
+impl std::fmt::Display for UsbDeviceSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsbDeviceSelector::Any => write!(f, "any"),
+            UsbDeviceSelector::Port(bus_number, port_numbers) => {
+                let port_numbers: Vec<String> =
+                    port_numbers.iter().map(|port| port.to_string()).collect();
+                write!(f, "port:{}-{}", bus_number, port_numbers.join("."))
+            }
+            UsbDeviceSelector::Address(bus_number, address) => {
+                write!(f, "address:{}:{}", bus_number, address)
+            }
+            UsbDeviceSelector::Serial(serial_number) => write!(f, "serial:{}", serial_number),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::id::{ProductId, VendorId};
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(UsbDeviceSelector::Any), "any".parse());
+        assert_eq!(
+            Ok(UsbDeviceSelector::Port(1, vec![1, 2])),
+            "port:1-1.2".parse()
+        );
+        assert_eq!(
+            Ok(UsbDeviceSelector::Address(2, 4)),
+            "address:002:004".parse()
+        );
+        assert_eq!(
+            Ok(UsbDeviceSelector::Serial("08FF20140315".to_string())),
+            "serial:08FF20140315".parse()
+        );
+        for invalid in &[
+            "port:1",
+            "port:1-1.x",
+            "address:2",
+            "address:2:256",
+            "serial:",
+        ] {
+            assert_eq!(
+                Err(format!("Invalid USB device selector: {}", invalid)),
+                invalid.parse::<UsbDeviceSelector>()
+            );
+        }
+        assert_eq!(
+            Err("Unknown USB device selector: usb".to_string()),
+            "usb".parse::<UsbDeviceSelector>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for selector in &["any", "port:1-1.2", "address:2:4", "serial:08FF20140315"] {
+            let usb_device_selector: UsbDeviceSelector = selector.parse().unwrap();
+            assert_eq!(*selector, format!("{}", usb_device_selector));
+        }
+        assert_eq!(UsbDeviceSelector::Any, UsbDeviceSelector::default());
+    }
+
+    #[test]
+    fn test_matches() {
+        let device = UsbDeviceInfo {
+            vendor_id: VendorId::from(0x16c0),
+            product_id: ProductId::from(0x27db),
+            bus_number: 1,
+            address: 4,
+            port_numbers: vec![1, 2],
+            manufacturer: None,
+            product: None,
+            serial_number: Some("08FF20140315".to_string()),
+            readable: true,
+        };
+        for selector in &["any", "port:1-1.2", "address:1:4", "serial:08FF20140315"] {
+            assert!(selector
+                .parse::<UsbDeviceSelector>()
+                .unwrap()
+                .matches(&device));
+        }
+        for selector in &["port:1-1", "port:2-1.2", "address:1:5", "serial:08FF"] {
+            assert!(!selector
+                .parse::<UsbDeviceSelector>()
+                .unwrap()
+                .matches(&device));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+// the recording was taken on a 64 bit little endian machine
+#[cfg(all(
+    target_os = "linux",
+    target_pointer_width = "64",
+    target_endian = "little"
+))]
+mod rfid_reader_integration_tests {
+    use rfid_reader::{open_event_device, Error, RfidReader};
+    use std::path::Path;
+    use std::time::Duration;
+
+    #[test]
+    fn test_recorded_event_stream() {
+        let mut rfid_reader =
+            open_event_device(Path::new("tests/card.events"), Duration::from_millis(100)).unwrap();
+        assert_eq!(
+            Ok("0012345678".to_string()),
+            rfid_reader.read().map(|rfid_value| rfid_value.to_string())
+        );
+        assert_eq!(
+            Ok("0087654321".to_string()),
+            rfid_reader.read().map(|rfid_value| rfid_value.to_string())
+        );
+        assert_eq!(Err(Error::Timeout), rfid_reader.read());
+        assert_eq!(Ok(()), rfid_reader.deinitialize());
+    }
+
+    #[test]
+    fn test_missing_event_device() {
+        let result = open_event_device(
+            Path::new("/dev/input/non_existing_event"),
+            Duration::from_millis(100),
+        );
+        assert_eq!(
+            Error::InputDeviceNotFound("/dev/input/non_existing_event".into()),
+            result.unwrap_err()
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod simulated_rfid_reader_integration_tests {
+    use rfid_reader::{open_simulated, Error, RfidReader, SimulatedInput};
+    use std::time::Duration;
+
+    #[test]
+    fn test_script() {
+        let input: SimulatedInput = "script:tests/cards.txt".parse().unwrap();
+        let mut rfid_reader = open_simulated(input, Duration::from_millis(500)).unwrap();
+        assert_eq!(
+            Ok("0012345678".to_string()),
+            rfid_reader.read().map(|rfid_value| rfid_value.to_string())
+        );
+        assert_eq!(
+            Ok("0087654321".to_string()),
+            rfid_reader.read().map(|rfid_value| rfid_value.to_string())
+        );
+        assert_eq!(Err(Error::Timeout), rfid_reader.read());
+        assert_eq!(Ok(()), rfid_reader.deinitialize());
+    }
+}
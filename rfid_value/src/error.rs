@@ -0,0 +1,30 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+/// RFID value errors.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// Returned whenever a value is not a valid [RfidValue](crate::RfidValue), e.g. it has more than 20 digits.
+    InvalidRfidValue(String),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = Error::InvalidRfidValue("card".to_string());
+        assert_eq!("InvalidRfidValue(\"card\")", format!("{}", error));
+    }
+}
@@ -0,0 +1,14 @@
+//! A crate for the value of an RFID card.
+//!
+//! The [RfidValue](crate::RfidValue) is read by the RFID reader and parsed from the notations printed on the card.
+//! It has no dependencies on a reader (e.g. libusb), so that crates which only map cards to something else,
+//! e.g. a track store, can use it.
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+mod error;
+mod rfidvalue;
+
+pub use error::Error;
+pub use rfidvalue::RfidValue;
@@ -0,0 +1,301 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Maximum number of decimal digits of an [RfidValue](crate::RfidValue), i.e. of a 64 bit number.
+const DECIMAL_DIGITS: usize = 20;
+/// Maximum number of hexadecimal digits of an [RfidValue](crate::RfidValue), i.e. of a 64 bit number.
+const HEX_DIGITS: usize = 16;
+/// Number of hexadecimal digits of the 40 bit EM4100 id, i.e. the version byte followed by the 32 bit value.
+const EM4100_DIGITS: usize = 10;
+
+/// Prefix of the hexadecimal notation.
+const HEX_PREFIX: &str = "hex:";
+/// Prefix of the EM4100 notation.
+const EM4100_PREFIX: &str = "em4100:";
+/// Prefix of the Wiegand 26 notation.
+const WIEGAND26_PREFIX: &str = "wiegand26:";
+
+/// The value of a card read by an RFID reader.
+///
+/// The value is usually the 32 bit number of an EM4100 card, which is printed on most cards in one or more of the following representations:
+/// * decimal with 10 digits, e.g. `0012345678`, which is also what the reader outputs
+/// * hexadecimal with 8 digits, e.g. `00BC614E`
+/// * Wiegand 26 facility code and card number of the lower 24 bits, e.g. `188,24910`
+///
+/// Readers of other cards output longer numbers, therefore the value holds up to 64 bits.
+///
+/// An [RfidValue](crate::RfidValue) is displayed as its decimal representation and serialized as such.
+/// It is parsed from (and deserialized from) one of the following notations:
+/// * the decimal representation, leading zeros may be omitted, e.g. `12345678`
+/// * `hex:` followed by the hexadecimal representation, e.g. `hex:00BC614E`
+/// * `em4100:` followed by the 10 hexadecimal digits of the 40 bit EM4100 id, whose version byte is not part of the value, e.g. `em4100:0A00BC614E`
+/// * `wiegand26:` followed by the Wiegand 26 facility code and card number, e.g. `wiegand26:188,24910`
+///
+/// # Example
+/// ```rust
+/// use rfid_value::RfidValue;
+///
+/// let rfid_value: RfidValue = "0012345678".parse().unwrap();
+/// assert_eq!("00BC614E", rfid_value.to_hex());
+/// assert_eq!("188,24910", rfid_value.to_wiegand26());
+/// assert_eq!(rfid_value, RfidValue::from_wiegand26(188, 24910));
+/// assert_eq!(Ok(rfid_value), "wiegand26:188,24910".parse());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RfidValue(u64);
+
+impl RfidValue {
+    /// Creates the [RfidValue](crate::RfidValue) from the Wiegand 26 `facility` code and `card` number.
+    ///
+    /// Wiegand 26 only contains the lower 24 bits, i.e. the upper 8 bits of the value are 0.
+    pub fn from_wiegand26(facility: u8, card: u16) -> Self {
+        RfidValue(u64::from(facility) << 16 | u64::from(card))
+    }
+
+    /// Tries to parse the hexadecimal representation with at most 16 digits, e.g. `00BC614E`.
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        if hex.is_empty() || hex.len() > HEX_DIGITS || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidRfidValue(hex.to_string()));
+        }
+        u64::from_str_radix(hex, 16)
+            .map(RfidValue)
+            .map_err(|_| Error::InvalidRfidValue(hex.to_string()))
+    }
+
+    /// Tries to parse the 10 hexadecimal digits of the 40 bit EM4100 id, e.g. `0A00BC614E`.
+    ///
+    /// The first byte is the version (or customer id), which the reader does not output, i.e. it is not part of the value.
+    pub fn from_em4100(em4100: &str) -> Result<Self, Error> {
+        if em4100.len() != EM4100_DIGITS || !em4100.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidRfidValue(em4100.to_string()));
+        }
+        RfidValue::from_hex(&em4100[2..]).map_err(|_| Error::InvalidRfidValue(em4100.to_string()))
+    }
+
+    /// Returns the number of the card.
+    pub fn number(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the decimal representation with at least 10 digits, e.g. `0012345678`.
+    pub fn to_decimal(&self) -> String {
+        format!("{:010}", self.0)
+    }
+
+    /// Returns the hexadecimal representation with at least 8 upper case digits, e.g. `00BC614E`.
+    pub fn to_hex(&self) -> String {
+        format!("{:08X}", self.0)
+    }
+
+    /// Returns the Wiegand 26 facility code and card number, i.e. bits 16 to 23 and the lower 16 bits.
+    pub fn wiegand26(&self) -> (u8, u16) {
+        ((self.0 >> 16) as u8, self.0 as u16)
+    }
+
+    /// Returns the Wiegand 26 representation, e.g. `188,24910`.
+    pub fn to_wiegand26(&self) -> String {
+        let (facility, card) = self.wiegand26();
+        format!("{},{}", facility, card)
+    }
+}
+
+impl From<u64> for RfidValue {
+    fn from(number: u64) -> Self {
+        RfidValue(number)
+    }
+}
+
+/// Parses one of the notations, see [RfidValue](crate::RfidValue).
+impl FromStr for RfidValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidRfidValue(s.to_string());
+        if let Some(hex) = s.strip_prefix(HEX_PREFIX) {
+            return RfidValue::from_hex(hex).map_err(|_| invalid());
+        }
+        if let Some(em4100) = s.strip_prefix(EM4100_PREFIX) {
+            return RfidValue::from_em4100(em4100).map_err(|_| invalid());
+        }
+        if let Some(wiegand26) = s.strip_prefix(WIEGAND26_PREFIX) {
+            let mut fields = wiegand26.splitn(2, ',');
+            // the fields are digits only, since parse also accepts a leading "+"
+            let is_number =
+                |field: &str| !field.is_empty() && field.chars().all(|c| c.is_ascii_digit());
+            return match (fields.next(), fields.next()) {
+                (Some(facility), Some(card)) if is_number(facility) && is_number(card) => {
+                    match (facility.parse(), card.parse()) {
+                        (Ok(facility), Ok(card)) => Ok(RfidValue::from_wiegand26(facility, card)),
+                        _ => Err(invalid()),
+                    }
+                }
+                _ => Err(invalid()),
+            };
+        }
+        if s.is_empty() || s.len() > DECIMAL_DIGITS || !s.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::InvalidRfidValue(s.to_string()));
+        }
+        // 20 digits may exceed 64 bits
+        s.parse::<u64>()
+            .map(RfidValue)
+            .map_err(|_| Error::InvalidRfidValue(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for RfidValue {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<RfidValue> for String {
+    fn from(rfid_value: RfidValue) -> Self {
+        rfid_value.to_decimal()
+    }
+}
+
+impl std::fmt::Display for RfidValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_decimal())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(RfidValue(12_345_678)), "0012345678".parse());
+        assert_eq!(Ok(RfidValue(1234)), "1234".parse());
+        assert_eq!(Ok(RfidValue(4_294_967_296)), "4294967296".parse());
+        assert_eq!(Ok(RfidValue(u64::MAX)), "18446744073709551615".parse());
+        for invalid in &[
+            "",
+            "18446744073709551616",
+            "000123456789012345678",
+            "12a4",
+            "+1234",
+            " 1234",
+        ] {
+            assert_eq!(
+                Err(Error::InvalidRfidValue(invalid.to_string())),
+                invalid.parse::<RfidValue>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_str_notations() {
+        let rfid_value = RfidValue(12_345_678);
+        assert_eq!(Ok(rfid_value), "hex:00BC614E".parse());
+        assert_eq!(Ok(rfid_value), "hex:bc614e".parse());
+        assert_eq!(Ok(rfid_value), "em4100:0A00BC614E".parse());
+        assert_eq!(Ok(rfid_value), "wiegand26:188,24910".parse());
+        assert_eq!(Ok(RfidValue(0x00A1_B2C3)), "em4100:0A00A1B2C3".parse());
+        for invalid in &[
+            "hex:",
+            "hex:100000000BC614E00",
+            "em4100:00BC614E",
+            "em4100:0A00BC614G",
+            "wiegand26:188",
+            "wiegand26:256,24910",
+            "wiegand26:188,65536",
+            "wiegand26:+188,24910",
+            "wiegand26:188,",
+            "dec:12345678",
+        ] {
+            assert_eq!(
+                Err(Error::InvalidRfidValue(invalid.to_string())),
+                invalid.parse::<RfidValue>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_em4100() {
+        assert_eq!(
+            Ok(RfidValue(12_345_678)),
+            RfidValue::from_em4100("0A00BC614E")
+        );
+        assert_eq!(
+            Err(Error::InvalidRfidValue("00BC614E".to_string())),
+            RfidValue::from_em4100("00BC614E")
+        );
+    }
+
+    #[test]
+    fn test_representations() {
+        let rfid_value = RfidValue::from(12_345_678);
+        assert_eq!(12_345_678, rfid_value.number());
+        assert_eq!("0012345678", rfid_value.to_decimal());
+        assert_eq!("0012345678", format!("{}", rfid_value));
+        assert_eq!("00BC614E", rfid_value.to_hex());
+        assert_eq!((188, 24910), rfid_value.wiegand26());
+        assert_eq!("188,24910", rfid_value.to_wiegand26());
+        assert_eq!("0012345678", String::from(rfid_value));
+        // numbers of more than 32 bits are not truncated
+        let rfid_value = RfidValue::from(123_456_789_012);
+        assert_eq!("123456789012", rfid_value.to_decimal());
+        assert_eq!("1CBE991A14", rfid_value.to_hex());
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(Ok(RfidValue(12_345_678)), RfidValue::from_hex("00BC614E"));
+        assert_eq!(Ok(RfidValue(12_345_678)), RfidValue::from_hex("bc614e"));
+        assert_eq!(
+            Ok(RfidValue(0x1_00BC_614E)),
+            RfidValue::from_hex("100BC614E")
+        );
+        for invalid in &["", "100000000BC614E00", "0xBC614E", "BC61 4E"] {
+            assert_eq!(
+                Err(Error::InvalidRfidValue(invalid.to_string())),
+                RfidValue::from_hex(invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_wiegand26() {
+        assert_eq!(RfidValue(12_345_678), RfidValue::from_wiegand26(188, 24910));
+        // the upper 8 bits are not part of Wiegand 26
+        let rfid_value = RfidValue::from(0x0100_0001);
+        let (facility, card) = rfid_value.wiegand26();
+        assert_eq!(RfidValue(1), RfidValue::from_wiegand26(facility, card));
+    }
+
+    #[test]
+    fn test_serde() {
+        let rfid_value = RfidValue::from(12_345_678);
+        assert_eq!(
+            "---\n\"0012345678\"",
+            serde_yaml::to_string(&rfid_value).unwrap()
+        );
+        assert_eq!(
+            rfid_value,
+            serde_yaml::from_str::<RfidValue>("\"12345678\"").unwrap()
+        );
+        assert_eq!(
+            rfid_value,
+            serde_yaml::from_str::<RfidValue>("0012345678").unwrap()
+        );
+        assert_eq!(
+            rfid_value,
+            serde_yaml::from_str::<RfidValue>("\"wiegand26:188,24910\"").unwrap()
+        );
+        assert!(serde_yaml::from_str::<RfidValue>("\"card\"").is_err());
+    }
+}
@@ -0,0 +1,132 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use serde::{Deserialize, Serialize};
+
+use std::convert::TryFrom;
+
+/// The action of a control card.
+///
+/// In the yaml string an action is a map with the key `action` and the snake case name of the action,
+/// e.g. `action: volume_up`. The sleep timer additionally needs the key `minutes`.
+/// Any other key is rejected, e.g. a `path`, so that an entry is never both a control card and a track.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(tag = "action", rename_all = "snake_case", try_from = "ActionMap")]
+pub enum Action {
+    /// Stops the playback.
+    Stop,
+    /// Pauses the playback.
+    Pause,
+    /// Skips to the next track of the playlist.
+    Next,
+    /// Goes back to the previous track of the playlist.
+    Previous,
+    /// Increases the volume.
+    VolumeUp,
+    /// Decreases the volume.
+    VolumeDown,
+    /// Shuffles the playlist.
+    Shuffle,
+    /// Stops the playback after the given minutes.
+    SleepTimer {
+        /// Minutes until the playback is stopped.
+        minutes: u64,
+    },
+}
+
+/// The map of an [`Action`] in the yaml string, which denies unknown keys also for actions without fields.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ActionMap {
+    action: ActionName,
+    minutes: Option<u64>,
+}
+
+/// The name of an [`Action`] in the yaml string.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ActionName {
+    Stop,
+    Pause,
+    Next,
+    Previous,
+    VolumeUp,
+    VolumeDown,
+    Shuffle,
+    SleepTimer,
+}
+
+impl TryFrom<ActionMap> for Action {
+    type Error = String;
+
+    fn try_from(action_map: ActionMap) -> Result<Self, Self::Error> {
+        let action = match action_map.action {
+            ActionName::SleepTimer => {
+                let minutes = action_map
+                    .minutes
+                    .ok_or_else(|| "missing field `minutes`".to_string())?;
+                return Ok(Action::SleepTimer { minutes });
+            }
+            ActionName::Stop => Action::Stop,
+            ActionName::Pause => Action::Pause,
+            ActionName::Next => Action::Next,
+            ActionName::Previous => Action::Previous,
+            ActionName::VolumeUp => Action::VolumeUp,
+            ActionName::VolumeDown => Action::VolumeDown,
+            ActionName::Shuffle => Action::Shuffle,
+        };
+        match action_map.minutes {
+            Some(_) => Err(format!("action {} has no field `minutes`", action)),
+            None => Ok(action),
+        }
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let action: Action = serde_yaml::from_str("action: volume_up").unwrap();
+        assert_eq!(Action::VolumeUp, action);
+
+        let action: Action = serde_yaml::from_str("action: sleep_timer\nminutes: 30").unwrap();
+        assert_eq!(Action::SleepTimer { minutes: 30 }, action);
+
+        let result: serde_yaml::Result<Action> = serde_yaml::from_str("action: unknown");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_unknown_fields() {
+        for invalid in &[
+            "action: stop\npath: path/to/track",
+            "action: sleep_timer\nminutes: 30\npath: path/to/track",
+            "action: stop\nminutes: 30",
+            "action: sleep_timer",
+        ] {
+            let result: serde_yaml::Result<Action> = serde_yaml::from_str(invalid);
+            assert!(result.is_err(), "{} is no action", invalid);
+        }
+        let error = serde_yaml::from_str::<Action>("action: stop\nminutes: 30").unwrap_err();
+        assert_eq!("action Stop has no field `minutes`", error.to_string());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("Stop", format!("{}", Action::Stop));
+        assert_eq!(
+            "SleepTimer { minutes: 5 }",
+            format!("{}", Action::SleepTimer { minutes: 5 })
+        );
+    }
+}
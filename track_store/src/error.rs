@@ -12,6 +12,10 @@ pub enum Error {
     /// Returned when [`load`](crate::load)ing the [`TrackStore`](crate::TrackStore) did not work,
     /// e.g. when the parsed yaml string is invalid.
     LoadError(String),
+    /// Save error.
+    ///
+    /// Returned when [`save`](crate::MutableTrackStore::save)ing the [`TrackStore`](crate::TrackStore) did not work.
+    SaveError(String),
 }
 
 impl std::error::Error for Error {}
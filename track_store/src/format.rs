@@ -0,0 +1,387 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::id::Id;
+use crate::playlist::Playlist;
+use crate::trackentry::TrackEntry;
+use crate::trackpath::TrackPath;
+use crate::trackstore::Entry;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use std::path::Path;
+use std::str::FromStr;
+
+/// The format of the string a [`TrackStore`](crate::TrackStore) is loaded from and saved to.
+///
+/// YAML, TOML and JSON contain a map from the [`Id`](crate::Id)s to their [`TrackEntry`](crate::TrackEntry) or
+/// [`Action`](crate::Action), e.g. `"1234" = "path/to/track"` in TOML or `{"1234": "path/to/track"}` in JSON.
+/// CSV contains a header row and a row with the columns `id`, `path` and optionally `title` per track,
+/// the rows of the same id make up its playlist and the first title is the title of the entry.
+/// Control cards and other options of the entries than the title cannot be saved as CSV.
+/// An SQLite database is no string, it is opened with [`load_file`](crate::load_file) and written to with
+/// [`migrate_to_sqlite`](crate::migrate_to_sqlite) instead.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    /// YAML, the default format.
+    Yaml,
+    /// TOML.
+    Toml,
+    /// JSON.
+    Json,
+    /// CSV with the columns `id`, `path` and optionally `title`, e.g. exported from a spreadsheet.
+    Csv,
+    /// An SQLite database file, e.g. for thousands of cards, which requires the feature `sqlite`.
+    Sqlite,
+}
+
+/// A row of a CSV string, the title column is optional.
+#[derive(Deserialize)]
+pub(crate) struct CsvRecord {
+    pub(crate) id: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+}
+
+impl Format {
+    /// Returns the format of the file at `path` by its extension, e.g. `tracks.toml`.
+    ///
+    /// Returns [`None`](std::option::Option::None) if the extension is missing or unknown.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+
+    /// Parses the `string` in this format to the entries of a track store.
+    pub(crate) fn deserialize(self, string: &str) -> Result<IndexMap<Id, Entry>, Error> {
+        let to_load_error = |error: &dyn std::fmt::Display| Error::LoadError(error.to_string());
+        match self {
+            Format::Yaml => serde_yaml::from_str(string).map_err(|error| to_load_error(&error)),
+            Format::Toml => toml::from_str(string).map_err(|error| to_load_error(&error)),
+            Format::Json => serde_json::from_str(string).map_err(|error| to_load_error(&error)),
+            Format::Csv => {
+                let mut rows: IndexMap<Id, (Vec<TrackPath>, Option<String>)> = IndexMap::new();
+                for record in csv::Reader::from_reader(string.as_bytes()).deserialize() {
+                    let record: CsvRecord = record.map_err(|error| to_load_error(&error))?;
+                    let (tracks, title) = rows.entry(Id::from(record.id)).or_default();
+                    tracks.push(TrackPath::from(record.path));
+                    if title.is_none() {
+                        *title = record.title.filter(|title| !title.is_empty());
+                    }
+                }
+                Ok(rows
+                    .into_iter()
+                    .map(|(id, (tracks, title))| {
+                        let track_entry =
+                            TrackEntry::from(Playlist::from(tracks)).with_title(title);
+                        (id, Entry::Track(track_entry))
+                    })
+                    .collect())
+            }
+            Format::Sqlite => Err(Error::LoadError(
+                "An SQLite database cannot be loaded from a string".to_string(),
+            )),
+        }
+    }
+
+    /// Formats the entries of a track store in this format.
+    pub(crate) fn serialize(self, entries: &IndexMap<Id, Entry>) -> Result<String, Error> {
+        let to_save_error = |error: &dyn std::fmt::Display| Error::SaveError(error.to_string());
+        match self {
+            Format::Yaml => serde_yaml::to_string(entries).map_err(|error| to_save_error(&error)),
+            // the tables of the actions must follow the values of the playlists
+            Format::Toml => toml::Value::try_from(entries)
+                .and_then(|value| toml::to_string(&value))
+                .map_err(|error| to_save_error(&error)),
+            Format::Json => {
+                serde_json::to_string_pretty(entries).map_err(|error| to_save_error(&error))
+            }
+            Format::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                // the header is written even without any row
+                writer
+                    .write_record(["id", "path", "title"])
+                    .map_err(|error| to_save_error(&error))?;
+                for (id, entry) in entries {
+                    let track_entry = match entry {
+                        Entry::Track(track_entry) if track_entry.has_only_title() => track_entry,
+                        Entry::Track(_) => {
+                            return Err(Error::SaveError(format!(
+                                "Options of id {} cannot be saved as CSV, only its title",
+                                String::from(id.clone())
+                            )))
+                        }
+                        Entry::Action(action) => {
+                            return Err(Error::SaveError(format!(
+                                "Action {} of id {} cannot be saved as CSV",
+                                action,
+                                String::from(id.clone())
+                            )))
+                        }
+                    };
+                    // the title is written to the first row of the id only
+                    let mut title = track_entry.title().unwrap_or_default();
+                    for track_path in track_entry.playlist().entries() {
+                        writer
+                            .write_record(&[
+                                String::from(id.clone()),
+                                track_path.to_string(),
+                                title.to_string(),
+                            ])
+                            .map_err(|error| to_save_error(&error))?;
+                        title = "";
+                    }
+                }
+                let bytes = writer.into_inner().map_err(|error| to_save_error(&error))?;
+                String::from_utf8(bytes).map_err(|error| to_save_error(&error))
+            }
+            Format::Sqlite => Err(Error::SaveError(
+                "An SQLite database cannot be saved to a string".to_string(),
+            )),
+        }
+    }
+}
+
+/// [YAML](crate::Format::Yaml) is the default format.
+impl Default for Format {
+    fn default() -> Self {
+        Format::Yaml
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "toml" => Ok(Format::Toml),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "sqlite" | "sqlite3" | "db" => Ok(Format::Sqlite),
+            _ => Err(format!("Unknown tracks file format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Yaml => write!(f, "yaml"),
+            Format::Toml => write!(f, "toml"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+            Format::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+
+    const FORMATS: [Format; 4] = [Format::Yaml, Format::Toml, Format::Json, Format::Csv];
+
+    #[test]
+    fn test_sqlite() {
+        assert_eq!(
+            Some(Format::Sqlite),
+            Format::from_path(Path::new("tracks.db"))
+        );
+        assert_eq!(Ok(Format::Sqlite), "sqlite".parse());
+        assert!(matches!(
+            Format::Sqlite.deserialize(""),
+            Err(Error::LoadError(_))
+        ));
+        assert!(matches!(
+            Format::Sqlite.serialize(&IndexMap::new()),
+            Err(Error::SaveError(_))
+        ));
+    }
+
+    fn entries() -> IndexMap<Id, Entry> {
+        let mut entries = IndexMap::new();
+        entries.insert(
+            Id::from("1234"),
+            Entry::Track(TrackEntry::from(Playlist::from(TrackPath::from(
+                "path/to/track",
+            )))),
+        );
+        entries.insert(
+            Id::from("wiegand26:1,2"),
+            Entry::Track(
+                TrackEntry::from(Playlist::from(vec![
+                    TrackPath::from("track, 1"),
+                    TrackPath::from("track \"2\""),
+                ]))
+                .with_title(Some("Title, \"1\"".to_string())),
+            ),
+        );
+        entries
+    }
+
+    fn tracks(entries: &IndexMap<Id, Entry>) -> Vec<(String, Vec<TrackPath>)> {
+        entries
+            .iter()
+            .map(|(id, entry)| match entry {
+                Entry::Track(track_entry) => (
+                    String::from(id.clone()),
+                    track_entry.playlist().entries().to_vec(),
+                ),
+                Entry::Action(action) => (action.to_string(), Vec::new()),
+            })
+            .collect()
+    }
+
+    fn title<'a>(entries: &'a IndexMap<Id, Entry>, id: &str) -> Option<&'a str> {
+        match entries.get(&Id::from(id)) {
+            Some(Entry::Track(track_entry)) => track_entry.title(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for format in FORMATS.iter() {
+            let string = format.serialize(&entries()).unwrap();
+            let entries_result = format.deserialize(&string);
+            assert!(entries_result.is_ok(), "{}: {}", format, string);
+            let deserialized = entries_result.unwrap();
+            assert_eq!(tracks(&entries()), tracks(&deserialized));
+            assert_eq!(
+                Some("Title, \"1\""),
+                title(&deserialized, "wiegand26:1,2"),
+                "{}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize_action() {
+        let mut entries = entries();
+        entries.insert(Id::from("5678"), Entry::Action(Action::Stop));
+        entries.insert(
+            Id::from("9012"),
+            Entry::Track(TrackEntry::from(Playlist::from(TrackPath::from("other")))),
+        );
+        let toml = Format::Toml.serialize(&entries).unwrap();
+        assert_eq!(
+            tracks(&entries).len(),
+            tracks(&Format::Toml.deserialize(&toml).unwrap()).len()
+        );
+        let json = Format::Json.serialize(&entries).unwrap();
+        assert_eq!(
+            tracks(&entries),
+            tracks(&Format::Json.deserialize(&json).unwrap())
+        );
+        assert_eq!(
+            Err(Error::SaveError(
+                "Action Stop of id 5678 cannot be saved as CSV".to_string()
+            )),
+            Format::Csv.serialize(&entries)
+        );
+    }
+
+    #[test]
+    fn test_serialize_options() {
+        let mut entries = IndexMap::new();
+        let track_entry: TrackEntry = serde_yaml::from_str("path: track\nstart: 5").unwrap();
+        entries.insert(Id::from("1234"), Entry::Track(track_entry));
+        for format in FORMATS[..3].iter() {
+            let string = format.serialize(&entries).unwrap();
+            let deserialized = format.deserialize(&string).unwrap();
+            assert_eq!(
+                entries.get(&Id::from("1234")).map(start),
+                deserialized.get(&Id::from("1234")).map(start),
+                "{}",
+                format
+            );
+        }
+        assert_eq!(
+            Err(Error::SaveError(
+                "Options of id 1234 cannot be saved as CSV, only its title".to_string()
+            )),
+            Format::Csv.serialize(&entries)
+        );
+    }
+
+    fn start(entry: &Entry) -> Option<std::time::Duration> {
+        match entry {
+            Entry::Track(track_entry) => track_entry.start(),
+            Entry::Action(_) => None,
+        }
+    }
+
+    #[test]
+    fn test_deserialize_csv() {
+        let csv = "id,path,title
+1234,path/to/track,
+5678,first,
+1234,path/to/other,Other";
+        let entries = Format::Csv.deserialize(csv).unwrap();
+        assert_eq!(Some("Other"), title(&entries, "1234"));
+        assert_eq!(None, title(&entries, "5678"));
+        assert_eq!(
+            vec![
+                (
+                    "1234".to_string(),
+                    vec![
+                        TrackPath::from("path/to/track"),
+                        TrackPath::from("path/to/other")
+                    ]
+                ),
+                ("5678".to_string(), vec![TrackPath::from("first")])
+            ],
+            tracks(&entries)
+        );
+
+        // the title is optional
+        assert!(Format::Csv.deserialize("id,path\n1234,track").is_ok());
+        assert!(matches!(
+            Format::Csv.deserialize("id\n1234"),
+            Err(Error::LoadError(_))
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_error() {
+        for format in FORMATS[..3].iter() {
+            assert!(matches!(
+                format.deserialize("\"1234\" = [path"),
+                Err(Error::LoadError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            Some(Format::Toml),
+            Format::from_path(Path::new("path/tracks.toml"))
+        );
+        assert_eq!(
+            Some(Format::Yaml),
+            Format::from_path(Path::new("tracks.yml"))
+        );
+        assert_eq!(
+            Some(Format::Csv),
+            Format::from_path(Path::new("tracks.CSV"))
+        );
+        assert_eq!(None, Format::from_path(Path::new("tracks.txt")));
+        assert_eq!(None, Format::from_path(Path::new("tracks")));
+    }
+
+    #[test]
+    fn test_from_str_and_display() {
+        for format in FORMATS.iter() {
+            assert_eq!(Ok(*format), format.to_string().parse());
+        }
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -2,29 +2,77 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
+use rfid_value::RfidValue;
 use serde::{Deserialize, Serialize};
+
+use std::hash::{Hash, Hasher};
+
 /// The Id of a track.
 ///
 /// In order to receive a [`TrackPath`](crate::TrackPath) from the [`TrackStore`](crate::TrackStore),
-/// one must must create an Id from a [`String`](std::string::String).
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
-pub struct Id(String);
+/// one must must create an Id from a [`String`](std::string::String) or an [`RfidValue`](rfid_value::RfidValue).
+///
+/// A [`String`](std::string::String) in one of the notations of an [`RfidValue`](rfid_value::RfidValue)
+/// (e.g. `12345678`, `em4100:0A00BC614E` or `wiegand26:188,24910`) is normalized to the decimal representation
+/// with 10 digits that the reader outputs, i.e. all notations of a card are the same Id.
+/// The Wiegand 26 notation only contains the lower 24 bits, i.e. it is the same Id as a card only if the upper 8 bits
+/// of its number are 0, see [`validate`](crate::validate).
+/// Other strings are used as they are.
+/// The notation is kept, so that the Id is saved as it was loaded.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "String", into = "String")]
+pub struct Id {
+    id: String,
+    notation: String,
+}
 
 impl From<String> for Id {
     fn from(s: String) -> Self {
-        Id(s)
+        let id = match s.parse::<RfidValue>() {
+            Ok(rfid_value) => rfid_value.to_decimal(),
+            Err(_) => s.clone(),
+        };
+        Id { id, notation: s }
     }
 }
 
 impl From<&str> for Id {
     fn from(path: &str) -> Self {
-        Id(path.into())
+        Id::from(path.to_string())
+    }
+}
+
+/// The Id of a card is the decimal representation of its [`RfidValue`](rfid_value::RfidValue) with 10 digits, e.g. `0012345678`.
+impl From<RfidValue> for Id {
+    fn from(rfid_value: RfidValue) -> Self {
+        Id::from(rfid_value.to_decimal())
+    }
+}
+
+/// Returns the notation of the Id.
+impl From<Id> for String {
+    fn from(id: Id) -> Self {
+        id.notation
+    }
+}
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Id {}
+
+impl Hash for Id {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
     }
 }
 
 impl std::fmt::Display for Id {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.id)
     }
 }
 
@@ -37,6 +85,28 @@ mod tests {
     fn test_display() {
         let id = Id::from("01234");
         let formatted_id = format!("{}", id);
-        assert_eq!(String::from("01234"), formatted_id);
+        assert_eq!(String::from("0000001234"), formatted_id);
+        assert_eq!(String::from("other"), format!("{}", Id::from("other")));
+    }
+
+    #[test]
+    fn test_from_rfid_value() {
+        let id = Id::from(RfidValue::from(1234));
+        assert_eq!(Id::from("0000001234"), id);
+    }
+
+    #[test]
+    fn test_notations() {
+        let id = Id::from("0012345678");
+        assert_eq!(id, Id::from("12345678"));
+        assert_eq!(id, Id::from("hex:00BC614E"));
+        assert_eq!(id, Id::from("em4100:0A00BC614E"));
+        assert_eq!(id, Id::from("wiegand26:188,24910"));
+        assert_ne!(id, Id::from("wiegand26:188,24911"));
+        // the notation is kept
+        assert_eq!(
+            String::from("wiegand26:188,24910"),
+            String::from(Id::from("wiegand26:188,24910"))
+        );
     }
 }
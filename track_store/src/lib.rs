@@ -2,13 +2,34 @@
 //!
 //! The [`TrackStore`](crate::TrackStore) can be [`load`](crate::load)ed from a yaml string that is a list
 //! of key value pairs.
-//! Each key is an [`Id`](crate::Id) defined by a [`String`](std::string::String).
-//! Each value is a [`TrackPath`](crate::TrackPath) defined by a [`String`](std::string::String).
+//! Each key is an [`Id`](crate::Id) defined by a [`String`](std::string::String), e.g. the decimal representation of
+//! the [`RfidValue`](rfid_value::RfidValue) read from a card.
+//! Keys in one of the other notations of an [`RfidValue`](rfid_value::RfidValue), i.e. the numbers printed on the card,
+//! are normalized to the decimal representation on load.
+//! Each value is a [`Playlist`](crate::Playlist), which is either a single [`TrackPath`](crate::TrackPath)
+//! or a list of [`TrackPath`](crate::TrackPath)s defined by [`String`](std::string::String)s.
+//! A [`TrackPath`](crate::TrackPath) to a directory adds all audio files of this directory to the playlist.
+//! The value can also be a map with the playlist as `path` and options like a title, start and end offsets, a volume,
+//! looping and shuffling, see [`TrackEntry`](crate::TrackEntry).
+//! Relative paths are resolved against the base directory given to [`load_with_base_directory`](crate::load_with_base_directory),
+//! usually the directory of the yaml file, after expanding `~` and environment variables.
+//! Instead of a playlist, the value can also be an [`Action`](crate::Action) of a control card.
+//! The loaded store is a [`MutableTrackStore`](crate::MutableTrackStore), i.e. entries can be inserted and removed
+//! and the store can be saved back to a yaml string, preserving the order of the entries.
+//! Instead of YAML, the store can also be [`load_as`](crate::load_as)ed from and saved to TOML, JSON or CSV,
+//! see [`Format`](crate::Format).
+//! For thousands of cards, the entries can be [`migrate_to_sqlite`](crate::migrate_to_sqlite)d to an SQLite database,
+//! which is opened with [`load_file`](crate::load_file) and written to on every change instead of rewriting a file.
+//! SQLite databases require the feature `sqlite`, which bundles the SQLite library.
+//! Before deploying a yaml string, [`validate`](crate::validate) reports all problems of its entries with their line,
+//! e.g. tracks that do not exist.
 //!
 //! # Example
 //! ```rust
 //! // use crate
-//! use track_store::{load, TrackStore, Id, TrackPath};
+//! use std::path::Path;
+//! use std::time::Duration;
+//! use track_store::{load, load_with_base_directory, Action, MutableTrackStore, TrackStore, Id, TrackPath};
 //!
 //! // simple key value list in the yaml string
 //! let yaml_string = "01234: path/to/track";
@@ -29,17 +50,71 @@
 //! let id = Id::from(String::from("0"));
 //! let track_path = track_store.get_path(&id);
 //! assert!(track_path.is_none());
+//!
+//! // an id can also have a list of tracks
+//! let yaml_string = "01234:\n  - path/to/track\n  - path/to/track_2";
+//! let track_store = load(yaml_string).unwrap();
+//! let id = Id::from("01234");
+//! let playlist = track_store.get_playlist(&id).unwrap();
+//! assert_eq!(2, playlist.tracks().len());
+//!
+//! // the playlist can have a title and options of its playback
+//! let yaml_string = "01234:\n  path: path/to/audiobook\n  title: Audiobook\n  start: 12.5\n  loop: true";
+//! let track_store = load(yaml_string).unwrap();
+//! let entry = track_store.get_entry(&Id::from("01234")).unwrap();
+//! assert_eq!(Some("Audiobook"), entry.title());
+//! assert_eq!(Some(Duration::from_millis(12500)), entry.start());
+//! assert!(entry.loops());
+//!
+//! // or it can be a control card with an action
+//! let yaml_string = "56789:\n  action: stop";
+//! let track_store = load(yaml_string).unwrap();
+//! let id = Id::from("56789");
+//! assert_eq!(Some(&Action::Stop), track_store.get_action(&id));
+//!
+//! // the store can be changed and saved back to a yaml string
+//! let mut track_store = load("01234: path/to/track").unwrap();
+//! track_store.insert(Id::from("56789"), TrackPath::from("path/to/track_2").into()).unwrap();
+//! track_store.remove(&Id::from("01234")).unwrap();
+//! let yaml_string = track_store.save().unwrap();
+//! assert_eq!("---\n\"56789\": path/to/track_2", yaml_string);
+//!
+//! // the number printed on the card can be used instead of the value output by the reader
+//! let track_store = load("wiegand26:188,24910: path/to/track").unwrap();
+//! assert!(track_store.get_path(&Id::from("0012345678")).is_some());
+//!
+//! // relative paths are resolved against the base directory, e.g. the directory of the yaml file
+//! let track_store = load_with_base_directory("01234: path/to/track", Path::new("/music")).unwrap();
+//! let track_path = track_store.get_path(&Id::from("01234")).unwrap();
+//! assert_eq!(Path::new("/music/path/to/track"), track_path.as_path());
 //! ```
 #![warn(missing_docs)]
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
+mod action;
 mod error;
+mod format;
 mod id;
+mod playlist;
+mod problem;
+#[cfg(feature = "sqlite")]
+mod sqlitedatabase;
+mod trackentry;
 mod trackpath;
 mod trackstore;
+mod validation;
 
+pub use action::Action;
 pub use error::Error;
+pub use format::Format;
 pub use id::Id;
+pub use playlist::Playlist;
+pub use problem::Problem;
+pub use trackentry::TrackEntry;
 pub use trackpath::TrackPath;
-pub use trackstore::{load, TrackStore};
+pub use trackstore::{
+    load, load_as, load_file, load_with_base_directory, migrate_to_sqlite, MutableTrackStore,
+    TrackStore,
+};
+pub use validation::{validate, validate_as};
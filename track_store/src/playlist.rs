@@ -0,0 +1,265 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::trackpath::TrackPath;
+
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// File extensions of the audio files that are added to a playlist when a directory is given.
+const AUDIO_FILE_EXTENSIONS: [&str; 4] = ["flac", "mp3", "ogg", "wav"];
+
+/// The playlist of an [`Id`](crate::Id).
+///
+/// In the yaml string a playlist is either a single path, or a list of paths.
+/// The paths are kept as they were written, the [`tracks`](crate::Playlist::tracks) are resolved when loaded.
+/// A path to a directory adds all audio files of the directory, sorted in natural order (i.e. `2.mp3` before `10.mp3`).
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(from = "PlaylistEntry", into = "PlaylistEntry")]
+pub struct Playlist {
+    entries: Vec<TrackPath>,
+    tracks: Vec<TrackPath>,
+}
+
+/// The representation of a [`Playlist`] in the yaml string.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum PlaylistEntry {
+    Single(TrackPath),
+    List(Vec<TrackPath>),
+}
+
+impl Playlist {
+    /// Returns the [`TrackPath`](crate::TrackPath)s of the playlist in the order they shall be played.
+    pub fn tracks(&self) -> &[TrackPath] {
+        &self.tracks
+    }
+
+    /// Returns the [`TrackPath`](crate::TrackPath)s as they were loaded or inserted, i.e. including directories.
+    pub(crate) fn entries(&self) -> &[TrackPath] {
+        &self.entries
+    }
+
+    /// Resolves the entries against the `base_directory` and replaces directories by the audio files they contain,
+    /// like the playlists of a loaded [`TrackStore`](crate::TrackStore).
+    ///
+    /// The entries are kept, so resolving again against the same `base_directory` results in the same tracks.
+    /// Returns a [`LoadError`](crate::Error::LoadError) if a path cannot be resolved or a directory cannot be read.
+    pub fn resolve_tracks(&mut self, base_directory: &Path) -> Result<(), Error> {
+        let mut tracks = Vec::with_capacity(self.entries.len());
+        for entry in self.entries.iter() {
+            let track_path = entry.resolve(base_directory)?;
+            if track_path.is_dir() {
+                tracks.extend(read_audio_files(&track_path)?);
+            } else {
+                tracks.push(track_path);
+            }
+        }
+        self.tracks = tracks;
+        Ok(())
+    }
+}
+
+impl From<Vec<TrackPath>> for Playlist {
+    fn from(entries: Vec<TrackPath>) -> Self {
+        Playlist {
+            tracks: entries.clone(),
+            entries,
+        }
+    }
+}
+
+impl From<TrackPath> for Playlist {
+    fn from(track_path: TrackPath) -> Self {
+        Playlist::from(vec![track_path])
+    }
+}
+
+impl From<PlaylistEntry> for Playlist {
+    fn from(entry: PlaylistEntry) -> Self {
+        match entry {
+            PlaylistEntry::Single(track_path) => Playlist::from(track_path),
+            PlaylistEntry::List(track_paths) => Playlist::from(track_paths),
+        }
+    }
+}
+
+impl From<Playlist> for PlaylistEntry {
+    fn from(playlist: Playlist) -> Self {
+        let mut entries = playlist.entries;
+        if entries.len() == 1 {
+            PlaylistEntry::Single(entries.remove(0))
+        } else {
+            PlaylistEntry::List(entries)
+        }
+    }
+}
+
+pub(crate) fn read_audio_files(directory: &Path) -> Result<Vec<TrackPath>, Error> {
+    let to_load_error = |error: std::io::Error| {
+        Error::LoadError(format!(
+            "Directory {} cannot be read: {}",
+            directory.display(),
+            error
+        ))
+    };
+    let mut files = Vec::new();
+    for dir_entry in std::fs::read_dir(directory).map_err(to_load_error)? {
+        let path = dir_entry.map_err(to_load_error)?.path();
+        if path.is_file() && is_audio_file(&path) {
+            files.push(path);
+        }
+    }
+    files.sort_by(|a, b| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+    Ok(files.into_iter().map(TrackPath::from).collect())
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    match path.extension() {
+        Some(extension) => AUDIO_FILE_EXTENSIONS
+            .iter()
+            .any(|audio_extension| extension.eq_ignore_ascii_case(audio_extension)),
+        None => false,
+    }
+}
+
+/// Compares two strings in natural order, i.e. numbers are compared by their value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let a_number = take_number(&mut a_chars);
+                let b_number = take_number(&mut b_chars);
+                let ordering = a_number
+                    .len()
+                    .cmp(&b_number.len())
+                    .then_with(|| a_number.cmp(&b_number));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(a_char), Some(b_char)) => {
+                let ordering = a_char.to_lowercase().cmp(b_char.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// Takes all consecutive digits without leading zeros.
+fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut number = String::new();
+    while let Some(c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        if !(number.is_empty() && *c == '0') {
+            number.push(*c);
+        }
+        chars.next();
+    }
+    number
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec![
+            "10.mp3",
+            "2.mp3",
+            "Track 1.mp3",
+            "1.mp3",
+            "track 01b.mp3",
+            "01.mp3",
+        ];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            vec![
+                "1.mp3",
+                "01.mp3",
+                "2.mp3",
+                "10.mp3",
+                "Track 1.mp3",
+                "track 01b.mp3"
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn test_is_audio_file() {
+        assert!(is_audio_file(Path::new("track.mp3")));
+        assert!(is_audio_file(Path::new("track.FLAC")));
+        assert!(!is_audio_file(Path::new("cover.jpg")));
+        assert!(!is_audio_file(Path::new("track")));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let playlist: Playlist = serde_yaml::from_str("path/to/track").unwrap();
+        assert_eq!(&[TrackPath::from("path/to/track")], playlist.tracks());
+
+        let playlist: Playlist = serde_yaml::from_str("- track1\n- track2").unwrap();
+        assert_eq!(
+            &[TrackPath::from("track1"), TrackPath::from("track2")],
+            playlist.tracks()
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let playlist = Playlist::from(TrackPath::from("track"));
+        assert_eq!("---\ntrack", serde_yaml::to_string(&playlist).unwrap());
+
+        let playlist = Playlist::from(vec![TrackPath::from("track1"), TrackPath::from("track2")]);
+        assert_eq!(
+            "---\n- track1\n- track2",
+            serde_yaml::to_string(&playlist).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_tracks() {
+        let directory =
+            std::env::temp_dir().join(format!("track_store_playlist_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        for name in &["10.wav", "2.wav", "1.flac", "cover.jpg"] {
+            std::fs::write(directory.join(name), "").unwrap();
+        }
+        let directory_path = TrackPath::from(directory.to_str().unwrap());
+        let mut playlist = Playlist::from(vec![TrackPath::from("first"), directory_path.clone()]);
+        playlist.resolve_tracks(Path::new("/base")).unwrap();
+
+        let expected: Vec<TrackPath> = vec![
+            TrackPath::from("/base/first"),
+            TrackPath::from(directory.join("1.flac").to_str().unwrap()),
+            TrackPath::from(directory.join("2.wav").to_str().unwrap()),
+            TrackPath::from(directory.join("10.wav").to_str().unwrap()),
+        ];
+        assert_eq!(expected.as_slice(), playlist.tracks());
+
+        // the directory is kept as written when serialized
+        assert_eq!(
+            format!("---\n- first\n- {}", directory_path),
+            serde_yaml::to_string(&playlist).unwrap()
+        );
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
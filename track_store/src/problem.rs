@@ -0,0 +1,79 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+/// A problem of a string found by [`validate`](crate::validate) or [`validate_as`](crate::validate_as).
+///
+/// A warning is a problem that does not make the string invalid, but may not do what was meant.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Problem {
+    line: Option<usize>,
+    message: String,
+    warning: bool,
+}
+
+impl Problem {
+    pub(crate) fn new(line: Option<usize>, message: impl Into<String>) -> Self {
+        Problem {
+            line,
+            message: message.into(),
+            warning: false,
+        }
+    }
+
+    pub(crate) fn warning(line: Option<usize>, message: impl Into<String>) -> Self {
+        Problem {
+            warning: true,
+            ..Problem::new(line, message)
+        }
+    }
+
+    /// Returns the number of the line (starting at 1) the problem was found in, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns `true` if the problem is only a warning.
+    pub fn is_warning(&self) -> bool {
+        self.warning
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        if self.warning {
+            write!(f, "warning: ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let problem = Problem::new(Some(3), "track a.mp3 does not exist");
+        assert_eq!(Some(3), problem.line());
+        assert_eq!("track a.mp3 does not exist", problem.message());
+        assert_eq!("line 3: track a.mp3 does not exist", format!("{}", problem));
+        assert_eq!(
+            "invalid yaml",
+            format!("{}", Problem::new(None, "invalid yaml"))
+        );
+        assert!(!problem.is_warning());
+        let warning = Problem::warning(Some(1), "id is ambiguous");
+        assert!(warning.is_warning());
+        assert_eq!("line 1: warning: id is ambiguous", format!("{}", warning));
+    }
+}
@@ -0,0 +1,237 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::id::Id;
+use crate::trackstore::Entry;
+
+use indexmap::IndexMap;
+use rusqlite::{params, Connection, OpenFlags};
+
+use std::path::Path;
+
+/// The table of the entries in the order they were inserted.
+///
+/// The `card` is the normalized [`Id`](crate::Id), so that all notations of a card share one row,
+/// the `id` is the notation it was inserted with and the `entry` is the JSON of the [`Entry`].
+const CREATE_ENTRIES_TABLE: &str = "CREATE TABLE IF NOT EXISTS entries (
+    position INTEGER PRIMARY KEY AUTOINCREMENT,
+    card TEXT NOT NULL UNIQUE,
+    id TEXT NOT NULL,
+    entry TEXT NOT NULL
+)";
+
+/// An SQLite database file that stores the entries of a [`TrackStore`](crate::TrackStore).
+///
+/// Every change is written to the database immediately, instead of rewriting a whole file.
+pub(crate) struct SqliteDatabase {
+    connection: Connection,
+}
+
+impl SqliteDatabase {
+    /// Opens the existing database at `path`, a missing file is not created.
+    ///
+    /// Returns a [`LoadError`](crate::Error::LoadError) if the file does not exist, cannot be opened or is no SQLite database.
+    pub(crate) fn open(path: &Path) -> Result<SqliteDatabase, Error> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        SqliteDatabase::from(Connection::open_with_flags(path, flags), path)
+    }
+
+    /// Opens the database at `path`, which is created if it does not exist.
+    ///
+    /// Returns a [`LoadError`](crate::Error::LoadError) if the file cannot be opened or is no SQLite database.
+    pub(crate) fn create(path: &Path) -> Result<SqliteDatabase, Error> {
+        SqliteDatabase::from(Connection::open(path), path)
+    }
+
+    /// Creates the table of the entries in the opened database, unless it exists.
+    fn from(
+        connection: Result<Connection, rusqlite::Error>,
+        path: &Path,
+    ) -> Result<SqliteDatabase, Error> {
+        let to_load_error =
+            |error: rusqlite::Error| Error::LoadError(format!("{}: {}", path.display(), error));
+        let connection = connection.map_err(to_load_error)?;
+        connection
+            .execute(CREATE_ENTRIES_TABLE, params![])
+            .map_err(to_load_error)?;
+        Ok(SqliteDatabase { connection })
+    }
+
+    /// Reads all entries in the order they were inserted.
+    ///
+    /// Returns a [`LoadError`](crate::Error::LoadError) if the entries cannot be read or an entry is invalid.
+    pub(crate) fn entries(&self) -> Result<IndexMap<Id, Entry>, Error> {
+        let to_load_error = |error: rusqlite::Error| Error::LoadError(error.to_string());
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, entry FROM entries ORDER BY position")
+            .map_err(to_load_error)?;
+        let rows = statement
+            .query_map(params![], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(to_load_error)?;
+        let mut entries = IndexMap::new();
+        for row in rows {
+            let (id, json) = row.map_err(to_load_error)?;
+            let entry = serde_json::from_str(&json).map_err(|error| {
+                Error::LoadError(format!("Entry of id {} is invalid: {}", id, error))
+            })?;
+            entries.insert(Id::from(id), entry);
+        }
+        Ok(entries)
+    }
+
+    /// Inserts the `entry` of the `id` or replaces the entry of the card, which keeps its position and notation.
+    ///
+    /// Returns a [`SaveError`](crate::Error::SaveError) if the entry cannot be written.
+    pub(crate) fn upsert(&self, id: &Id, entry: &Entry) -> Result<(), Error> {
+        upsert(&self.connection, id, entry)
+    }
+
+    /// Deletes the entry of the card with the `id` and returns `true` if there was one.
+    ///
+    /// Returns a [`SaveError`](crate::Error::SaveError) if the entry cannot be deleted.
+    pub(crate) fn delete(&self, id: &Id) -> Result<bool, Error> {
+        self.connection
+            .execute(
+                "DELETE FROM entries WHERE card = ?1",
+                params![id.to_string()],
+            )
+            .map(|deleted| deleted > 0)
+            .map_err(|error| Error::SaveError(error.to_string()))
+    }
+
+    /// Replaces all entries of the database by the `entries` at once, i.e. nothing is changed if writing fails.
+    ///
+    /// Returns a [`SaveError`](crate::Error::SaveError) if the entries cannot be written.
+    pub(crate) fn replace_all(&mut self, entries: &IndexMap<Id, Entry>) -> Result<(), Error> {
+        let to_save_error = |error: rusqlite::Error| Error::SaveError(error.to_string());
+        let transaction = self.connection.transaction().map_err(to_save_error)?;
+        transaction
+            .execute("DELETE FROM entries", params![])
+            .map_err(to_save_error)?;
+        for (id, entry) in entries {
+            upsert(&transaction, id, entry)?;
+        }
+        transaction.commit().map_err(to_save_error)
+    }
+}
+
+fn upsert(connection: &Connection, id: &Id, entry: &Entry) -> Result<(), Error> {
+    let json = serde_json::to_string(entry).map_err(|error| Error::SaveError(error.to_string()))?;
+    connection
+        .execute(
+            "INSERT INTO entries (card, id, entry) VALUES (?1, ?2, ?3)
+            ON CONFLICT(card) DO UPDATE SET entry = excluded.entry",
+            params![id.to_string(), String::from(id.clone()), json],
+        )
+        .map(|_| ())
+        .map_err(|error| Error::SaveError(error.to_string()))
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::playlist::Playlist;
+    use crate::trackentry::TrackEntry;
+    use crate::trackpath::TrackPath;
+
+    fn temporary_file(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "track_store_sqlite_database_{}_{}.sqlite",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn track(path: &str) -> Entry {
+        Entry::Track(TrackEntry::from(Playlist::from(TrackPath::from(path))))
+    }
+
+    fn ids(entries: &IndexMap<Id, Entry>) -> Vec<String> {
+        entries.keys().map(|id| String::from(id.clone())).collect()
+    }
+
+    #[test]
+    fn test_upsert_and_delete() {
+        let path = temporary_file("upsert_and_delete");
+        let database = SqliteDatabase::create(&path).unwrap();
+        assert!(database.entries().unwrap().is_empty());
+
+        database.upsert(&Id::from("1234"), &track("track")).unwrap();
+        database
+            .upsert(&Id::from("5678"), &Entry::Action(Action::Stop))
+            .unwrap();
+        // another notation of the same card replaces the entry, but keeps its position and notation
+        database
+            .upsert(&Id::from("hex:000004D2"), &track("other"))
+            .unwrap();
+
+        // the entries are durable
+        drop(database);
+        let database = SqliteDatabase::open(&path).unwrap();
+        let entries = database.entries().unwrap();
+        assert_eq!(vec!["1234", "5678"], ids(&entries));
+        match entries.get(&Id::from("1234")) {
+            Some(Entry::Track(track_entry)) => assert_eq!(
+                &[TrackPath::from("other")],
+                track_entry.playlist().entries()
+            ),
+            _ => panic!("Expected track entry"),
+        }
+
+        assert_eq!(Ok(true), database.delete(&Id::from("5678")));
+        assert_eq!(Ok(false), database.delete(&Id::from("5678")));
+        assert_eq!(vec!["1234"], ids(&database.entries().unwrap()));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let path = temporary_file("replace_all");
+        let mut database = SqliteDatabase::create(&path).unwrap();
+        database.upsert(&Id::from("1234"), &track("track")).unwrap();
+
+        let mut entries = IndexMap::new();
+        entries.insert(Id::from("wiegand26:1,2"), track("first"));
+        entries.insert(Id::from("0000"), track("second"));
+        database.replace_all(&entries).unwrap();
+        assert_eq!(
+            vec!["wiegand26:1,2", "0000"],
+            ids(&database.entries().unwrap())
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_error() {
+        let path = temporary_file("open_error");
+        std::fs::write(&path, "no database").unwrap();
+        assert!(matches!(
+            SqliteDatabase::open(&path),
+            Err(Error::LoadError(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            SqliteDatabase::open(Path::new("/non/existing/tracks.sqlite")),
+            Err(Error::LoadError(_))
+        ));
+
+        // a missing database is only created by create
+        let path = temporary_file("missing");
+        assert!(matches!(
+            SqliteDatabase::open(&path),
+            Err(Error::LoadError(_))
+        ));
+        assert!(!path.exists());
+        assert!(SqliteDatabase::create(&path).is_ok());
+        assert!(SqliteDatabase::open(&path).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,363 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::playlist::Playlist;
+
+use serde::{Deserialize, Serialize};
+
+use std::convert::TryFrom;
+use std::path::Path;
+use std::time::Duration;
+
+/// The entry of an [`Id`](crate::Id) that is played, i.e. its [`Playlist`](crate::Playlist) and the options of the playback.
+///
+/// In the yaml string an entry is either just the playlist, or a map with the playlist as `path` and the optional keys
+/// * `title` and `description`, human-readable names e.g. for logs,
+/// * `start` and `end`, the offsets in seconds between which every track of the playlist is played, e.g. to skip intros,
+/// * `volume` between `0.0` and `1.0`, where `1.0` is the original volume of the tracks,
+/// * `loop`, whether the playlist is played again after its end, and
+/// * `shuffle`, whether the playlist is shuffled when it is started.
+///
+/// For example `1234: {path: path/to/audiobook, title: Audiobook, start: 12.5, loop: true}`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(
+    try_from = "TrackEntryRepresentation",
+    into = "TrackEntryRepresentation"
+)]
+pub struct TrackEntry {
+    playlist: Playlist,
+    title: Option<String>,
+    description: Option<String>,
+    start: Option<Duration>,
+    end: Option<Duration>,
+    volume: Option<f32>,
+    loops: bool,
+    shuffles: bool,
+}
+
+/// The representation of a [`TrackEntry`] in the yaml string.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum TrackEntryRepresentation {
+    Plain(Playlist),
+    Extended(ExtendedTrackEntry),
+}
+
+/// The map of a [`TrackEntry`] with options, the offsets are given in seconds.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExtendedTrackEntry {
+    path: Playlist,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    volume: Option<f32>,
+    #[serde(rename = "loop", default, skip_serializing_if = "is_false")]
+    loops: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    shuffle: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl TrackEntry {
+    /// Returns the [`Playlist`](crate::Playlist) of the entry.
+    pub fn playlist(&self) -> &Playlist {
+        &self.playlist
+    }
+
+    /// Returns the human-readable title of the entry.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the description of the entry.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the offset at which every track of the playlist starts.
+    pub fn start(&self) -> Option<Duration> {
+        self.start
+    }
+
+    /// Returns the offset at which every track of the playlist ends.
+    pub fn end(&self) -> Option<Duration> {
+        self.end
+    }
+
+    /// Returns the volume of the tracks, where `1.0` is their original volume.
+    pub fn volume(&self) -> Option<f32> {
+        self.volume
+    }
+
+    /// Returns `true` if the playlist is played again from its beginning after its end.
+    pub fn loops(&self) -> bool {
+        self.loops
+    }
+
+    /// Returns `true` if the playlist is shuffled when it is started.
+    pub fn shuffles(&self) -> bool {
+        self.shuffles
+    }
+
+    /// Returns `true` if the entry has none of the options, i.e. it is just the playlist.
+    pub(crate) fn is_plain(&self) -> bool {
+        self.title.is_none() && self.has_only_title()
+    }
+
+    /// Returns `true` if the entry has no other option than the title.
+    pub(crate) fn has_only_title(&self) -> bool {
+        self.description.is_none()
+            && self.start.is_none()
+            && self.end.is_none()
+            && self.volume.is_none()
+            && !self.loops
+            && !self.shuffles
+    }
+
+    /// Returns the entry with the `title`.
+    pub(crate) fn with_title(mut self, title: Option<String>) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Resolves the playlist like [`Playlist::resolve_tracks`](crate::Playlist).
+    pub(crate) fn resolve_tracks(&mut self, base_directory: &Path) -> Result<(), Error> {
+        self.playlist.resolve_tracks(base_directory)
+    }
+
+    /// Parses the map of an entry with options, in contrast to deserializing a [`TrackEntry`] the error tells
+    /// the invalid option.
+    pub(crate) fn from_extended(value: serde_yaml::Value) -> Result<TrackEntry, String> {
+        let extended: ExtendedTrackEntry =
+            serde_yaml::from_value(value).map_err(|error| error.to_string())?;
+        TrackEntry::try_from(TrackEntryRepresentation::Extended(extended))
+    }
+}
+
+impl From<Playlist> for TrackEntry {
+    fn from(playlist: Playlist) -> Self {
+        TrackEntry {
+            playlist,
+            title: None,
+            description: None,
+            start: None,
+            end: None,
+            volume: None,
+            loops: false,
+            shuffles: false,
+        }
+    }
+}
+
+impl TryFrom<TrackEntryRepresentation> for TrackEntry {
+    type Error = String;
+
+    fn try_from(representation: TrackEntryRepresentation) -> Result<Self, Self::Error> {
+        let extended = match representation {
+            TrackEntryRepresentation::Plain(playlist) => return Ok(TrackEntry::from(playlist)),
+            TrackEntryRepresentation::Extended(extended) => extended,
+        };
+        let start = extended
+            .start
+            .map(|start| offset("start", start))
+            .transpose()?;
+        let end = extended.end.map(|end| offset("end", end)).transpose()?;
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                return Err(format!(
+                    "end offset {} s is not after start offset {} s",
+                    end.as_secs_f64(),
+                    start.as_secs_f64()
+                ));
+            }
+        }
+        if let Some(volume) = extended.volume {
+            // a track is never louder than the maximum volume of the player
+            if !(0.0..=1.0).contains(&volume) {
+                return Err(format!("volume {} is not between 0.0 and 1.0", volume));
+            }
+        }
+        Ok(TrackEntry {
+            playlist: extended.path,
+            title: extended.title,
+            description: extended.description,
+            start,
+            end,
+            volume: extended.volume,
+            loops: extended.loops,
+            shuffles: extended.shuffle,
+        })
+    }
+}
+
+/// Converts the offset in `seconds` to a duration.
+fn offset(name: &str, seconds: f64) -> Result<Duration, String> {
+    if seconds.is_finite() && seconds >= 0.0 {
+        Ok(Duration::from_secs_f64(seconds))
+    } else {
+        Err(format!(
+            "{} offset {} is no duration in seconds",
+            name, seconds
+        ))
+    }
+}
+
+impl From<TrackEntry> for TrackEntryRepresentation {
+    fn from(entry: TrackEntry) -> Self {
+        if entry.is_plain() {
+            return TrackEntryRepresentation::Plain(entry.playlist);
+        }
+        TrackEntryRepresentation::Extended(ExtendedTrackEntry {
+            path: entry.playlist,
+            title: entry.title,
+            description: entry.description,
+            start: entry.start.map(|start| start.as_secs_f64()),
+            end: entry.end.map(|end| end.as_secs_f64()),
+            volume: entry.volume,
+            loops: entry.loops,
+            shuffle: entry.shuffles,
+        })
+    }
+}
+
+impl std::fmt::Display for TrackEntry {
+    /// Formats the title, or the paths of the playlist if there is no title.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match &self.title {
+            Some(title) => write!(f, "{}", title),
+            None => {
+                let paths: Vec<String> = self
+                    .playlist
+                    .entries()
+                    .iter()
+                    .map(|track_path| track_path.to_string())
+                    .collect();
+                write!(f, "{}", paths.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::trackpath::TrackPath;
+
+    #[test]
+    fn test_deserialize_plain() {
+        let entry: TrackEntry = serde_yaml::from_str("path/to/track").unwrap();
+        assert_eq!(
+            TrackEntry::from(Playlist::from(TrackPath::from("path/to/track"))),
+            entry
+        );
+
+        let entry: TrackEntry = serde_yaml::from_str("- track1\n- track2").unwrap();
+        assert_eq!(2, entry.playlist().tracks().len());
+        assert!(entry.is_plain());
+    }
+
+    #[test]
+    fn test_deserialize_extended() {
+        let yaml = "path:
+  - track1
+  - track2
+title: Audiobook
+description: Read aloud
+start: 12.5
+end: 300
+volume: 0.8
+loop: true
+shuffle: true";
+        let entry: TrackEntry = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(2, entry.playlist().tracks().len());
+        assert_eq!(Some("Audiobook"), entry.title());
+        assert_eq!(Some("Read aloud"), entry.description());
+        assert_eq!(Some(Duration::from_millis(12500)), entry.start());
+        assert_eq!(Some(Duration::from_secs(300)), entry.end());
+        assert_eq!(Some(0.8), entry.volume());
+        assert!(entry.loops());
+        assert!(entry.shuffles());
+
+        let entry: TrackEntry = serde_yaml::from_str("path: track\ntitle: Track").unwrap();
+        assert_eq!(None, entry.start());
+        assert!(!entry.loops());
+        assert!(entry.has_only_title());
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        for yaml in &[
+            "path: track\ntitel: Track",
+            "title: Track",
+            "path: track\nstart: -1",
+            "path: track\nstart: 10\nend: 5",
+            "path: track\nvolume: -0.5",
+            "path: track\nvolume: 2.0",
+            "path: track\nvolume: .nan",
+        ] {
+            let result: serde_yaml::Result<TrackEntry> = serde_yaml::from_str(yaml);
+            assert!(result.is_err(), "{}", yaml);
+        }
+    }
+
+    #[test]
+    fn test_from_extended() {
+        let from_extended =
+            |yaml: &str| TrackEntry::from_extended(serde_yaml::from_str(yaml).unwrap());
+        assert!(from_extended("path: track\nloop: true").is_ok());
+        assert_eq!(
+            Err("end offset 5 s is not after start offset 10 s".to_string()),
+            from_extended("path: track\nstart: 10\nend: 5")
+        );
+        assert_eq!(
+            Err("start offset -1 is no duration in seconds".to_string()),
+            from_extended("path: track\nstart: -1")
+        );
+        assert_eq!(
+            Err("volume -0.5 is not between 0.0 and 1.0".to_string()),
+            from_extended("path: track\nvolume: -0.5")
+        );
+        assert_eq!(
+            Err("volume 2 is not between 0.0 and 1.0".to_string()),
+            from_extended("path: track\nvolume: 2.0")
+        );
+        assert_eq!(
+            Err("volume inf is not between 0.0 and 1.0".to_string()),
+            from_extended("path: track\nvolume: .inf")
+        );
+        assert!(from_extended("path: track\ntitel: Track")
+            .unwrap_err()
+            .starts_with("unknown field `titel`"));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let entry = TrackEntry::from(Playlist::from(TrackPath::from("track")));
+        assert_eq!("---\ntrack", serde_yaml::to_string(&entry).unwrap());
+
+        let yaml = "---\npath: track\ntitle: Track\nstart: 12.5\nloop: true";
+        let entry: TrackEntry = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(yaml, serde_yaml::to_string(&entry).unwrap());
+    }
+
+    #[test]
+    fn test_display() {
+        let entry: TrackEntry = serde_yaml::from_str("- track1\n- track2").unwrap();
+        assert_eq!("track1, track2", entry.to_string());
+        let entry = entry.with_title(Some("Tracks".to_string()));
+        assert_eq!("Tracks", entry.to_string());
+    }
+}
@@ -2,19 +2,89 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
+use crate::error::Error;
+
 use std::fmt::Display;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 /// The path of a track.
 ///
-/// The [`TrackPath`](crate::TrackPath) is created from a [`String`](std::string::String) or a [`&str`](std::str) and contains the path to the track.
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-pub struct TrackPath(String);
+/// The [`TrackPath`](crate::TrackPath) is created from a [`String`](std::string::String), a [`&str`](std::str) or a
+/// [`PathBuf`](std::path::PathBuf) and dereferences to the [`Path`](std::path::Path) of the track.
+/// The [`Playlist`](crate::Playlist)s of a loaded [`TrackStore`](crate::TrackStore) contain resolved paths,
+/// i.e. `~` and environment variables are expanded and relative paths are joined to the base directory.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TrackPath(PathBuf);
+
+impl TrackPath {
+    /// Returns the path of the track.
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Expands a leading `~` to the home directory and `$NAME` or `${NAME}` to the value of the environment variable,
+    /// then joins a relative path to the `base_directory`.
+    ///
+    /// Returns a [`LoadError`](crate::Error::LoadError) if an environment variable is not set.
+    pub(crate) fn resolve(&self, base_directory: &Path) -> Result<TrackPath, Error> {
+        let path = match self.0.to_str() {
+            Some(path) => PathBuf::from(expand(path)?),
+            // paths of the yaml string are always valid unicode, others are kept as they are
+            None => self.0.clone(),
+        };
+        Ok(TrackPath(base_directory.join(path)))
+    }
+}
+
+/// Expands the home directory and the environment variables of the `path`.
+fn expand(path: &str) -> Result<String, Error> {
+    let var = |name: &str| {
+        std::env::var(name).map_err(|_| {
+            Error::LoadError(format!(
+                "Environment variable {} of track path {} is not set",
+                name, path
+            ))
+        })
+    };
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+    if rest == "~" || rest.starts_with("~/") {
+        expanded.push_str(&var("HOME")?);
+        rest = &rest[1..];
+    }
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        let (name, remainder) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", rest),
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            }
+        };
+        if name.is_empty() {
+            // a `$` without a name is part of the path
+            expanded.push('$');
+        } else {
+            expanded.push_str(&var(name)?);
+            rest = remainder;
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
 
 impl From<String> for TrackPath {
     fn from(s: String) -> Self {
-        TrackPath(s)
+        TrackPath(s.into())
     }
 }
 
@@ -24,15 +94,29 @@ impl From<&str> for TrackPath {
     }
 }
 
-impl AsRef<str> for TrackPath {
-    fn as_ref(&self) -> &str {
-        self.0.as_ref()
+impl From<PathBuf> for TrackPath {
+    fn from(path: PathBuf) -> Self {
+        TrackPath(path)
+    }
+}
+
+impl AsRef<Path> for TrackPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Deref for TrackPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
     }
 }
 
 impl Display for TrackPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.0.display())
     }
 }
 
@@ -47,4 +131,46 @@ mod tests {
         let formatted_track_path = format!("{}", track_path);
         assert_eq!(String::from("path"), formatted_track_path);
     }
+
+    #[test]
+    fn test_as_path() {
+        let track_path = TrackPath::from(PathBuf::from("path/to/track.mp3"));
+        assert_eq!(Path::new("path/to/track.mp3"), track_path.as_path());
+        assert_eq!(Some("mp3".as_ref()), track_path.extension());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let base_directory = Path::new("/base");
+        let resolve = |path: &str| TrackPath::from(path).resolve(base_directory);
+        assert_eq!(Ok(TrackPath::from("/base/track")), resolve("track"));
+        assert_eq!(Ok(TrackPath::from("/base/../track")), resolve("../track"));
+        assert_eq!(Ok(TrackPath::from("/track")), resolve("/track"));
+        assert_eq!(
+            Ok(TrackPath::from("track")),
+            TrackPath::from("track").resolve(Path::new(""))
+        );
+    }
+
+    #[test]
+    fn test_expand() {
+        std::env::set_var("TRACK_STORE_TEST_DIRECTORY", "/music");
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(Ok(format!("{}/track", home)), expand("~/track"));
+        assert_eq!(Ok("~track".to_string()), expand("~track"));
+        assert_eq!(
+            Ok("/music/track".to_string()),
+            expand("$TRACK_STORE_TEST_DIRECTORY/track")
+        );
+        assert_eq!(
+            Ok("/music_1/track".to_string()),
+            expand("${TRACK_STORE_TEST_DIRECTORY}_1/track")
+        );
+        assert_eq!(Ok("$/track$".to_string()), expand("$/track$"));
+        assert_eq!(Ok("${/track".to_string()), expand("${/track"));
+        assert!(matches!(
+            expand("$TRACK_STORE_TEST_NOT_SET/track"),
+            Err(Error::LoadError(_))
+        ));
+    }
 }
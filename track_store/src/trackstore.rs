@@ -2,41 +2,311 @@
 #![warn(missing_doc_code_examples)]
 #![forbid(unsafe_code)]
 
+use crate::action::Action;
 use crate::error::Error;
+use crate::format::Format;
 use crate::id::Id;
+use crate::playlist::Playlist;
+#[cfg(feature = "sqlite")]
+use crate::sqlitedatabase::SqliteDatabase;
+use crate::trackentry::TrackEntry;
 use crate::trackpath::TrackPath;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+use std::path::{Path, PathBuf};
 
 /// The [`TrackStore`](crate::TrackStore) trait.
 ///
-/// Enables to get the [`TrackPath`](crate::TrackPath) of a track if there is a path found for the
-/// given [`Id`](crate::Id).
+/// Enables to get the [`TrackEntry`](crate::TrackEntry), the [`Playlist`](crate::Playlist) or the
+/// [`TrackPath`](crate::TrackPath) of a track if there is one found for the given [`Id`](crate::Id).
+/// An [`Id`](crate::Id) can also belong to a control card, which has an [`Action`](crate::Action) instead of a playlist.
 pub trait TrackStore {
     /// Get the [`TrackPath`](crate::TrackPath) for the supplied [`Id`](crate::Id) or [`None`](std::option::Option::None)
     /// if there is no path for the supplied id.
-    fn get_path(&self, id: &Id) -> Option<&TrackPath>;
+    ///
+    /// If the id has a [`Playlist`](crate::Playlist), the first track of the playlist is returned.
+    fn get_path(&self, id: &Id) -> Option<&TrackPath> {
+        self.get_playlist(id)
+            .and_then(|playlist| playlist.tracks().first())
+    }
+
+    /// Get the [`Playlist`](crate::Playlist) for the supplied [`Id`](crate::Id) or [`None`](std::option::Option::None)
+    /// if there is no playlist for the supplied id.
+    fn get_playlist(&self, id: &Id) -> Option<&Playlist> {
+        self.get_entry(id).map(TrackEntry::playlist)
+    }
+
+    /// Get the [`TrackEntry`](crate::TrackEntry), i.e. the playlist with its title and the options of its playback,
+    /// for the supplied [`Id`](crate::Id) or [`None`](std::option::Option::None) if there is no playlist for the
+    /// supplied id.
+    fn get_entry(&self, id: &Id) -> Option<&TrackEntry>;
+
+    /// Get the [`Action`](crate::Action) for the supplied [`Id`](crate::Id) or [`None`](std::option::Option::None)
+    /// if the supplied id is not a control card.
+    fn get_action(&self, id: &Id) -> Option<&Action>;
+
+    /// Returns `true` if the [`TrackPath`](crate::TrackPath) is one of the resolved tracks of any playlist.
+    fn contains_track(&self, track_path: &TrackPath) -> bool;
+}
+
+/// The [`MutableTrackStore`](crate::MutableTrackStore) trait.
+///
+/// Enables to change the [`TrackStore`](crate::TrackStore) and to save it back to a yaml string or any other
+/// [`Format`](crate::Format).
+/// The order of the entries is preserved, new entries are appended.
+pub trait MutableTrackStore: TrackStore {
+    /// Inserts the [`Playlist`](crate::Playlist) for the supplied [`Id`](crate::Id), replacing a previous entry of the id.
+    ///
+    /// The inserted entry has no title and no options.
+    /// The playlist is resolved like the loaded ones and directories are replaced by the audio files they contain.
+    /// A store loaded from an SQLite database writes the entry to the database immediately.
+    /// Returns a [`LoadError`](crate::Error::LoadError) if a path cannot be resolved or a directory cannot be read
+    /// and a [`SaveError`](crate::Error::SaveError) if the entry cannot be written to the database,
+    /// the store is unchanged in these cases.
+    fn insert(&mut self, id: Id, playlist: Playlist) -> Result<(), Error>;
+
+    /// Removes the entry of the supplied [`Id`](crate::Id).
+    ///
+    /// Returns `true` if there was an entry for the id.
+    /// Returns a [`SaveError`](crate::Error::SaveError) if the entry cannot be removed from the SQLite database
+    /// backing the store, the store is unchanged in this case.
+    fn remove(&mut self, id: &Id) -> Result<bool, Error>;
+
+    /// Saves the [`TrackStore`](crate::TrackStore) to a yaml [String](std::string::String) that can be [`load`](crate::load)ed again.
+    ///
+    /// Returns a [`SaveError`](crate::Error::SaveError) if the store cannot be serialized.
+    fn save(&self) -> Result<String, Error> {
+        self.save_as(Format::Yaml)
+    }
+
+    /// Saves the [`TrackStore`](crate::TrackStore) to a [String](std::string::String) in the given
+    /// [`Format`](crate::Format) that can be loaded again with [`load_as`](crate::load_as).
+    ///
+    /// Playlists are saved as they were loaded or inserted, i.e. directories, relative paths, `~` and environment
+    /// variables are kept.
+    /// Returns a [`SaveError`](crate::Error::SaveError) if the store cannot be serialized,
+    /// e.g. a control card as CSV.
+    fn save_as(&self, format: Format) -> Result<String, Error>;
 }
 
 /// Load a [`TrackStore`](crate::TrackStore) from the supplied yaml [String](std::string::String).
 ///
+/// Relative paths are kept relative, i.e. they are resolved against the working directory of the process.
+/// See [`load_with_base_directory`](crate::load_with_base_directory) for the resolution of the paths.
+///
+/// Returns a [`LoadError`](crate::Error::LoadError) if the store cannot be loaded. Most likely the yaml string is
+/// ill-formed or a directory cannot be read.
+pub fn load(yaml_string: &str) -> Result<impl MutableTrackStore, Error> {
+    load_with_base_directory(yaml_string, Path::new(""))
+}
+
+/// Load a [`TrackStore`](crate::TrackStore) from the supplied yaml [String](std::string::String) whose relative paths
+/// are resolved against the `base_directory`, usually the directory of the yaml file.
+///
+/// A leading `~` of a path is expanded to the home directory and `$NAME` or `${NAME}` to the value of the
+/// environment variable, the paths are saved as they were written though.
+/// Directories in the playlists are replaced by the audio files they contain.
+/// Inserted playlists are resolved the same way.
+///
 /// Returns a [`LoadError`](crate::Error::LoadError) if the store cannot be loaded. Most likely the yaml string is
-/// ill-formed.
-pub fn load(yaml_string: &str) -> Result<impl TrackStore, Error> {
-    let track_store_result: serde_yaml::Result<TrackStoreImplementation> =
-        serde_yaml::from_str(yaml_string);
-    match track_store_result {
-        Err(error) => Err(Error::LoadError(error.to_string())),
-        Ok(track_store) => Ok(track_store),
+/// ill-formed, an environment variable is not set or a directory cannot be read.
+pub fn load_with_base_directory(
+    yaml_string: &str,
+    base_directory: &Path,
+) -> Result<impl MutableTrackStore, Error> {
+    load_as(yaml_string, Format::Yaml, base_directory)
+}
+
+/// Load a [`TrackStore`](crate::TrackStore) from the supplied [String](std::string::String) in the given
+/// [`Format`](crate::Format), e.g. selected by the extension of the file with [`from_path`](crate::Format::from_path).
+///
+/// The paths are resolved like by [`load_with_base_directory`](crate::load_with_base_directory).
+///
+/// Returns a [`LoadError`](crate::Error::LoadError) if the store cannot be loaded. Most likely the string is
+/// ill-formed, an environment variable is not set or a directory cannot be read.
+pub fn load_as(
+    string: &str,
+    format: Format,
+    base_directory: &Path,
+) -> Result<impl MutableTrackStore, Error> {
+    TrackStoreImplementation::new(format.deserialize(string)?, base_directory)
+}
+
+/// Load a [`TrackStore`](crate::TrackStore) from the file at `path` in the given [`Format`](crate::Format),
+/// e.g. selected by the extension of the file with [`from_path`](crate::Format::from_path).
+///
+/// The paths are resolved like by [`load_with_base_directory`](crate::load_with_base_directory).
+/// An [`Sqlite`](crate::Format::Sqlite) database must exist, e.g. created by [`migrate_to_sqlite`](crate::migrate_to_sqlite),
+/// its entries are read once and every change of the store is written to the database immediately, i.e. without
+/// rewriting the whole file. Loading a database requires the feature `sqlite`.
+/// The changes of a store loaded from any other format must be [`save`](crate::MutableTrackStore::save_as)d instead.
+///
+/// Returns a [`LoadError`](crate::Error::LoadError) if the store cannot be loaded. Most likely the file cannot be read
+/// or is ill-formed, an environment variable is not set or a directory cannot be read.
+pub fn load_file(
+    path: &Path,
+    format: Format,
+    base_directory: &Path,
+) -> Result<impl MutableTrackStore, Error> {
+    if format == Format::Sqlite {
+        return load_sqlite(path, base_directory);
     }
+    let string = std::fs::read_to_string(path)
+        .map_err(|error| Error::LoadError(format!("{}: {}", path.display(), error)))?;
+    TrackStoreImplementation::new(format.deserialize(&string)?, base_directory)
+}
+
+#[cfg(feature = "sqlite")]
+fn load_sqlite(path: &Path, base_directory: &Path) -> Result<TrackStoreImplementation, Error> {
+    let database = SqliteDatabase::open(path)?;
+    let mut track_store = TrackStoreImplementation::new(database.entries()?, base_directory)?;
+    track_store.database = Some(database);
+    Ok(track_store)
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn load_sqlite(path: &Path, _base_directory: &Path) -> Result<TrackStoreImplementation, Error> {
+    Err(Error::LoadError(format!(
+        "{}: {}",
+        path.display(),
+        SQLITE_DISABLED
+    )))
+}
+
+/// Writes the entries of the supplied [String](std::string::String) in the given [`Format`](crate::Format) to the
+/// SQLite database at `database_path`, which can then be loaded with [`load_file`](crate::load_file).
+///
+/// The database is created if it does not exist and all its previous entries are replaced at once.
+/// Writing a database requires the feature `sqlite`, otherwise a [`SaveError`](crate::Error::SaveError) is returned.
+/// The paths are written as they are in the string, i.e. relative paths are resolved against the base directory
+/// given when loading the database.
+/// Returns the number of written entries.
+///
+/// Returns a [`LoadError`](crate::Error::LoadError) if the string is ill-formed or the database cannot be opened and
+/// a [`SaveError`](crate::Error::SaveError) if the entries cannot be written.
+///
+/// # Example
+#[cfg_attr(feature = "sqlite", doc = "```rust")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```rust,ignore")]
+/// use std::path::Path;
+/// use track_store::{load_file, migrate_to_sqlite, Format, Id, TrackStore};
+///
+/// let database_path = std::env::temp_dir().join(format!("track_store_doc_{}.sqlite", std::process::id()));
+/// let migrated = migrate_to_sqlite("1234: path/to/track", Format::Yaml, &database_path).unwrap();
+/// assert_eq!(1, migrated);
+///
+/// let track_store = load_file(&database_path, Format::Sqlite, Path::new("/music")).unwrap();
+/// let track_path = track_store.get_path(&Id::from("1234")).unwrap();
+/// assert_eq!(Path::new("/music/path/to/track"), track_path.as_path());
+/// # std::fs::remove_file(&database_path).unwrap();
+/// ```
+pub fn migrate_to_sqlite(
+    string: &str,
+    format: Format,
+    database_path: &Path,
+) -> Result<usize, Error> {
+    let entries = format.deserialize(string)?;
+    write_sqlite(database_path, &entries)?;
+    Ok(entries.len())
+}
+
+#[cfg(feature = "sqlite")]
+fn write_sqlite(database_path: &Path, entries: &IndexMap<Id, Entry>) -> Result<(), Error> {
+    SqliteDatabase::create(database_path)?.replace_all(entries)
 }
 
+#[cfg(not(feature = "sqlite"))]
+fn write_sqlite(database_path: &Path, _entries: &IndexMap<Id, Entry>) -> Result<(), Error> {
+    Err(Error::SaveError(format!(
+        "{}: {}",
+        database_path.display(),
+        SQLITE_DISABLED
+    )))
+}
+
+/// The error message of SQLite databases without the feature `sqlite`.
+#[cfg(not(feature = "sqlite"))]
+const SQLITE_DISABLED: &str =
+    "SQLite databases are not supported, track_store is built without the feature sqlite";
+
+struct TrackStoreImplementation {
+    entries: IndexMap<Id, Entry>,
+    base_directory: PathBuf,
+    #[cfg(feature = "sqlite")]
+    database: Option<SqliteDatabase>,
+}
+
+impl TrackStoreImplementation {
+    /// Creates the store of the `entries` and resolves their playlists against the `base_directory`.
+    fn new(mut entries: IndexMap<Id, Entry>, base_directory: &Path) -> Result<Self, Error> {
+        for entry in entries.values_mut() {
+            if let Entry::Track(track_entry) = entry {
+                track_entry.resolve_tracks(base_directory)?;
+            }
+        }
+        Ok(TrackStoreImplementation {
+            entries,
+            base_directory: base_directory.to_path_buf(),
+            #[cfg(feature = "sqlite")]
+            database: None,
+        })
+    }
+}
+
+/// The value of an [`Id`](crate::Id) in the yaml string.
 #[derive(Serialize, Deserialize)]
-struct TrackStoreImplementation(HashMap<Id, TrackPath>);
+#[serde(untagged)]
+pub(crate) enum Entry {
+    Action(Action),
+    Track(TrackEntry),
+}
 
 impl TrackStore for TrackStoreImplementation {
-    fn get_path(&self, id: &Id) -> Option<&TrackPath> {
-        self.0.get(id)
+    fn get_entry(&self, id: &Id) -> Option<&TrackEntry> {
+        match self.entries.get(id) {
+            Some(Entry::Track(track_entry)) => Some(track_entry),
+            _ => None,
+        }
+    }
+
+    fn get_action(&self, id: &Id) -> Option<&Action> {
+        match self.entries.get(id) {
+            Some(Entry::Action(action)) => Some(action),
+            _ => None,
+        }
+    }
+
+    fn contains_track(&self, track_path: &TrackPath) -> bool {
+        self.entries.values().any(|entry| match entry {
+            Entry::Track(track_entry) => track_entry.playlist().tracks().contains(track_path),
+            Entry::Action(_) => false,
+        })
+    }
+}
+
+impl MutableTrackStore for TrackStoreImplementation {
+    fn insert(&mut self, id: Id, mut playlist: Playlist) -> Result<(), Error> {
+        playlist.resolve_tracks(&self.base_directory)?;
+        let entry = Entry::Track(TrackEntry::from(playlist));
+        #[cfg(feature = "sqlite")]
+        if let Some(database) = &self.database {
+            database.upsert(&id, &entry)?;
+        }
+        self.entries.insert(id, entry);
+        Ok(())
+    }
+
+    fn remove(&mut self, id: &Id) -> Result<bool, Error> {
+        #[cfg(feature = "sqlite")]
+        if let Some(database) = &self.database {
+            database.delete(id)?;
+        }
+        Ok(self.entries.shift_remove(id).is_some())
+    }
+
+    fn save_as(&self, format: Format) -> Result<String, Error> {
+        format.serialize(&self.entries)
     }
 }
 
@@ -45,16 +315,22 @@ impl TrackStore for TrackStoreImplementation {
 mod tests {
 
     use super::*;
+    use rfid_value::RfidValue;
 
     #[test]
     fn test_serialize() {
-        let mut map = HashMap::new();
+        let mut map = IndexMap::new();
         let id = Id::from("1234");
         let path = TrackPath::from("path/to/track");
-        map.insert(id, path);
+        map.insert(id, Entry::Track(TrackEntry::from(Playlist::from(path))));
 
-        let track_store = TrackStoreImplementation(map);
-        let yaml = serde_yaml::to_string(&track_store).unwrap();
+        let track_store = TrackStoreImplementation {
+            entries: map,
+            base_directory: PathBuf::from("/base"),
+            #[cfg(feature = "sqlite")]
+            database: None,
+        };
+        let yaml = track_store.save().unwrap();
         let expected_string = "---
 \"1234\": path/to/track";
         assert_eq!(expected_string, yaml);
@@ -82,6 +358,114 @@ mod tests {
         assert!(track_path.is_none());
     }
 
+    #[test]
+    fn test_deserialize_playlist() {
+        let yaml = "1234:
+  - path/to/track
+  - path/to/track_2";
+        let track_store = load(yaml).unwrap();
+        let id = "1234".into();
+        let playlist = track_store.get_playlist(&id).unwrap();
+        let expected_tracks = [
+            TrackPath::from("path/to/track"),
+            TrackPath::from("path/to/track_2"),
+        ];
+        assert_eq!(&expected_tracks, playlist.tracks());
+        assert_eq!(Some(&expected_tracks[0]), track_store.get_path(&id));
+
+        let id = "4".into();
+        assert!(track_store.get_playlist(&id).is_none());
+    }
+
+    #[test]
+    fn test_deserialize_action() {
+        let yaml = "1234: path/to/track
+5678:
+  action: stop
+9012:
+  action: sleep_timer
+  minutes: 30";
+        let track_store = load(yaml).unwrap();
+        let id = "5678".into();
+        assert_eq!(Some(&Action::Stop), track_store.get_action(&id));
+        assert!(track_store.get_playlist(&id).is_none());
+        assert!(track_store.get_path(&id).is_none());
+
+        let id = "9012".into();
+        assert_eq!(
+            Some(&Action::SleepTimer { minutes: 30 }),
+            track_store.get_action(&id)
+        );
+
+        let id = "1234".into();
+        assert!(track_store.get_action(&id).is_none());
+        assert!(track_store.get_playlist(&id).is_some());
+
+        // an entry is never both a control card and a track
+        assert!(load("5678:\n  action: stop\n  path: path/to/track").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_entry() {
+        let yaml = "1234:
+  path: path/to/track
+  title: Track
+  start: 5
+  loop: true
+5678: path/to/track_2";
+        let mut track_store = load(yaml).unwrap();
+        let entry = track_store.get_entry(&"1234".into()).unwrap();
+        assert_eq!(Some("Track"), entry.title());
+        assert_eq!(Some(std::time::Duration::from_secs(5)), entry.start());
+        assert!(entry.loops());
+        assert_eq!(
+            Some(&"path/to/track".into()),
+            track_store.get_path(&"1234".into())
+        );
+        let entry = track_store.get_entry(&"5678".into()).unwrap();
+        assert_eq!(None, entry.title());
+
+        // the options are kept when saved, inserted entries have none
+        track_store
+            .insert("5678".into(), TrackPath::from("path/to/other").into())
+            .unwrap();
+        let expected_yaml = "---
+\"1234\":
+  path: path/to/track
+  title: Track
+  start: 5.0
+  loop: true
+\"5678\": path/to/other";
+        assert_eq!(Ok(expected_yaml.to_string()), track_store.save());
+    }
+
+    #[test]
+    fn test_load_notations() {
+        let yaml = "12345678: path/to/track
+em4100:0A00A1B2C3: path/to/track_2
+wiegand26:1,2: path/to/track_3";
+        let mut track_store = load(yaml).unwrap();
+        let id = RfidValue::from(12_345_678).into();
+        assert_eq!(Some(&"path/to/track".into()), track_store.get_path(&id));
+        let id = RfidValue::from(0x00A1_B2C3).into();
+        assert_eq!(Some(&"path/to/track_2".into()), track_store.get_path(&id));
+        let id = RfidValue::from_wiegand26(1, 2).into();
+        assert_eq!(Some(&"path/to/track_3".into()), track_store.get_path(&id));
+
+        // the ids are saved in the notation they were loaded
+        track_store
+            .insert(
+                "hex:00010002".into(),
+                TrackPath::from("path/to/other").into(),
+            )
+            .unwrap();
+        let expected_yaml = "---
+\"12345678\": path/to/track
+\"em4100:0A00A1B2C3\": path/to/track_2
+\"wiegand26:1,2\": path/to/other";
+        assert_eq!(Ok(expected_yaml.to_string()), track_store.save());
+    }
+
     #[test]
     fn test_load_error() {
         let yaml = "error";
@@ -97,4 +481,126 @@ mod tests {
             Ok(value) => assert!(false, "Expected error found value"),
         }
     }
+
+    #[test]
+    fn test_insert_remove_and_save() {
+        let yaml = "1234: path/to/track
+5678:
+  action: stop
+9012:
+  - path/to/track_2
+  - path/to/track_3";
+        let mut track_store = load(yaml).unwrap();
+
+        // existing entries keep their position, new entries are appended
+        track_store
+            .insert("1234".into(), TrackPath::from("path/to/other").into())
+            .unwrap();
+        track_store
+            .insert("0000".into(), TrackPath::from("path/to/new").into())
+            .unwrap();
+        assert_eq!(
+            Some(&TrackPath::from("path/to/new")),
+            track_store.get_path(&"0000".into())
+        );
+        assert_eq!(Ok(true), track_store.remove(&"5678".into()));
+        assert_eq!(Ok(false), track_store.remove(&"5678".into()));
+
+        let expected_yaml = "---
+\"1234\": path/to/other
+\"9012\":
+  - path/to/track_2
+  - path/to/track_3
+\"0000\": path/to/new";
+        assert_eq!(Ok(expected_yaml.to_string()), track_store.save());
+        assert!(load(&track_store.save().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_load_with_base_directory() {
+        std::env::set_var("TRACK_STORE_TEST_MUSIC", "/music");
+        let yaml = "1234: track
+5678:
+  - ../other/track
+  - /absolute/track
+  - $TRACK_STORE_TEST_MUSIC/track";
+        let mut track_store = load_with_base_directory(yaml, Path::new("/base/tracks")).unwrap();
+        assert_eq!(
+            Some(&TrackPath::from("/base/tracks/track")),
+            track_store.get_path(&"1234".into())
+        );
+        let expected_tracks = [
+            TrackPath::from("/base/tracks/../other/track"),
+            TrackPath::from("/absolute/track"),
+            TrackPath::from("/music/track"),
+        ];
+        assert_eq!(
+            &expected_tracks,
+            track_store.get_playlist(&"5678".into()).unwrap().tracks()
+        );
+
+        // inserted paths are resolved as well, but all paths are saved as they were written
+        track_store
+            .insert("0000".into(), TrackPath::from("new").into())
+            .unwrap();
+        assert_eq!(
+            Some(&TrackPath::from("/base/tracks/new")),
+            track_store.get_path(&"0000".into())
+        );
+        let expected_yaml = String::from("---\n\"1234\": track\n\"5678\":\n  - \"../other/track\"\n  - /absolute/track\n  - $TRACK_STORE_TEST_MUSIC/track\n\"0000\": new");
+        assert_eq!(Ok(expected_yaml), track_store.save());
+
+        assert!(matches!(
+            load_with_base_directory("1234: $TRACK_STORE_TEST_UNSET", Path::new("")),
+            Err(Error::LoadError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn test_load_file_sqlite() {
+        let path = std::env::temp_dir().join(format!(
+            "track_store_load_file_{}.sqlite",
+            std::process::id()
+        ));
+        let yaml = "1234: track\n5678:\n  action: stop";
+        assert_eq!(Ok(2), migrate_to_sqlite(yaml, Format::Yaml, &path));
+
+        let mut track_store = load_file(&path, Format::Sqlite, Path::new("/base")).unwrap();
+        assert_eq!(
+            Some(&TrackPath::from("/base/track")),
+            track_store.get_path(&"1234".into())
+        );
+        assert_eq!(Some(&Action::Stop), track_store.get_action(&"5678".into()));
+
+        // the changes are written to the database immediately
+        track_store
+            .insert("0000".into(), TrackPath::from("new").into())
+            .unwrap();
+        assert_eq!(Ok(true), track_store.remove(&"5678".into()));
+        let track_store = load_file(&path, Format::Sqlite, Path::new("/base")).unwrap();
+        assert_eq!(
+            Ok("---\n\"1234\": track\n\"0000\": new".to_string()),
+            track_store.save()
+        );
+        assert!(track_store.get_action(&"5678".into()).is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_load_file() {
+        let path =
+            std::env::temp_dir().join(format!("track_store_load_file_{}.json", std::process::id()));
+        std::fs::write(&path, "{\"1234\": \"track\"}").unwrap();
+        let track_store = load_file(&path, Format::Json, Path::new("/base")).unwrap();
+        assert_eq!(
+            Some(&TrackPath::from("/base/track")),
+            track_store.get_path(&"1234".into())
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            load_file(&path, Format::Json, Path::new("")),
+            Err(Error::LoadError(_))
+        ));
+    }
 }
@@ -0,0 +1,509 @@
+#![warn(missing_docs)]
+#![warn(missing_doc_code_examples)]
+#![forbid(unsafe_code)]
+
+use crate::error::Error;
+use crate::format::{CsvRecord, Format};
+use crate::id::Id;
+use crate::playlist::read_audio_files;
+use crate::problem::Problem;
+use crate::trackentry::TrackEntry;
+use crate::trackpath::TrackPath;
+use crate::trackstore::Entry;
+
+use indexmap::IndexMap;
+use rfid_value::RfidValue;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_yaml::Value;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Prefix of the Wiegand 26 notation of an [`RfidValue`](rfid_value::RfidValue).
+const WIEGAND26_PREFIX: &str = "wiegand26:";
+
+/// The entries of the string in their order, including entries with duplicate ids.
+struct Entries(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for Entries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = Entries;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Entries, M::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// Validates the yaml string of a [`TrackStore`](crate::TrackStore) and returns all problems found.
+///
+/// In contrast to [`load`](crate::load), which only fails for ill-formed yaml strings, this detects
+/// * ids that are no valid [`RfidValue`](rfid_value::RfidValue) in any notation, i.e. never match a card,
+/// * duplicate ids, also in different notations of the same card,
+/// * ids in the Wiegand 26 notation, which only contains the lower 24 bits of a card, as [warning](crate::Problem::is_warning),
+/// * values that are neither a playlist nor an action and invalid options of track entries, e.g. negative offsets,
+/// * entries with both a path and an action,
+/// * tracks that do not exist or cannot be read and directories without audio files,
+/// * environment variables of paths that are not set.
+///
+/// The paths are resolved against the `base_directory` like by [`load_with_base_directory`](crate::load_with_base_directory).
+/// Every readable track is passed to `check_track`, e.g. in order to check that it can be decoded,
+/// the returned error message is reported as problem.
+/// The problems are reported in the order of the entries with the line of their id.
+///
+/// # Example
+/// ```rust
+/// use std::path::Path;
+/// use track_store::validate;
+///
+/// let yaml_string = "1234: path/to/track\nabc: path/to/track";
+/// let problems = validate(yaml_string, Path::new(""), |_| Ok(()));
+/// assert_eq!(3, problems.len());
+/// assert_eq!("line 1: track path/to/track does not exist", problems[0].to_string());
+/// ```
+pub fn validate<F>(yaml_string: &str, base_directory: &Path, check_track: F) -> Vec<Problem>
+where
+    F: FnMut(&TrackPath) -> Result<(), String>,
+{
+    validate_as(yaml_string, Format::Yaml, base_directory, check_track)
+}
+
+/// Validates the string of a [`TrackStore`](crate::TrackStore) in the given [`Format`](crate::Format) like
+/// [`validate`](crate::validate) and returns all problems found.
+///
+/// The lines of the ids are only known for YAML and CSV, where the line of the first row of an id is reported.
+/// Rows of the same id in CSV are its playlist, whereas rows of different notations of the same card are duplicates.
+pub fn validate_as<F>(
+    string: &str,
+    format: Format,
+    base_directory: &Path,
+    mut check_track: F,
+) -> Vec<Problem>
+where
+    F: FnMut(&TrackPath) -> Result<(), String>,
+{
+    let (entries, mut lines) = match parse(string, format) {
+        Ok(parsed) => parsed,
+        Err(problem) => return vec![problem],
+    };
+    // the lines are only known if every entry was found
+    if lines.len() != entries.0.len() {
+        lines.clear();
+    }
+    let line_of = |index: usize| lines.get(index).copied();
+    let mut problems = Vec::new();
+    let mut ids: HashMap<Id, (usize, String)> = HashMap::new();
+    for (index, (key, value)) in entries.0.into_iter().enumerate() {
+        let line = line_of(index);
+        if key.parse::<RfidValue>().is_err() {
+            problems.push(Problem::new(
+                line,
+                format!(
+                    "id \"{}\" is no valid RFID value, it never matches a card",
+                    key
+                ),
+            ));
+        } else if key.starts_with(WIEGAND26_PREFIX) {
+            problems.push(Problem::warning(
+                line,
+                format!(
+                    "id \"{}\" only contains the lower 24 bits, it does not match cards with a greater number than 16777215, prefer the decimal number of the card",
+                    key
+                ),
+            ));
+        }
+        if let Some((duplicate_index, duplicate_key)) = ids.get(&Id::from(key.clone())) {
+            let duplicate = match line_of(*duplicate_index) {
+                Some(duplicate_line) => format!("line {}", duplicate_line),
+                None => format!("id \"{}\"", duplicate_key),
+            };
+            problems.push(Problem::new(
+                line,
+                format!("id \"{}\" is a duplicate of {}", key, duplicate),
+            ));
+        }
+        ids.entry(Id::from(key.clone()))
+            .or_insert_with(|| (index, key.clone()));
+        if is_extended(&value) && has_key(&value, "action") {
+            problems.push(Problem::new(
+                line,
+                format!(
+                    "entry of id \"{}\" has both a path and an action, it is either a track or a control card",
+                    key
+                ),
+            ));
+            continue;
+        }
+        match serde_yaml::from_value::<Entry>(value.clone()) {
+            Ok(Entry::Action(_)) => {}
+            Ok(Entry::Track(track_entry)) => {
+                for track_path in track_entry.playlist().entries() {
+                    problems.extend(
+                        validate_track_path(track_path, base_directory, &mut check_track)
+                            .into_iter()
+                            .map(|message| Problem::new(line, message)),
+                    );
+                }
+            }
+            Err(_) if is_extended(&value) => {
+                let error = TrackEntry::from_extended(value).err().unwrap_or_default();
+                problems.push(Problem::new(
+                    line,
+                    format!("track entry of id \"{}\" is invalid: {}", key, error),
+                ))
+            }
+            Err(_) => problems.push(Problem::new(
+                line,
+                format!(
+                    "value of id \"{}\" is neither a playlist nor an action",
+                    key
+                ),
+            )),
+        }
+    }
+    problems
+}
+
+/// Returns `true` if the `value` is meant as [`TrackEntry`] with options, i.e. a map with a path.
+fn is_extended(value: &Value) -> bool {
+    has_key(value, "path")
+}
+
+/// Returns `true` if the `value` is a map with the `key`.
+fn has_key(value: &Value, key: &str) -> bool {
+    match value {
+        Value::Mapping(mapping) => mapping.contains_key(&Value::String(key.to_string())),
+        _ => false,
+    }
+}
+
+/// Returns the messages of the problems of the track or the tracks of the directory.
+fn validate_track_path<F>(
+    track_path: &TrackPath,
+    base_directory: &Path,
+    check_track: &mut F,
+) -> Vec<String>
+where
+    F: FnMut(&TrackPath) -> Result<(), String>,
+{
+    let track_path = match track_path.resolve(base_directory) {
+        Ok(track_path) => track_path,
+        Err(error) => return vec![message(error)],
+    };
+    if !track_path.exists() {
+        return vec![format!("track {} does not exist", track_path)];
+    }
+    if !track_path.is_dir() {
+        return validate_track(&track_path, check_track)
+            .into_iter()
+            .collect();
+    }
+    match read_audio_files(&track_path) {
+        Err(error) => vec![message(error)],
+        Ok(tracks) if tracks.is_empty() => {
+            vec![format!("directory {} contains no audio files", track_path)]
+        }
+        Ok(tracks) => tracks
+            .iter()
+            .filter_map(|track| validate_track(track, check_track))
+            .collect(),
+    }
+}
+
+fn validate_track<F>(track_path: &TrackPath, check_track: &mut F) -> Option<String>
+where
+    F: FnMut(&TrackPath) -> Result<(), String>,
+{
+    if let Err(error) = std::fs::File::open(track_path) {
+        return Some(format!("track {} cannot be read: {}", track_path, error));
+    }
+    check_track(track_path)
+        .err()
+        .map(|error| format!("track {} cannot be played: {}", track_path, error))
+}
+
+/// Parses the entries including duplicate ids and the lines of the ids, or returns the syntax error as problem.
+fn parse(string: &str, format: Format) -> Result<(Entries, Vec<usize>), Problem> {
+    match format {
+        Format::Yaml => match serde_yaml::from_str(string) {
+            Ok(entries) => Ok((entries, key_lines(string))),
+            Err(error) => Err(syntax_problem(
+                error.location().map(|location| location.line()),
+                error.to_string(),
+            )),
+        },
+        Format::Toml => toml::from_str(string)
+            .map(|entries| (entries, Vec::new()))
+            .map_err(|error| {
+                // the line of the toml error starts at 0
+                syntax_problem(
+                    error.line_col().map(|(line, _)| line + 1),
+                    error.to_string(),
+                )
+            }),
+        Format::Json => serde_json::from_str(string)
+            .map(|entries| (entries, Vec::new()))
+            .map_err(|error| syntax_problem(Some(error.line()), error.to_string())),
+        Format::Csv => parse_csv(string).map_err(|error| {
+            let line = error.position().map(|position| position.line() as usize);
+            Problem::new(line, error.to_string())
+        }),
+        Format::Sqlite => Err(Problem::new(
+            None,
+            "An SQLite database cannot be validated as string, validate its entries saved as JSON instead"
+                .to_string(),
+        )),
+    }
+}
+
+/// Groups the rows of the CSV string by their id, a playlist for every id with the line of its first row.
+fn parse_csv(string: &str) -> Result<(Entries, Vec<usize>), csv::Error> {
+    let mut reader = csv::Reader::from_reader(string.as_bytes());
+    let headers = reader.headers()?.clone();
+    let mut rows: IndexMap<String, (usize, Vec<Value>)> = IndexMap::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record
+            .position()
+            .map_or(0, |position| position.line() as usize);
+        let csv_record: CsvRecord = record.deserialize(Some(&headers))?;
+        rows.entry(csv_record.id)
+            .or_insert_with(|| (line, Vec::new()))
+            .1
+            .push(Value::String(csv_record.path));
+    }
+    let lines = rows.values().map(|(line, _)| *line).collect();
+    let entries = rows
+        .into_iter()
+        .map(|(id, (_, mut paths))| match paths.len() {
+            1 => (id, paths.remove(0)),
+            _ => (id, Value::Sequence(paths)),
+        })
+        .collect();
+    Ok((Entries(entries), lines))
+}
+
+/// Returns the problem of a syntax error, whose line is part of the problem and the column stays part of the message.
+fn syntax_problem(line: Option<usize>, message: String) -> Problem {
+    match line {
+        Some(line) => {
+            let suffix = format!(" at line {} column", line);
+            Problem::new(Some(line), message.replacen(&suffix, " at column", 1))
+        }
+        None => Problem::new(None, message),
+    }
+}
+
+/// Returns the message of the `error` without its variant.
+fn message(error: Error) -> String {
+    match error {
+        Error::LoadError(message) | Error::SaveError(message) => message,
+    }
+}
+
+/// Returns the numbers of the lines (starting at 1) of the top-level keys, i.e. of the ids.
+fn key_lines(yaml_string: &str) -> Vec<usize> {
+    yaml_string
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let is_indented = line.starts_with(|c: char| c.is_whitespace());
+            let is_key = line.contains(": ") || line.trim_end().ends_with(':');
+            !line.is_empty()
+                && !is_indented
+                && is_key
+                && !line.starts_with('#')
+                && !line.starts_with("---")
+                && !line.starts_with('-')
+        })
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn messages(problems: Vec<Problem>) -> Vec<String> {
+        problems.iter().map(|problem| problem.to_string()).collect()
+    }
+
+    #[test]
+    fn test_key_lines() {
+        let yaml = "---
+# comment
+1234: path/to/track
+
+\"wiegand26:1,2\":
+  - path/to/track
+  - other: value
+5678:
+  action: stop";
+        assert_eq!(vec![3, 5, 8], key_lines(yaml));
+    }
+
+    #[test]
+    fn test_validate_syntax() {
+        assert_eq!(
+            vec!["line 1: invalid type: string \"error\", expected a map at column 1"],
+            messages(validate("error", Path::new(""), |_| Ok(())))
+        );
+    }
+
+    #[test]
+    fn test_validate_ids() {
+        let yaml = "1234:
+  action: stop
+abc:
+  action: stop
+hex:000004D2:
+  action: stop
+em4100:0A0:
+  action: stop
+wiegand26:0,5678:
+  action: stop";
+        assert_eq!(
+            vec![
+                "line 3: id \"abc\" is no valid RFID value, it never matches a card",
+                "line 5: id \"hex:000004D2\" is a duplicate of line 1",
+                "line 7: id \"em4100:0A0\" is no valid RFID value, it never matches a card",
+                "line 9: warning: id \"wiegand26:0,5678\" only contains the lower 24 bits, it does not match cards with a greater number than 16777215, prefer the decimal number of the card",
+            ],
+            messages(validate(yaml, Path::new(""), |_| Ok(())))
+        );
+    }
+
+    #[test]
+    fn test_validate_entries() {
+        let yaml = "1234:
+  action: unknown
+5678: Cargo.toml
+9012:
+  - Cargo.toml
+  - src
+  - path/to/track
+3456: $TRACK_STORE_TEST_UNSET/track";
+        let mut checked_tracks = Vec::new();
+        let problems = validate(yaml, Path::new(""), |track| {
+            checked_tracks.push(track.to_string());
+            Err("unrecognized format".to_string())
+        });
+        assert_eq!(
+            vec![
+                "line 1: value of id \"1234\" is neither a playlist nor an action",
+                "line 3: track Cargo.toml cannot be played: unrecognized format",
+                "line 4: track Cargo.toml cannot be played: unrecognized format",
+                "line 4: directory src contains no audio files",
+                "line 4: track path/to/track does not exist",
+                "line 8: Environment variable TRACK_STORE_TEST_UNSET of track path $TRACK_STORE_TEST_UNSET/track is not set",
+            ],
+            messages(problems)
+        );
+        assert_eq!(vec!["Cargo.toml", "Cargo.toml"], checked_tracks);
+    }
+
+    #[test]
+    fn test_validate_track_entries() {
+        let yaml = "1234:
+  path: Cargo.toml
+  title: Cargo
+  start: 10
+  end: 5
+5678:
+  path: Cargo.toml
+  titel: Cargo
+9012:
+  path: path/to/track
+  loop: true
+3456:
+  path: Cargo.toml
+  action: stop
+7890:
+  action: stop
+  minutes: 5";
+        assert_eq!(
+            vec![
+                "line 1: track entry of id \"1234\" is invalid: end offset 5 s is not after start offset 10 s",
+                "line 6: track entry of id \"5678\" is invalid: unknown field `titel`, expected one of `path`, `title`, `description`, `start`, `end`, `volume`, `loop`, `shuffle`",
+                "line 9: track path/to/track does not exist",
+                "line 12: entry of id \"3456\" has both a path and an action, it is either a track or a control card",
+                "line 15: value of id \"7890\" is neither a playlist nor an action",
+            ],
+            messages(validate(yaml, Path::new(""), |_| Ok(())))
+        );
+    }
+
+    #[test]
+    fn test_validate_base_directory() {
+        let base_directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut checked_tracks = Vec::new();
+        let problems = validate("1234: Cargo.toml\n5678: missing", base_directory, |track| {
+            checked_tracks.push(track.clone());
+            Ok(())
+        });
+        assert_eq!(
+            vec![format!(
+                "line 2: track {} does not exist",
+                base_directory.join("missing").display()
+            )],
+            messages(problems)
+        );
+        assert_eq!(
+            vec![TrackPath::from(base_directory.join("Cargo.toml"))],
+            checked_tracks
+        );
+    }
+
+    #[test]
+    fn test_validate_formats() {
+        let validate_ids = |string: &str, format: Format| {
+            messages(validate_as(string, format, Path::new(""), |_| Ok(())))
+        };
+        assert_eq!(
+            vec!["id \"hex:000004D2\" is a duplicate of id \"1234\""],
+            validate_ids(
+                "{\"1234\": {\"action\": \"stop\"}, \"hex:000004D2\": {\"action\": \"stop\"}}",
+                Format::Json
+            )
+        );
+        assert_eq!(
+            vec!["id \"abc\" is no valid RFID value, it never matches a card"],
+            validate_ids("[abc]\naction = \"stop\"", Format::Toml)
+        );
+        assert_eq!(
+            vec![
+                "line 3: id \"abc\" is no valid RFID value, it never matches a card",
+                "line 3: directory src contains no audio files",
+            ],
+            messages(validate_as(
+                "id,path\n1234,Cargo.toml\nabc,src\n1234,Cargo.toml",
+                Format::Csv,
+                Path::new(""),
+                |_| Ok(())
+            ))
+        );
+
+        // syntax errors are reported with their line
+        let problems = validate_as("{\n\"1234\": [", Format::Json, Path::new(""), |_| Ok(()));
+        assert_eq!(Some(2), problems[0].line());
+        let problems = validate_as("\n[1234", Format::Toml, Path::new(""), |_| Ok(()));
+        assert_eq!(Some(2), problems[0].line());
+        let problems = validate_as("id,path\n1234", Format::Csv, Path::new(""), |_| Ok(()));
+        assert_eq!(Some(2), problems[0].line());
+    }
+}